@@ -0,0 +1,26 @@
+#![no_main]
+
+use h3o::CellBitmap;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let Ok(bitmap) = CellBitmap::from_bytes(bytes) else {
+        return;
+    };
+    let resolution = bitmap.resolution();
+
+    // A successfully decoded bitmap only holds valid cells, sorted, without
+    // duplicate, and found by lookups.
+    let cells = bitmap.iter().collect::<Vec<_>>();
+    assert_eq!(cells.len() as u64, bitmap.len());
+    assert!(cells.windows(2).all(|pair| pair[0] < pair[1]), "sorted set");
+    for &cell in &cells {
+        assert_eq!(cell.resolution(), resolution);
+        assert!(bitmap.contains(cell), "{cell} not found");
+    }
+
+    // And survives a round-trip.
+    let decoded =
+        CellBitmap::from_bytes(&bitmap.to_bytes()).expect("round-trip");
+    assert_eq!(decoded, bitmap, "round-trip");
+});
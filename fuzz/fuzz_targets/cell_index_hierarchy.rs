@@ -44,6 +44,14 @@ fuzz_target!(|args: Args| {
         "cellToParent"
     );
 
+    let ordinal = args.index.ordinal();
+    assert!(ordinal < args.index.resolution().cell_count(), "ordinal range");
+    assert_eq!(
+        CellIndex::from_ordinal(args.index.resolution(), ordinal),
+        Some(args.index),
+        "ordinal round-trip"
+    );
+
     // Do not generate children when the generation gap is too large (OOM risk).
     if u8::from(args.res).saturating_sub(u8::from(args.index.resolution())) < 10
     {
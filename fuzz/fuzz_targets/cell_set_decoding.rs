@@ -0,0 +1,38 @@
+#![no_main]
+
+use h3o::CellIndex;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let Ok(decoder) = CellIndex::decode_set(bytes) else {
+        return;
+    };
+    let resolution = decoder.resolution();
+
+    // Skip inputs that would produce too many indexes.
+    let Ok(compacted) = decoder.compacted().collect::<Result<Vec<_>, _>>()
+    else {
+        return;
+    };
+    if CellIndex::uncompact_size(compacted.iter().copied(), resolution)
+        > 4_000_000
+    {
+        return;
+    }
+
+    // A successfully decoded set is sorted, without duplicate, and survives a
+    // round-trip.
+    let cells = CellIndex::decode_set(bytes)
+        .expect("header")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("decode");
+    assert!(cells.windows(2).all(|pair| pair[0] < pair[1]), "sorted set");
+    assert!(cells.iter().all(|cell| cell.resolution() == resolution));
+
+    let bytes = CellIndex::encode_set(cells.iter().copied()).expect("encode");
+    let decoded = CellIndex::decode_set(&bytes)
+        .expect("header")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("decode");
+    assert_eq!(decoded, cells, "round-trip");
+});
@@ -0,0 +1,37 @@
+#![no_main]
+
+use h3o::{CellIndex, Resolution};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, arbitrary::Arbitrary)]
+pub struct Args {
+    resolution: Resolution,
+    cells: Vec<CellIndex>,
+}
+
+fuzz_target!(|args: Args| {
+    // Build an homogeneous set, without duplicate.
+    let mut cells = args
+        .cells
+        .into_iter()
+        .filter_map(|cell| cell.parent(args.resolution))
+        .collect::<Vec<_>>();
+    cells.sort_unstable();
+    cells.dedup();
+
+    let bytes = CellIndex::encode_set(cells.iter().copied()).expect("encode");
+    let decoded = CellIndex::decode_set(&bytes)
+        .expect("header")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("decode");
+    assert_eq!(decoded, cells, "round-trip");
+
+    let mut compacted = cells;
+    CellIndex::compact(&mut compacted).expect("compact");
+    let decoded = CellIndex::decode_set(&bytes)
+        .expect("header")
+        .compacted()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("decode");
+    assert_eq!(decoded, compacted, "compacted");
+});
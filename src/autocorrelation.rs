@@ -0,0 +1,380 @@
+//! Spatial autocorrelation statistics over cell values.
+//!
+//! Neighborhoods are computed once from the cell grid (rings or distance
+//! bands) and restricted to the cells with a value, so that cells with less
+//! neighbors (pentagons, edges of the study area) are handled consistently.
+//! Every weight is binary: a cell either is a neighbor or isn't.
+
+use crate::{
+    CellIndex, EARTH_RADIUS_KM, LatLng, Resolution,
+    collections::Map,
+    error::AutocorrelationError,
+    math::{mul_add, sqrt},
+};
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+/// Lower bound of the ratio between the distance of two neighboring cell
+/// centers and its average, at every resolution.
+///
+/// Used to bound the number of rings to explore for a distance band.
+const MIN_SPACING_RATIO: f64 = 0.5;
+
+/// Maximum distance between two points on Earth, in meters.
+///
+/// Wider distance bands are rejected, as they would cover every cell anyway.
+const MAX_DISTANCE_BAND_M: f64 = PI * EARTH_RADIUS_KM * 1000.;
+
+/// Definition of the neighbors of a cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum SpatialWeights {
+    /// Cells within the given grid distance (i.e. `1` for the immediate
+    /// neighbors).
+    Rings(u32),
+    /// Cells whose center is within the given distance, in meters.
+    ///
+    /// Must be positive (or zero), finite and no wider than half the Earth
+    /// circumference.
+    DistanceBand(f64),
+}
+
+/// Global Moran's I statistic, and its significance under the normality
+/// assumption.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlobalMoran {
+    /// Moran's I.
+    pub i: f64,
+    /// Expected value of I under the null hypothesis (no autocorrelation).
+    pub expected: f64,
+    /// Variance of I under the null hypothesis.
+    pub variance: f64,
+    /// Standard score of I.
+    pub z_score: f64,
+}
+
+// -----------------------------------------------------------------------------
+
+/// Spatial autocorrelation statistics over a set of cell values.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{CellIndex, SpatialAutocorrelation, SpatialWeights};
+///
+/// let center = CellIndex::try_from(0x8a1fb46622dffff)?;
+/// // A hotspot in the middle of a quiet area.
+/// let values = center.grid_disk_distances::<Vec<_>>(4).into_iter()
+///     .map(|(cell, distance)| (cell, if distance <= 1 { 10. } else { 1. }));
+/// let stats = SpatialAutocorrelation::new(values, SpatialWeights::Rings(1))?;
+///
+/// let moran = stats.morans_i().expect("non-constant values");
+/// assert!(moran.i > moran.expected);
+///
+/// let gi = stats.getis_ord_gi_star();
+/// let hottest = gi.iter().max_by(|a, b| a.1.total_cmp(&b.1)).expect("cells");
+/// assert_eq!(hottest.0, center);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpatialAutocorrelation {
+    /// Cells, sorted.
+    cells: Vec<CellIndex>,
+    values: Vec<f64>,
+    /// Indexes of the neighbors of each cell (itself excluded).
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl SpatialAutocorrelation {
+    /// Computes the neighborhoods of a set of cell values.
+    ///
+    /// Values of duplicate cells are added up.
+    ///
+    /// # Errors
+    ///
+    /// [`AutocorrelationError::ResolutionMismatch`] if the cells don't share
+    /// the same resolution, and [`AutocorrelationError::InvalidDistanceBand`]
+    /// if the distance band is negative, not finite or too wide.
+    pub fn new(
+        values: impl IntoIterator<Item = (CellIndex, f64)>,
+        weights: SpatialWeights,
+    ) -> Result<Self, AutocorrelationError> {
+        if let SpatialWeights::DistanceBand(distance) = weights
+            && !(0. ..=MAX_DISTANCE_BAND_M).contains(&distance)
+        {
+            return Err(AutocorrelationError::InvalidDistanceBand);
+        }
+
+        let mut values = values.into_iter().collect::<Vec<_>>();
+        values.sort_unstable_by_key(|&(cell, _)| cell);
+        values.dedup_by(|(cell, value), (prev_cell, prev_value)| {
+            let is_same = cell == prev_cell;
+            if is_same {
+                *prev_value += *value;
+            }
+            is_same
+        });
+        let Some(resolution) =
+            values.first().map(|(cell, _)| cell.resolution())
+        else {
+            return Ok(Self {
+                cells: Vec::new(),
+                values: Vec::new(),
+                neighbors: Vec::new(),
+            });
+        };
+        if values
+            .iter()
+            .any(|(cell, _)| cell.resolution() != resolution)
+        {
+            return Err(AutocorrelationError::ResolutionMismatch);
+        }
+
+        let (cells, values): (Vec<_>, Vec<_>) = values.into_iter().unzip();
+        let positions = cells
+            .iter()
+            .enumerate()
+            .map(|(position, &cell)| (cell, position))
+            .collect::<Map<_, _>>();
+        let neighbors = match weights {
+            SpatialWeights::DistanceBand(distance)
+                if disk_size(band_radius(resolution, distance))
+                    > cells.len() =>
+            {
+                // Sparse input: cheaper to test every pair of cells than to
+                // walk disks made of mostly missing cells.
+                let centers = cells
+                    .iter()
+                    .map(|&cell| LatLng::from(cell))
+                    .collect::<Vec<_>>();
+                band_neighbors(&centers, distance)
+            }
+            _ => cells
+                .iter()
+                .map(|&cell| {
+                    neighborhood(cell, resolution, weights)
+                        .filter_map(|neighbor| {
+                            positions.get(&neighbor).copied()
+                        })
+                        .collect()
+                })
+                .collect(),
+        };
+
+        Ok(Self {
+            cells,
+            values,
+            neighbors,
+        })
+    }
+
+    /// Returns the number of cells.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns true if there is no cell.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns the number of neighbors (with a value) of each cell, sorted by
+    /// cell.
+    pub fn neighbor_counts(
+        &self,
+    ) -> impl Iterator<Item = (CellIndex, usize)> + '_ {
+        self.cells
+            .iter()
+            .zip(&self.neighbors)
+            .map(|(&cell, neighbors)| (cell, neighbors.len()))
+    }
+
+    /// Computes the global Moran's I.
+    ///
+    /// Returns `None` when there are less than two cells, no pair of
+    /// neighbors, or if every value is the same.
+    #[must_use]
+    pub fn morans_i(&self) -> Option<GlobalMoran> {
+        let n = self.count();
+        let deviations = self.deviations();
+        let m2 = deviations.iter().map(|z| z * z).sum::<f64>();
+        // Sum of weights, S1 (symmetric weights: 2 * S0) and S2.
+        let s0 = self.neighbors.iter().map(Vec::len).sum::<usize>();
+        if n < 2. || s0 == 0 || m2 == 0. {
+            return None;
+        }
+        #[expect(clippy::cast_precision_loss, reason = "small counts")]
+        let s0 = s0 as f64;
+        let s1 = 2. * s0;
+        #[expect(clippy::cast_precision_loss, reason = "small counts")]
+        let s2 = self
+            .neighbors
+            .iter()
+            .map(|neighbors| {
+                let degree = 2 * neighbors.len();
+                (degree * degree) as f64
+            })
+            .sum::<f64>();
+
+        let cross = self.weighted_sums(&deviations);
+        let cross = deviations
+            .iter()
+            .zip(&cross)
+            .map(|(z, lag)| z * lag)
+            .sum::<f64>();
+        let i = n / s0 * cross / m2;
+
+        let expected = -1. / (n - 1.);
+        let variance = mul_add(n * n, s1, mul_add(-n, s2, 3. * s0 * s0))
+            / (mul_add(n, n, -1.) * s0 * s0)
+            - expected * expected;
+        let z_score = (i - expected) / sqrt(variance);
+
+        Some(GlobalMoran {
+            i,
+            expected,
+            variance,
+            z_score,
+        })
+    }
+
+    /// Computes the local Moran's I (Anselin's LISA) of each cell, sorted by
+    /// cell.
+    ///
+    /// Positive values denote a cell similar to its neighbors (cluster),
+    /// negative ones a cell dissimilar to its neighbors (outlier). Returns an
+    /// empty list if every value is the same.
+    #[must_use]
+    pub fn local_morans_i(&self) -> Vec<(CellIndex, f64)> {
+        let n = self.count();
+        let deviations = self.deviations();
+        let m2 = deviations.iter().map(|z| z * z).sum::<f64>() / n;
+        if self.is_empty() || m2 == 0. {
+            return Vec::new();
+        }
+
+        let lags = self.weighted_sums(&deviations);
+        self.cells
+            .iter()
+            .zip(deviations.iter().zip(&lags))
+            .map(|(&cell, (z, lag))| (cell, z / m2 * lag))
+            .collect()
+    }
+
+    /// Computes the Getis-Ord Gi* statistic of each cell, sorted by cell.
+    ///
+    /// The statistic is a standard score: high positive values denote a
+    /// cluster of high values (hotspot), low negative values a cluster of low
+    /// values (coldspot). Returns an empty list if every value is the same.
+    #[must_use]
+    pub fn getis_ord_gi_star(&self) -> Vec<(CellIndex, f64)> {
+        let n = self.count();
+        let mean = self.values.iter().sum::<f64>() / n;
+        let variance = self.deviations().iter().map(|z| z * z).sum::<f64>() / n;
+        if self.len() < 2 || variance <= 0. {
+            return Vec::new();
+        }
+        let std_dev = sqrt(variance);
+
+        // Gi* includes the cell itself in its neighborhood.
+        let sums = self.weighted_sums(&self.values);
+        self.cells
+            .iter()
+            .zip(&self.neighbors)
+            .zip(self.values.iter().zip(&sums))
+            .map(|((&cell, neighbors), (value, sum))| {
+                #[expect(clippy::cast_precision_loss, reason = "small counts")]
+                let weights = (neighbors.len() + 1) as f64;
+                let numerator = mul_add(-mean, weights, value + sum);
+                let denominator = std_dev
+                    * sqrt(mul_add(n, weights, -weights * weights) / (n - 1.));
+                (cell, numerator / denominator)
+            })
+            .collect()
+    }
+
+    /// Returns the number of cells, as a float.
+    #[expect(clippy::cast_precision_loss, reason = "small counts")]
+    const fn count(&self) -> f64 {
+        self.cells.len() as f64
+    }
+
+    /// Returns the deviations of the values from their mean.
+    fn deviations(&self) -> Vec<f64> {
+        let mean = self.values.iter().sum::<f64>() / self.count();
+        self.values.iter().map(|value| value - mean).collect()
+    }
+
+    /// Returns, for each cell, the sum of the given values of its neighbors
+    /// (i.e. the spatial lag).
+    fn weighted_sums(&self, values: &[f64]) -> Vec<f64> {
+        self.neighbors
+            .iter()
+            .map(|neighbors| neighbors.iter().map(|&j| values[j]).sum())
+            .collect()
+    }
+}
+
+/// Returns the neighbors of a cell (itself excluded).
+fn neighborhood(
+    cell: CellIndex,
+    resolution: Resolution,
+    weights: SpatialWeights,
+) -> impl Iterator<Item = CellIndex> {
+    let (radius, max_distance) = match weights {
+        SpatialWeights::Rings(k) => (k, None),
+        SpatialWeights::DistanceBand(distance) => {
+            (band_radius(resolution, distance), Some(distance))
+        }
+    };
+    let center = LatLng::from(cell);
+
+    cell.grid_disk::<Vec<_>>(radius)
+        .into_iter()
+        .filter(move |&neighbor| {
+            neighbor != cell
+                && max_distance.is_none_or(|max| {
+                    center.distance_m(LatLng::from(neighbor)) <= max
+                })
+        })
+}
+
+/// Returns, for every center, the positions of the other centers within the
+/// distance band.
+fn band_neighbors(centers: &[LatLng], distance: f64) -> Vec<Vec<usize>> {
+    centers
+        .iter()
+        .enumerate()
+        .map(|(position, center)| {
+            centers
+                .iter()
+                .enumerate()
+                .filter_map(|(other, &neighbor)| {
+                    (other != position
+                        && center.distance_m(neighbor) <= distance)
+                        .then_some(other)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the number of rings covering a distance band.
+fn band_radius(resolution: Resolution, distance: f64) -> u32 {
+    // Ring `k` is at least `k * min_spacing` away from the center.
+    let min_spacing = resolution.edge_length_m() * sqrt(3.) * MIN_SPACING_RATIO;
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "floor of a validated, non-negative distance band"
+    )]
+    let radius = (distance / min_spacing) as u32;
+    radius
+}
+
+/// Approximate number of cells in a disk of the given radius.
+fn disk_size(radius: u32) -> usize {
+    usize::try_from(3 * u64::from(radius).pow(2)).unwrap_or(usize::MAX)
+}
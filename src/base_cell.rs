@@ -108,9 +108,13 @@ impl BaseCell {
     ) -> [Face; NUM_PENT_VERTS as usize] {
         debug_assert!(self.is_pentagon(), "not a pentagon");
 
+        PENTAGON_DIRECTION_FACES[self.pentagon_rank() as usize]
+    }
+
+    /// Returns the number of pentagonal base cells preceding this one.
+    pub(crate) const fn pentagon_rank(self) -> u32 {
         let mask = (1_u128 << self.0) - 1;
-        let index = (BASE_PENTAGONS & mask).count_ones();
-        PENTAGON_DIRECTION_FACES[index as usize]
+        (BASE_PENTAGONS & mask).count_ones()
     }
 
     /// Returns the neighboring base cell in the given direction.
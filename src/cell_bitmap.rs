@@ -0,0 +1,800 @@
+//! Compressed bitmaps of cells.
+//!
+//! Cells are stored by ordinal (see `CellIndex::ordinal`), split in chunks of
+//! 2^16 consecutive ordinals (i.e. groups of neighboring cells, sharing a
+//! parent a few resolutions up). Like Roaring bitmaps, each chunk is either a
+//! sorted array of values, when sparse, or a plain bitmap, when dense.
+
+use crate::{
+    CellIndex, Resolution,
+    error::{DecodingError, ResolutionMismatch},
+    index::{read_varint, write_varint},
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::iter;
+use either::Either;
+
+/// Number of bits of an ordinal used to address a value in a chunk.
+const CHUNK_BITS: u32 = 16;
+/// Number of words of a bitmap container.
+const BITMAP_WORDS: usize = 1 << (CHUNK_BITS - u64::BITS.trailing_zeros());
+/// Maximum number of values stored in an array container.
+const ARRAY_MAX_LEN: usize = 4096;
+
+/// Magic bytes, at the start of every serialized bitmap.
+const MAGIC: [u8; 4] = *b"H3CB";
+/// Current version of the serialization format.
+const VERSION: u8 = 1;
+
+/// A compressed set of cells, all at the same resolution.
+///
+/// Cells are iterated in order, and set operations (union, intersection,
+/// cardinality, rank, …) are performed chunk by chunk.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{CellBitmap, CellIndex, Resolution};
+///
+/// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+/// let disk = CellBitmap::from_cells(Resolution::Ten, index.grid_disk::<Vec<_>>(2))?;
+/// let ring = CellBitmap::from_cells(Resolution::Ten, index.grid_ring::<Vec<_>>(2))?;
+///
+/// assert_eq!(disk.len(), 19);
+/// assert_eq!(disk.intersection(&ring)?.len(), 12);
+/// assert!(!disk.union(&ring)?.contains(index.grid_ring::<Vec<_>>(3)[0]));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellBitmap {
+    resolution: Resolution,
+    /// Non-empty chunks, sorted by key.
+    chunks: Vec<Chunk>,
+}
+
+impl CellBitmap {
+    /// Initializes an empty bitmap for cells at the given resolution.
+    #[must_use]
+    pub const fn new(resolution: Resolution) -> Self {
+        Self {
+            resolution,
+            chunks: Vec::new(),
+        }
+    }
+
+    /// Builds a bitmap from a set of cells.
+    ///
+    /// Cells coarser than `resolution` (e.g. a compacted set) are expanded
+    /// into their children, and duplicates are ignored.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if a cell is finer than `resolution`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellBitmap, CellIndex, Resolution};
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let bitmap = CellBitmap::from_cells(Resolution::Twelve, [index])?;
+    /// assert_eq!(bitmap.len(), 49);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_cells(
+        resolution: Resolution,
+        cells: impl IntoIterator<Item = CellIndex>,
+    ) -> Result<Self, ResolutionMismatch> {
+        let mut bitmap = Self::new(resolution);
+        for cell in cells {
+            if cell.resolution() == resolution {
+                bitmap.insert(cell)?;
+                continue;
+            }
+            let first =
+                cell.center_child(resolution).ok_or(ResolutionMismatch)?;
+            let start = first.ordinal();
+            bitmap.insert_range(start, start + cell.children_count(resolution));
+        }
+
+        Ok(bitmap)
+    }
+
+    /// Returns the resolution of the cells in the bitmap.
+    #[must_use]
+    pub const fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// Returns the number of cells in the bitmap.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.chunks
+            .iter()
+            .map(|chunk| chunk.container.len() as u64)
+            .sum()
+    }
+
+    /// Returns true if the bitmap contains no cell.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Returns true if the bitmap contains the given cell.
+    ///
+    /// Cells at another resolution are never contained.
+    #[must_use]
+    pub fn contains(&self, cell: CellIndex) -> bool {
+        if cell.resolution() != self.resolution {
+            return false;
+        }
+        let (key, value) = split(cell.ordinal());
+        self.chunk(key)
+            .is_some_and(|chunk| chunk.container.contains(value))
+    }
+
+    /// Adds a cell to the bitmap.
+    ///
+    /// Returns whether the cell was newly inserted.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if the cell isn't at the bitmap resolution.
+    pub fn insert(
+        &mut self,
+        cell: CellIndex,
+    ) -> Result<bool, ResolutionMismatch> {
+        if cell.resolution() != self.resolution {
+            return Err(ResolutionMismatch);
+        }
+        let (key, value) = split(cell.ordinal());
+        Ok(self.chunk_mut(key).container.insert(value))
+    }
+
+    /// Removes a cell from the bitmap.
+    ///
+    /// Returns whether the cell was present in the bitmap.
+    pub fn remove(&mut self, cell: CellIndex) -> bool {
+        if cell.resolution() != self.resolution {
+            return false;
+        }
+        let (key, value) = split(cell.ordinal());
+        let Ok(idx) = self.chunks.binary_search_by_key(&key, |chunk| chunk.key)
+        else {
+            return false;
+        };
+        let container = &mut self.chunks[idx].container;
+        let is_removed = container.remove(value);
+        if container.len() == 0 {
+            self.chunks.remove(idx);
+        }
+
+        is_removed
+    }
+
+    /// Returns the number of cells in the bitmap that are lower than or equal
+    /// to the given cell.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if the cell isn't at the bitmap resolution.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellBitmap, CellIndex, Resolution};
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let cells = index.grid_disk::<Vec<_>>(2);
+    /// let bitmap = CellBitmap::from_cells(Resolution::Ten, cells.iter().copied())?;
+    /// let last = bitmap.iter().last().expect("last cell");
+    /// assert_eq!(bitmap.rank(last)?, 19);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn rank(&self, cell: CellIndex) -> Result<u64, ResolutionMismatch> {
+        if cell.resolution() != self.resolution {
+            return Err(ResolutionMismatch);
+        }
+        let (key, value) = split(cell.ordinal());
+
+        Ok(self
+            .chunks
+            .iter()
+            .take_while(|chunk| chunk.key <= key)
+            .map(|chunk| {
+                if chunk.key == key {
+                    chunk.container.rank(value) as u64
+                } else {
+                    chunk.container.len() as u64
+                }
+            })
+            .sum())
+    }
+
+    /// Returns the `n`-th cell (0-based) of the bitmap, if any.
+    #[must_use]
+    pub fn select(&self, mut n: u64) -> Option<CellIndex> {
+        for chunk in &self.chunks {
+            let len = chunk.container.len() as u64;
+            if n < len {
+                let value = chunk
+                    .container
+                    .select(usize::try_from(n).expect("chunk index"))?;
+                return self.cell(chunk.key, value);
+            }
+            n -= len;
+        }
+
+        None
+    }
+
+    /// Returns an iterator over the cells of the bitmap, in order.
+    pub fn iter(&self) -> impl Iterator<Item = CellIndex> + '_ {
+        self.chunks.iter().flat_map(move |chunk| {
+            chunk.container.iter().map(move |value| {
+                self.cell(chunk.key, value).expect("valid ordinal")
+            })
+        })
+    }
+
+    /// Returns the union of two bitmaps.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if the bitmaps have different resolutions.
+    pub fn union(&self, other: &Self) -> Result<Self, ResolutionMismatch> {
+        if self.resolution != other.resolution {
+            return Err(ResolutionMismatch);
+        }
+        let mut chunks = Vec::with_capacity(self.chunks.len());
+        let (mut lhs, mut rhs) = (self.chunks.iter(), other.chunks.iter());
+        let (mut left, mut right) = (lhs.next(), rhs.next());
+        loop {
+            let chunk = match (left, right) {
+                (Some(a), Some(b)) if a.key == b.key => {
+                    (left, right) = (lhs.next(), rhs.next());
+                    Chunk {
+                        key: a.key,
+                        container: a.container.union(&b.container),
+                    }
+                }
+                (Some(a), Some(b)) if a.key < b.key => {
+                    left = lhs.next();
+                    a.clone()
+                }
+                (Some(a), None) => {
+                    left = lhs.next();
+                    a.clone()
+                }
+                (_, Some(b)) => {
+                    right = rhs.next();
+                    b.clone()
+                }
+                (None, None) => break,
+            };
+            chunks.push(chunk);
+        }
+
+        Ok(Self {
+            resolution: self.resolution,
+            chunks,
+        })
+    }
+
+    /// Returns the intersection of two bitmaps.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if the bitmaps have different resolutions.
+    pub fn intersection(
+        &self,
+        other: &Self,
+    ) -> Result<Self, ResolutionMismatch> {
+        if self.resolution != other.resolution {
+            return Err(ResolutionMismatch);
+        }
+        let mut chunks = Vec::new();
+        let mut rhs = other.chunks.iter().peekable();
+        for a in &self.chunks {
+            while rhs.next_if(|b| b.key < a.key).is_some() {}
+            if let Some(b) = rhs.next_if(|b| b.key == a.key)
+                && let Some(container) = a.container.intersection(&b.container)
+            {
+                chunks.push(Chunk {
+                    key: a.key,
+                    container,
+                });
+            }
+        }
+
+        Ok(Self {
+            resolution: self.resolution,
+            chunks,
+        })
+    }
+
+    /// Serializes the bitmap.
+    ///
+    /// The format (version 1) is:
+    /// - the magic bytes `H3CB`;
+    /// - the version of the format, on one byte;
+    /// - the resolution of the cells, on one byte;
+    /// - the number of chunks, as an unsigned LEB128 integer;
+    /// - for each chunk, in order:
+    ///   - the difference between its key (the ordinals of its cells divided
+    ///     by 2^16) and the key of the previous chunk (or 0 for the first
+    ///     one), as an unsigned LEB128 integer;
+    ///   - the number of cells in the chunk, minus one, as an unsigned LEB128
+    ///     integer;
+    ///   - if the chunk contains at most 4096 cells, the sorted 16 lowest bits
+    ///     of their ordinals, as little-endian `u16`. Otherwise, a bitmap of
+    ///     2^16 bits, as 1024 little-endian `u64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellBitmap, CellIndex, Resolution};
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let bitmap = CellBitmap::from_cells(Resolution::Ten, index.grid_disk::<Vec<_>>(2))?;
+    /// let bytes = bitmap.to_bytes();
+    /// assert_eq!(CellBitmap::from_bytes(&bytes)?, bitmap);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(VERSION);
+        bytes.push(self.resolution.into());
+        write_varint(&mut bytes, self.chunks.len() as u64);
+
+        let mut prev_key = 0;
+        for chunk in &self.chunks {
+            write_varint(&mut bytes, chunk.key - prev_key);
+            write_varint(&mut bytes, chunk.container.len() as u64 - 1);
+            match chunk.container {
+                Container::Array(ref values) => {
+                    for value in values {
+                        bytes.extend_from_slice(&value.to_le_bytes());
+                    }
+                }
+                Container::Bitmap { ref words, .. } => {
+                    for word in words.iter() {
+                        bytes.extend_from_slice(&word.to_le_bytes());
+                    }
+                }
+            }
+            prev_key = chunk.key;
+        }
+
+        bytes
+    }
+
+    /// Deserializes a bitmap serialized with [`Self::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// [`DecodingError`] if the input is invalid.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodingError> {
+        let mut bytes = bytes
+            .strip_prefix(&MAGIC)
+            .ok_or(DecodingError::InvalidHeader)?;
+        let [version, resolution] =
+            *take(&mut bytes, 2)?.first_chunk().expect("two bytes");
+        if version != VERSION {
+            return Err(DecodingError::UnsupportedVersion(version));
+        }
+        let resolution = Resolution::try_from(resolution)
+            .map_err(|_| DecodingError::InvalidHeader)?;
+        let count = read_varint(&mut bytes)?;
+
+        let mut bitmap = Self::new(resolution);
+        let max_key = (resolution.cell_count() - 1) >> CHUNK_BITS;
+        let mut prev_key = None;
+        for _ in 0..count {
+            let delta = read_varint(&mut bytes)?;
+            let key = match prev_key {
+                // Keys are strictly increasing.
+                Some(_) if delta == 0 => None,
+                Some(prev) => u64::checked_add(prev, delta),
+                None => Some(delta),
+            }
+            // Larger keys would overflow the ordinals.
+            .filter(|&key| key <= max_key)
+            .ok_or(DecodingError::InvalidEntry)?;
+            let len = read_varint(&mut bytes)?
+                .checked_add(1)
+                .and_then(|len| usize::try_from(len).ok())
+                .filter(|&len| len <= 1 << CHUNK_BITS)
+                .ok_or(DecodingError::InvalidEntry)?;
+
+            let container = if len <= ARRAY_MAX_LEN {
+                let values = take(&mut bytes, len * 2)?
+                    .chunks_exact(2)
+                    .map(|value| u16::from_le_bytes([value[0], value[1]]))
+                    .collect::<Vec<_>>();
+                if !values.is_sorted_by(|a, b| a < b) {
+                    return Err(DecodingError::InvalidEntry);
+                }
+                Container::Array(values)
+            } else {
+                let mut words = Box::new([0; BITMAP_WORDS]);
+                for (word, value) in words
+                    .iter_mut()
+                    .zip(take(&mut bytes, BITMAP_WORDS * 8)?.chunks_exact(8))
+                {
+                    *word =
+                        u64::from_le_bytes(value.try_into().expect("8 bytes"));
+                }
+                if count_ones(words.as_slice()) != len {
+                    return Err(DecodingError::InvalidEntry);
+                }
+                Container::Bitmap { words, len }
+            };
+
+            // The last ordinal of the chunk must be a valid cell.
+            let last = container.select(len - 1).expect("non-empty chunk");
+            if bitmap.cell(key, last).is_none() {
+                return Err(DecodingError::InvalidEntry);
+            }
+            bitmap.chunks.push(Chunk { key, container });
+            prev_key = Some(key);
+        }
+        if !bytes.is_empty() {
+            return Err(DecodingError::TrailingBytes);
+        }
+
+        Ok(bitmap)
+    }
+
+    /// Inserts every ordinal in `start..end`.
+    fn insert_range(&mut self, start: u64, end: u64) {
+        let mut start = start;
+        while start < end {
+            let (key, low) = split(start);
+            // End of the range within this chunk (inclusive).
+            let chunk_end = ((key + 1) << CHUNK_BITS).min(end);
+            let (_, high) = split(chunk_end - 1);
+            self.chunk_mut(key).container.insert_range(low, high);
+            start = chunk_end;
+        }
+    }
+
+    /// Returns the chunk with the given key, if any.
+    fn chunk(&self, key: u64) -> Option<&Chunk> {
+        self.chunks
+            .binary_search_by_key(&key, |chunk| chunk.key)
+            .ok()
+            .map(|idx| &self.chunks[idx])
+    }
+
+    /// Returns the chunk with the given key, creating it if necessary.
+    fn chunk_mut(&mut self, key: u64) -> &mut Chunk {
+        let idx = self
+            .chunks
+            .binary_search_by_key(&key, |chunk| chunk.key)
+            .unwrap_or_else(|idx| {
+                self.chunks.insert(
+                    idx,
+                    Chunk {
+                        key,
+                        container: Container::Array(Vec::new()),
+                    },
+                );
+                idx
+            });
+        &mut self.chunks[idx]
+    }
+
+    /// Returns the cell for the given chunk and value.
+    fn cell(&self, key: u64, value: u16) -> Option<CellIndex> {
+        let ordinal = (key << CHUNK_BITS) | u64::from(value);
+        CellIndex::from_ordinal(self.resolution, ordinal)
+    }
+}
+
+impl<'a> IntoIterator for &'a CellBitmap {
+    type Item = CellIndex;
+    type IntoIter = Box<dyn Iterator<Item = CellIndex> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A set of values sharing the same high bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Chunk {
+    /// High bits of the values.
+    key: u64,
+    /// Low bits of the values.
+    container: Container,
+}
+
+/// A set of 16-bit values.
+///
+/// Containers holding at most `ARRAY_MAX_LEN` values are always arrays,
+/// bitmaps otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Container {
+    /// Sorted values.
+    Array(Vec<u16>),
+    /// One bit per value.
+    Bitmap {
+        words: Box<[u64; BITMAP_WORDS]>,
+        len: usize,
+    },
+}
+
+impl Container {
+    const fn len(&self) -> usize {
+        match *self {
+            Self::Array(ref values) => values.len(),
+            Self::Bitmap { len, .. } => len,
+        }
+    }
+
+    fn contains(&self, value: u16) -> bool {
+        match *self {
+            Self::Array(ref values) => values.binary_search(&value).is_ok(),
+            Self::Bitmap { ref words, .. } => {
+                let (idx, mask) = bit(value);
+                words[idx] & mask != 0
+            }
+        }
+    }
+
+    fn insert(&mut self, value: u16) -> bool {
+        match *self {
+            Self::Array(ref mut values) => {
+                let Err(idx) = values.binary_search(&value) else {
+                    return false;
+                };
+                values.insert(idx, value);
+                if values.len() > ARRAY_MAX_LEN {
+                    *self = Self::Bitmap {
+                        words: self.to_words(),
+                        len: ARRAY_MAX_LEN + 1,
+                    };
+                }
+                true
+            }
+            Self::Bitmap {
+                ref mut words,
+                ref mut len,
+            } => {
+                let (idx, mask) = bit(value);
+                let is_new = words[idx] & mask == 0;
+                words[idx] |= mask;
+                *len += usize::from(is_new);
+                is_new
+            }
+        }
+    }
+
+    fn remove(&mut self, value: u16) -> bool {
+        match *self {
+            Self::Array(ref mut values) => {
+                let Ok(idx) = values.binary_search(&value) else {
+                    return false;
+                };
+                values.remove(idx);
+                true
+            }
+            Self::Bitmap {
+                ref mut words,
+                ref mut len,
+            } => {
+                let (idx, mask) = bit(value);
+                let is_present = words[idx] & mask != 0;
+                words[idx] &= !mask;
+                *len -= usize::from(is_present);
+                if *len <= ARRAY_MAX_LEN {
+                    *self = Self::from_words(&self.to_words());
+                }
+                is_present
+            }
+        }
+    }
+
+    /// Inserts every value in `start..=end`.
+    fn insert_range(&mut self, start: u16, end: u16) {
+        let mut words = self.to_words();
+        let (start, end) = (usize::from(start), usize::from(end));
+        for (idx, word) in words
+            .iter_mut()
+            .enumerate()
+            .take(end / 64 + 1)
+            .skip(start / 64)
+        {
+            let lo = if idx == start / 64 { start % 64 } else { 0 };
+            let hi = if idx == end / 64 { end % 64 } else { 63 };
+            *word |= (u64::MAX >> (63 - hi)) & (u64::MAX << lo);
+        }
+        *self = Self::from_words(&words);
+    }
+
+    /// Returns the number of values lower than or equal to `value`.
+    fn rank(&self, value: u16) -> usize {
+        match *self {
+            Self::Array(ref values) => values.partition_point(|&x| x <= value),
+            Self::Bitmap { ref words, .. } => {
+                let (idx, mask) = bit(value);
+                count_ones(&words[..idx])
+                    + (words[idx] & (mask | (mask - 1))).count_ones() as usize
+            }
+        }
+    }
+
+    /// Returns the `n`-th value, if any.
+    fn select(&self, mut n: usize) -> Option<u16> {
+        match *self {
+            Self::Array(ref values) => values.get(n).copied(),
+            Self::Bitmap { ref words, .. } => {
+                for (idx, &word) in words.iter().enumerate() {
+                    let count = word.count_ones() as usize;
+                    if n < count {
+                        let mut word = word;
+                        for _ in 0..n {
+                            word &= word - 1;
+                        }
+                        return Some(value(idx, word.trailing_zeros()));
+                    }
+                    n -= count;
+                }
+                None
+            }
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u16> + '_ {
+        match *self {
+            Self::Array(ref values) => Either::Left(values.iter().copied()),
+            Self::Bitmap { ref words, .. } => Either::Right(
+                words.iter().enumerate().flat_map(|(idx, &word)| {
+                    let mut word = word;
+                    iter::from_fn(move || {
+                        (word != 0).then(|| {
+                            let bit = word.trailing_zeros();
+                            word &= word - 1;
+                            value(idx, bit)
+                        })
+                    })
+                }),
+            ),
+        }
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Array(lhs), Self::Array(rhs))
+                if lhs.len() + rhs.len() <= ARRAY_MAX_LEN =>
+            {
+                let mut values = Vec::with_capacity(lhs.len() + rhs.len());
+                let (mut i, mut j) = (0, 0);
+                while i < lhs.len() && j < rhs.len() {
+                    let (a, b) = (lhs[i], rhs[j]);
+                    values.push(a.min(b));
+                    i += usize::from(a <= b);
+                    j += usize::from(b <= a);
+                }
+                values.extend_from_slice(&lhs[i..]);
+                values.extend_from_slice(&rhs[j..]);
+                Self::Array(values)
+            }
+            _ => {
+                let mut words = self.to_words();
+                for (word, other) in
+                    words.iter_mut().zip(other.to_words().iter())
+                {
+                    *word |= other;
+                }
+                Self::from_words(&words)
+            }
+        }
+    }
+
+    /// Returns the intersection of two containers, if not empty.
+    fn intersection(&self, other: &Self) -> Option<Self> {
+        let result = match (self, other) {
+            (
+                Self::Bitmap { words: lhs, .. },
+                Self::Bitmap { words: rhs, .. },
+            ) => {
+                let mut words = lhs.clone();
+                for (word, other) in words.iter_mut().zip(rhs.iter()) {
+                    *word &= other;
+                }
+                Self::from_words(&words)
+            }
+            (Self::Array(values), container)
+            | (container, Self::Array(values)) => Self::Array(
+                values
+                    .iter()
+                    .copied()
+                    .filter(|&value| container.contains(value))
+                    .collect(),
+            ),
+        };
+
+        (result.len() != 0).then_some(result)
+    }
+
+    /// Returns the values as a bitmap.
+    fn to_words(&self) -> Box<[u64; BITMAP_WORDS]> {
+        match *self {
+            Self::Array(ref values) => {
+                let mut words = Box::new([0; BITMAP_WORDS]);
+                for &value in values {
+                    let (idx, mask) = bit(value);
+                    words[idx] |= mask;
+                }
+                words
+            }
+            Self::Bitmap { ref words, .. } => words.clone(),
+        }
+    }
+
+    /// Builds a container, of the appropriate kind, from a bitmap.
+    fn from_words(words: &[u64; BITMAP_WORDS]) -> Self {
+        let len = count_ones(words);
+        if len <= ARRAY_MAX_LEN {
+            let values = Self::Bitmap {
+                words: Box::new(*words),
+                len,
+            }
+            .iter()
+            .collect();
+            Self::Array(values)
+        } else {
+            Self::Bitmap {
+                words: Box::new(*words),
+                len,
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Splits an ordinal into a chunk key and a value.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "only the lowest bits are kept"
+)]
+const fn split(ordinal: u64) -> (u64, u16) {
+    (ordinal >> CHUNK_BITS, ordinal as u16)
+}
+
+/// Returns the word index and the bit mask of a value.
+const fn bit(value: u16) -> (usize, u64) {
+    ((value >> 6) as usize, 1 << (value & 63))
+}
+
+/// Returns the value for a bit of a word.
+#[expect(clippy::cast_possible_truncation, reason = "bitmaps hold 2^16 bits")]
+const fn value(idx: usize, bit: u32) -> u16 {
+    (idx as u16) << 6 | bit as u16
+}
+
+/// Counts the bits set in a bitmap.
+fn count_ones(words: &[u64]) -> usize {
+    words.iter().map(|word| word.count_ones() as usize).sum()
+}
+
+/// Consumes `len` bytes of the input.
+const fn take<'a>(
+    bytes: &mut &'a [u8],
+    len: usize,
+) -> Result<&'a [u8], DecodingError> {
+    if bytes.len() < len {
+        return Err(DecodingError::UnexpectedEnd);
+    }
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Ok(head)
+}
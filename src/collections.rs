@@ -0,0 +1,16 @@
+//! Hash-based collections when `std` is available, ordered ones otherwise.
+
+#[cfg(feature = "std")]
+use ahash::{HashMap, HashSet};
+#[cfg(not(feature = "std"))]
+use alloc::collections::{BTreeMap, BTreeSet};
+
+#[cfg(not(feature = "std"))]
+pub type Set<K> = BTreeSet<K>;
+#[cfg(feature = "std")]
+pub type Set<K> = HashSet<K>;
+
+#[cfg(not(feature = "std"))]
+pub type Map<K, V> = BTreeMap<K, V>;
+#[cfg(feature = "std")]
+pub type Map<K, V> = HashMap<K, V>;
@@ -3,10 +3,10 @@ use super::{
     faceijk::FaceIJK, to_positive_angle,
 };
 use crate::{
-    CellIndex, EARTH_RADIUS_KM, Face, Resolution, TWO_PI,
+    CellIndex, EARTH_RADIUS_KM, EarthModel, Face, Resolution, TWO_PI,
     error::InvalidLatLng,
-    face,
-    math::{acos, asin, atan2, cos, mul_add, sin, sqrt, tan},
+    face, geodesic,
+    math::{abs, acos, asin, atan2, cos, hypot, mul_add, sin, sqrt, tan},
 };
 use core::{
     f64::consts::{FRAC_PI_2, PI},
@@ -206,6 +206,389 @@ impl LatLng {
         self.distance_km(other) * 1000.
     }
 
+    /// The distance, in kilometers, between two coordinates on the specified
+    /// Earth model.
+    ///
+    /// With [`EarthModel::Sphere`], this is the same as
+    /// [`Self::distance_km`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{EarthModel, LatLng};
+    ///
+    /// let src = LatLng::new(48.864716, 2.349014)?;
+    /// let dst = LatLng::new(31.224361, 121.469170)?;
+    ///
+    /// float_eq::assert_float_eq!(
+    ///     src.distance_km_with(dst, EarthModel::Wgs84),
+    ///     9283.246367843005,
+    ///     abs <= 1e-6
+    /// );
+    /// # Ok::<(), h3o::error::InvalidLatLng>(())
+    /// ```
+    #[must_use]
+    pub fn distance_km_with(self, other: Self, model: EarthModel) -> f64 {
+        match model {
+            EarthModel::Sphere => self.distance_km(other),
+            EarthModel::Wgs84 => self.distance_m_with(other, model) / 1000.,
+        }
+    }
+
+    /// The distance, in meters, between two coordinates on the specified
+    /// Earth model.
+    ///
+    /// With [`EarthModel::Sphere`], this is the same as [`Self::distance_m`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{EarthModel, LatLng};
+    ///
+    /// let src = LatLng::new(48.864716, 2.349014)?;
+    /// let dst = LatLng::new(31.224361, 121.469170)?;
+    ///
+    /// float_eq::assert_float_eq!(
+    ///     src.distance_m_with(dst, EarthModel::Wgs84),
+    ///     9283246.367843006,
+    ///     abs <= 1e-3
+    /// );
+    /// # Ok::<(), h3o::error::InvalidLatLng>(())
+    /// ```
+    #[must_use]
+    pub fn distance_m_with(self, other: Self, model: EarthModel) -> f64 {
+        match model {
+            EarthModel::Sphere => self.distance_m(other),
+            EarthModel::Wgs84 => geodesic::WGS84.inverse(self, other).distance,
+        }
+    }
+
+    /// The initial azimuth of the shortest path from `self` to `other` on the
+    /// specified Earth model, in degrees clockwise from the north, in
+    /// `[0, 360)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{EarthModel, LatLng};
+    ///
+    /// let src = LatLng::new(0., 0.)?;
+    /// let dst = LatLng::new(0., 10.)?;
+    ///
+    /// assert_eq!(src.azimuth_with(dst, EarthModel::Sphere), 90.);
+    /// assert_eq!(src.azimuth_with(dst, EarthModel::Wgs84), 90.);
+    /// # Ok::<(), h3o::error::InvalidLatLng>(())
+    /// ```
+    #[must_use]
+    pub fn azimuth_with(self, other: Self, model: EarthModel) -> f64 {
+        match model {
+            EarthModel::Sphere => self.initial_bearing(other),
+            EarthModel::Wgs84 => {
+                let azimuth = geodesic::WGS84.inverse(self, other).azimuth;
+                if azimuth < 0. {
+                    azimuth + 360.
+                } else {
+                    azimuth
+                }
+            }
+        }
+    }
+
+    /// The initial bearing of the great circle path from `self` to `other`, in
+    /// degrees clockwise from the north, in `[0, 360)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let src = h3o::LatLng::new(48.864716, 2.349014)?;
+    /// let dst = h3o::LatLng::new(31.224361, 121.469170)?;
+    ///
+    /// float_eq::assert_float_eq!(
+    ///     src.initial_bearing(dst),
+    ///     48.78058327875722,
+    ///     abs <= 1e-9
+    /// );
+    /// # Ok::<(), h3o::error::InvalidLatLng>(())
+    /// ```
+    #[must_use]
+    pub fn initial_bearing(self, other: Self) -> f64 {
+        let bearing = self.azimuth(&other).to_degrees();
+        if bearing < 0. {
+            bearing + 360.
+        } else {
+            bearing
+        }
+    }
+
+    /// The point reached by following the great circle starting from `self`
+    /// with the given bearing (in degrees clockwise from the north), for the
+    /// given distance (in meters).
+    ///
+    /// At the poles, the bearing is relative to the meridian of the
+    /// longitude of `self`.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidLatLng`] when the bearing or the distance is not a finite
+    /// number.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let src = h3o::LatLng::new(0., 0.)?;
+    /// let dst = src.destination(90., 111_195.)?;
+    ///
+    /// float_eq::assert_float_eq!(dst.lat(), 0., abs <= 1e-9);
+    /// float_eq::assert_float_eq!(dst.lng(), 1., abs <= 1e-5);
+    /// # Ok::<(), h3o::error::InvalidLatLng>(())
+    /// ```
+    pub fn destination(
+        self,
+        bearing: f64,
+        distance_m: f64,
+    ) -> Result<Self, InvalidLatLng> {
+        if !bearing.is_finite() {
+            return Err(InvalidLatLng::new(bearing, "infinite bearing"));
+        }
+        if !distance_m.is_finite() {
+            return Err(InvalidLatLng::new(distance_m, "infinite distance"));
+        }
+        let distance = distance_m / 1000. / EARTH_RADIUS_KM;
+        let bearing = bearing.to_radians();
+
+        // Rotate the starting point, in the plane of the great circle, toward
+        // the unit vector tangent to the path.
+        let (sin_lat, cos_lat) = (sin(self.lat), cos(self.lat));
+        let (sin_lng, cos_lng) = (sin(self.lng), cos(self.lng));
+        let (sin_bearing, cos_bearing) = (sin(bearing), cos(bearing));
+        let tangent = Vec3d::new(
+            mul_add(-sin_lat * cos_lng, cos_bearing, -sin_lng * sin_bearing),
+            mul_add(-sin_lat * sin_lng, cos_bearing, cos_lng * sin_bearing),
+            cos_lat * cos_bearing,
+        );
+        let point =
+            combine(Vec3d::from(self), cos(distance), tangent, sin(distance));
+
+        Ok(Self::from(point))
+    }
+
+    /// The point at the given fraction of the great circle path from `self`
+    /// (0) to `other` (1).
+    ///
+    /// Fractions outside of `[0, 1]` extrapolate the path.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidLatLng`] when the fraction is not a finite number, or when the
+    /// points are antipodal (the path is undefined).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let src = h3o::LatLng::new(0., 0.)?;
+    /// let dst = h3o::LatLng::new(0., 10.)?;
+    /// let mid = src.intermediate(dst, 0.5)?;
+    ///
+    /// float_eq::assert_float_eq!(mid.lat(), 0., abs <= 1e-9);
+    /// float_eq::assert_float_eq!(mid.lng(), 5., abs <= 1e-9);
+    /// # Ok::<(), h3o::error::InvalidLatLng>(())
+    /// ```
+    pub fn intermediate(
+        self,
+        other: Self,
+        fraction: f64,
+    ) -> Result<Self, InvalidLatLng> {
+        if !fraction.is_finite() {
+            return Err(InvalidLatLng::new(fraction, "infinite fraction"));
+        }
+        let (a, b) = (Vec3d::from(self), Vec3d::from(other));
+        let normal = a.cross(&b);
+        let sin_distance = sqrt(normal.dot(&normal));
+        let distance = atan2(sin_distance, a.dot(&b));
+        if distance < EPSILON {
+            return Ok(self);
+        }
+        if sin_distance < EPSILON {
+            return Err(InvalidLatLng::new(distance, "antipodal points"));
+        }
+
+        let point = combine(
+            a,
+            sin((1. - fraction) * distance) / sin_distance,
+            b,
+            sin(fraction * distance) / sin_distance,
+        );
+
+        Ok(Self::from(point))
+    }
+
+    /// The signed distance, in radians, from `self` to the great circle going
+    /// through `start` and `end`.
+    ///
+    /// The distance is positive when `self` is on the right of the path from
+    /// `start` to `end`, negative on the left. If `start` and `end` don't
+    /// define a unique great circle (same or antipodal points), this is the
+    /// distance to `start`.
+    ///
+    /// The great circle extends beyond `start` and `end`: see
+    /// [`Self::distance_to_segment_rads`] for the distance to the arc between
+    /// them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let start = h3o::LatLng::new(0., 0.)?;
+    /// let end = h3o::LatLng::new(0., 10.)?;
+    /// let point = h3o::LatLng::new(-1., 5.)?;
+    ///
+    /// float_eq::assert_float_eq!(
+    ///     point.cross_track_distance_rads(start, end),
+    ///     1_f64.to_radians(),
+    ///     abs <= 1e-12
+    /// );
+    /// # Ok::<(), h3o::error::InvalidLatLng>(())
+    /// ```
+    #[must_use]
+    pub fn cross_track_distance_rads(self, start: Self, end: Self) -> f64 {
+        let normal = Vec3d::from(start).cross(&Vec3d::from(end));
+        let norm = sqrt(normal.dot(&normal));
+        if norm < EPSILON {
+            return self.distance_rads(start);
+        }
+
+        -asin((Vec3d::from(self).dot(&normal) / norm).clamp(-1., 1.))
+    }
+
+    /// The signed distance, in kilometers, from `self` to the great circle
+    /// going through `start` and `end`.
+    ///
+    /// See [`Self::cross_track_distance_rads`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let start = h3o::LatLng::new(0., 0.)?;
+    /// let end = h3o::LatLng::new(0., 10.)?;
+    /// let point = h3o::LatLng::new(1., 5.)?;
+    ///
+    /// assert!(point.cross_track_distance_km(start, end) < 0.);
+    /// # Ok::<(), h3o::error::InvalidLatLng>(())
+    /// ```
+    #[must_use]
+    pub fn cross_track_distance_km(self, start: Self, end: Self) -> f64 {
+        self.cross_track_distance_rads(start, end) * EARTH_RADIUS_KM
+    }
+
+    /// The signed distance, in meters, from `self` to the great circle going
+    /// through `start` and `end`.
+    ///
+    /// See [`Self::cross_track_distance_rads`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let start = h3o::LatLng::new(0., 0.)?;
+    /// let end = h3o::LatLng::new(0., 10.)?;
+    /// let point = h3o::LatLng::new(-1., 5.)?;
+    ///
+    /// float_eq::assert_float_eq!(
+    ///     point.cross_track_distance_m(start, end),
+    ///     111195.05197522943,
+    ///     abs <= 1e-6
+    /// );
+    /// # Ok::<(), h3o::error::InvalidLatLng>(())
+    /// ```
+    #[must_use]
+    pub fn cross_track_distance_m(self, start: Self, end: Self) -> f64 {
+        self.cross_track_distance_km(start, end) * 1000.
+    }
+
+    /// The distance, in radians, from `self` to the great circle arc between
+    /// `start` and `end`.
+    ///
+    /// This is the absolute cross-track distance when the closest point of the
+    /// great circle lies between `start` and `end`, and the distance to the
+    /// nearest endpoint otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let start = h3o::LatLng::new(0., 0.)?;
+    /// let end = h3o::LatLng::new(0., 10.)?;
+    /// let point = h3o::LatLng::new(1., 15.)?;
+    ///
+    /// assert_eq!(point.distance_to_segment_rads(start, end), point.distance_rads(end));
+    /// # Ok::<(), h3o::error::InvalidLatLng>(())
+    /// ```
+    #[must_use]
+    pub fn distance_to_segment_rads(self, start: Self, end: Self) -> f64 {
+        let (a, b) = (Vec3d::from(start), Vec3d::from(end));
+        let normal = a.cross(&b);
+        let norm2 = normal.dot(&normal);
+        if sqrt(norm2) < EPSILON {
+            return f64::min(
+                self.distance_rads(start),
+                self.distance_rads(end),
+            );
+        }
+
+        // Project the point on the plane of the great circle: the projection
+        // is on the arc if it's between `start` and `end`, going toward `end`.
+        let point = Vec3d::from(self);
+        let foot = combine(point, 1., normal, -point.dot(&normal) / norm2);
+        if a.cross(&foot).dot(&normal) >= 0.
+            && foot.cross(&b).dot(&normal) >= 0.
+        {
+            abs(self.cross_track_distance_rads(start, end))
+        } else {
+            f64::min(self.distance_rads(start), self.distance_rads(end))
+        }
+    }
+
+    /// The distance, in kilometers, from `self` to the great circle arc
+    /// between `start` and `end`.
+    ///
+    /// See [`Self::distance_to_segment_rads`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let start = h3o::LatLng::new(0., 0.)?;
+    /// let end = h3o::LatLng::new(0., 10.)?;
+    /// let point = h3o::LatLng::new(0., -1.)?;
+    ///
+    /// assert_eq!(point.distance_to_segment_km(start, end), point.distance_km(start));
+    /// # Ok::<(), h3o::error::InvalidLatLng>(())
+    /// ```
+    #[must_use]
+    pub fn distance_to_segment_km(self, start: Self, end: Self) -> f64 {
+        self.distance_to_segment_rads(start, end) * EARTH_RADIUS_KM
+    }
+
+    /// The distance, in meters, from `self` to the great circle arc between
+    /// `start` and `end`.
+    ///
+    /// See [`Self::distance_to_segment_rads`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let start = h3o::LatLng::new(0., 0.)?;
+    /// let end = h3o::LatLng::new(0., 10.)?;
+    /// let point = h3o::LatLng::new(-1., 5.)?;
+    ///
+    /// float_eq::assert_float_eq!(
+    ///     point.distance_to_segment_m(start, end),
+    ///     111195.05197522943,
+    ///     abs <= 1e-6
+    /// );
+    /// # Ok::<(), h3o::error::InvalidLatLng>(())
+    /// ```
+    #[must_use]
+    pub fn distance_to_segment_m(self, start: Self, end: Self) -> f64 {
+        self.distance_to_segment_km(start, end) * 1000.
+    }
+
     /// Indexes the location at the specified resolution, returning the index of
     /// the cell containing the location.
     ///
@@ -421,6 +804,16 @@ impl From<LatLng> for Vec3d {
     }
 }
 
+impl From<Vec3d> for LatLng {
+    /// Computes the latitude and longitude of the direction of a 3D vector.
+    fn from(value: Vec3d) -> Self {
+        Self::new_unchecked(
+            atan2(value.z, hypot(value.x, value.y)),
+            atan2(value.y, value.x),
+        )
+    }
+}
+
 impl fmt::Display for LatLng {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // For display purpose, 10 decimals be more than enough.
@@ -486,6 +879,17 @@ impl<'a> arbitrary::Arbitrary<'a> for LatLng {
     }
 }
 
+// -----------------------------------------------------------------------------
+
+/// Computes the linear combination `a * ka + b * kb`.
+fn combine(a: Vec3d, ka: f64, b: Vec3d, kb: f64) -> Vec3d {
+    Vec3d::new(
+        mul_add(a.x, ka, b.x * kb),
+        mul_add(a.y, ka, b.y * kb),
+        mul_add(a.z, ka, b.z * kb),
+    )
+}
+
 #[cfg(test)]
 #[path = "./latlng_tests.rs"]
 mod tests;
@@ -25,6 +25,20 @@ impl Vec3d {
 
         mul_add(x_diff, x_diff, mul_add(y_diff, y_diff, z_diff * z_diff))
     }
+
+    /// Computes the dot product of two 3D vectors.
+    pub fn dot(&self, other: &Self) -> f64 {
+        mul_add(self.x, other.x, mul_add(self.y, other.y, self.z * other.z))
+    }
+
+    /// Computes the cross product of two 3D vectors.
+    pub fn cross(&self, other: &Self) -> Self {
+        Self::new(
+            mul_add(self.y, other.z, -self.z * other.y),
+            mul_add(self.z, other.x, -self.x * other.z),
+            mul_add(self.x, other.y, -self.y * other.x),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -40,3 +40,30 @@ fn distance() {
         "distance to <1,1,2> is 6"
     );
 }
+
+#[test]
+fn dot() {
+    let v1 = Vec3d::new(1., 2., 3.);
+    let v2 = Vec3d::new(4., -5., 6.);
+
+    assert_float_eq!(v1.dot(&v2), 12., abs <= f64::EPSILON);
+    assert_float_eq!(
+        v1.dot(&v1),
+        v1.distance(&Vec3d::new(0., 0., 0.)),
+        abs <= f64::EPSILON
+    );
+}
+
+#[test]
+fn cross() {
+    let x = Vec3d::new(1., 0., 0.);
+    let y = Vec3d::new(0., 1., 0.);
+    let v1 = Vec3d::new(1., 2., 3.);
+    let v2 = Vec3d::new(4., -5., 6.);
+
+    assert_eq!(x.cross(&y), Vec3d::new(0., 0., 1.));
+    assert_eq!(y.cross(&x), Vec3d::new(0., 0., -1.));
+    let result = v1.cross(&v2);
+    assert_eq!(result, Vec3d::new(27., 6., -13.));
+    assert_float_eq!(result.dot(&v1), 0., abs <= f64::EPSILON);
+}
@@ -0,0 +1,395 @@
+//! Dissolution of cell sets into rings of coordinates.
+//!
+//! This is the `geo`-free counterpart of `geom::Solvent`: rings are produced
+//! one at a time, straight from the graph of borders, without being assembled
+//! into polygons.
+//!
+//! The out-of-core mode relies on the input being sorted: once every cell of a
+//! given parent has been seen, the rings that don't border any upcoming parent
+//! are complete and can be emitted (and forgotten) right away.
+
+use crate::{
+    CellIndex, EdgeRing, LatLng, Resolution, VertexIndex,
+    collections::{Map, Set},
+    error::DissolutionError,
+    outline::{EdgeGraph, InputOptions, InputValidator},
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{iter::FusedIterator, mem};
+
+/// A dissolver that merges a set of H3 cell indexes into the rings outlining
+/// the set.
+///
+/// Rings are not grouped into polygons: outer rings run counter-clockwise
+/// whereas holes run clockwise. Like [`EdgeRing::boundary`], rings are not
+/// explicitly closed.
+#[derive(Debug, Clone, Copy)]
+pub struct Dissolver {
+    input: InputOptions,
+}
+
+impl Dissolver {
+    /// Computes the rings outlining a set of cells.
+    ///
+    /// The whole set is consumed upfront, but the coordinates of the rings are
+    /// only computed when iterating.
+    ///
+    /// # Errors
+    ///
+    /// All cell indexes must be unique and have the expected resolution,
+    /// otherwise [`DissolutionError`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, DissolverBuilder, Resolution};
+    ///
+    /// let index = CellIndex::try_from(0x089283470803ffff)?;
+    /// let cells = index.children(Resolution::Twelve);
+    /// let dissolver = DissolverBuilder::new().build();
+    /// for ring in dissolver.dissolve(cells)? {
+    ///     assert!(ring.len() >= 6);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn dissolve(
+        &self,
+        cells: impl IntoIterator<Item = CellIndex>,
+    ) -> Result<Rings, DissolutionError> {
+        let graph = self.input.edge_graph(cells)?;
+
+        Ok(Rings { graph })
+    }
+
+    /// Computes the rings outlining a sorted stream of cells, out-of-core.
+    ///
+    /// Cells must be sorted (as per [`CellIndex`] ordering) or, at least,
+    /// grouped by parent at `resolution` with the parents in ascending order.
+    /// Rings are emitted as soon as they can't be altered by the upcoming
+    /// cells, thus the memory usage is bounded by the size of the frontier
+    /// between the processed and the upcoming cells rather than by the size of
+    /// the input.
+    ///
+    /// Duplicate detection, when enabled, is performed per parent.
+    ///
+    /// A coarser `resolution` gives bigger batches (and thus less frequent
+    /// emissions) but each ring is checked less often.
+    ///
+    /// # Errors
+    ///
+    /// The iterator yields a [`DissolutionError`] and stops if a cell is
+    /// duplicated, has an unexpected resolution (including resolutions
+    /// coarser than `resolution`) or is out of order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, DissolverBuilder, Resolution};
+    ///
+    /// let index = CellIndex::try_from(0x089283470803ffff)?;
+    /// // Children are produced in order.
+    /// let cells = index.children(Resolution::Twelve);
+    /// let dissolver = DissolverBuilder::new().build();
+    /// let rings = dissolver
+    ///     .dissolve_sorted(cells, Resolution::Ten)
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(rings.len(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn dissolve_sorted<I>(
+        &self,
+        cells: I,
+        resolution: Resolution,
+    ) -> SortedRings<I::IntoIter>
+    where
+        I: IntoIterator<Item = CellIndex>,
+    {
+        SortedRings {
+            cells: cells.into_iter(),
+            graph: EdgeGraph::with_journal(),
+            validator: self.input.validator(),
+            resolution,
+            parent: None,
+            blocked: BTreeMap::new(),
+            resume: Map::default(),
+            ready: Vec::new(),
+            is_done: false,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A builder to configure a dissolver.
+#[derive(Debug, Clone, Copy)]
+pub struct DissolverBuilder {
+    input: InputOptions,
+}
+
+impl Default for DissolverBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DissolverBuilder {
+    /// Initializes a new dissolver builder with default settings.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            input: InputOptions::new(),
+        }
+    }
+
+    /// Disable duplicate detection.
+    ///
+    /// If the input set contains duplicate cells, the resulting rings will be
+    /// incorrect.
+    ///
+    /// By default the dissolver will ensure that the input set doesn't
+    /// contains any duplicate but this implies a memory overhead.
+    #[must_use]
+    pub const fn disable_duplicate_detection(mut self) -> Self {
+        self.input = self.input.disable_duplicate_detection();
+        self
+    }
+
+    /// Enable support for heterogeneous (e.g. compacted) cell set.
+    ///
+    /// When enabling this mode you must specify the finest resolution you want
+    /// to support. Coarser cells will be converted on-the-fly and finer cells
+    /// will trigger an error.
+    #[must_use]
+    pub const fn enable_heterogeneous_support(
+        mut self,
+        resolution: Resolution,
+    ) -> Self {
+        self.input = self.input.enable_heterogeneous_support(resolution);
+        self
+    }
+
+    /// Builds the dissolver.
+    #[must_use]
+    pub const fn build(self) -> Dissolver {
+        Dissolver { input: self.input }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Iterator over the rings of a dissolved cell set.
+///
+/// Rings are yielded in an arbitrary order.
+#[derive(Debug)]
+pub struct Rings {
+    graph: EdgeGraph,
+}
+
+impl Iterator for Rings {
+    type Item = Vec<LatLng>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.graph.pop_ring().map(|ring| ring.boundary())
+    }
+}
+
+impl FusedIterator for Rings {}
+
+// -----------------------------------------------------------------------------
+
+/// Iterator over the rings of a sorted stream of cells.
+///
+/// Rings are yielded as soon as they are complete.
+#[derive(Debug)]
+pub struct SortedRings<I> {
+    /// The input stream.
+    cells: I,
+    /// The borders that may still change.
+    graph: EdgeGraph,
+    /// Input checks, reset on every batch.
+    validator: InputValidator,
+    /// Resolution of the parents used to split the input in batches.
+    resolution: Resolution,
+    /// The parent of the batch being processed.
+    parent: Option<CellIndex>,
+    /// Rings waiting on an upcoming parent, indexed by said parent.
+    ///
+    /// Rings are identified by their starting vertex.
+    blocked: BTreeMap<CellIndex, Vec<VertexIndex>>,
+    /// For each blocked ring, the vertex where its walk stopped.
+    ///
+    /// The borders between the start of a ring and this vertex are final,
+    /// there is no need to walk them again.
+    resume: Map<VertexIndex, VertexIndex>,
+    /// Complete rings, ready to be emitted.
+    ready: Vec<EdgeRing>,
+    is_done: bool,
+}
+
+impl<I> SortedRings<I>
+where
+    I: Iterator<Item = CellIndex>,
+{
+    /// Adds a cell to the graph, emitting the complete rings on batch change.
+    fn insert(&mut self, cell: CellIndex) -> Result<(), DissolutionError> {
+        let parent = cell
+            .parent(self.resolution)
+            .ok_or(DissolutionError::UnsupportedResolution)?;
+
+        match self.parent {
+            Some(current) if parent < current => {
+                return Err(DissolutionError::UnsortedInput);
+            }
+            Some(current) if parent == current => (),
+            _ => {
+                self.flush(parent);
+                self.parent = Some(parent);
+                self.validator.clear();
+            }
+        }
+
+        let resolution = self.validator.check(cell)?;
+        self.graph.insert_cell(cell, resolution);
+
+        Ok(())
+    }
+
+    /// Moves the rings that don't border `next`, or any upcoming parent, to
+    /// the output queue.
+    fn flush(&mut self, next: CellIndex) {
+        // Only the rings altered since the last flush, or waiting on an
+        // already processed parent, can be complete.
+        let mut candidates = self.graph.drain_journal();
+        let pending = self.blocked.split_off(&next);
+        let unblocked = mem::replace(&mut self.blocked, pending);
+        candidates.extend(
+            unblocked
+                .into_values()
+                .flatten()
+                // Rings absorbed by another walk are tracked by the latter.
+                .filter(|start| self.resume.contains_key(start)),
+        );
+
+        let mut visited = Set::default();
+        for start in candidates {
+            if !self.graph.contains(start) {
+                self.resume.remove(&start);
+                continue;
+            }
+            match self.walk(start, next, &mut visited) {
+                Walk::Closed => self.ready.push(self.graph.take_ring(start)),
+                Walk::Blocked(vertex, parent) => {
+                    self.resume.insert(start, vertex);
+                    self.blocked.entry(parent).or_default().push(start);
+                }
+                Walk::Visited => (),
+            }
+        }
+    }
+
+    /// Walks the ring going through `start` until it closes or reaches a
+    /// border with a cell of `next` or of an upcoming parent.
+    ///
+    /// The final parts of the ring, known from previous walks, are skipped
+    /// (and their bookkeeping merged into the one of `start`).
+    fn walk(
+        &mut self,
+        start: VertexIndex,
+        next: CellIndex,
+        visited: &mut Set<VertexIndex>,
+    ) -> Walk {
+        // Already walked: it's waiting on an upcoming parent.
+        if !visited.insert(start) {
+            return Walk::Visited;
+        }
+        let mut absorbed = Vec::new();
+        let mut from = self.resume.get(&start).copied().unwrap_or(start);
+        let mut is_first = true;
+        // A ring can't be longer than the graph, this bounds the walk even on
+        // an inconsistent graph (e.g. duplicate input without detection).
+        let mut budget = self.graph.vertex_count();
+
+        let result = loop {
+            if from == start && !is_first {
+                break Walk::Closed;
+            }
+            is_first = false;
+            if from != start {
+                if !visited.insert(from) {
+                    break Walk::Visited;
+                }
+                if let Some(&to) = self.resume.get(&from) {
+                    absorbed.push(from);
+                    if to != from {
+                        from = to;
+                        continue;
+                    }
+                }
+            }
+            let Some((edge, to)) = self.graph.border(from) else {
+                break Walk::Visited;
+            };
+            let parent = edge
+                .destination()
+                .parent(self.resolution)
+                .expect("coarser parent");
+            if parent >= next {
+                break Walk::Blocked(from, parent);
+            }
+            if budget == 0 {
+                break Walk::Visited;
+            }
+            budget -= 1;
+            from = to;
+        };
+
+        if !matches!(result, Walk::Visited) {
+            for vertex in absorbed {
+                self.resume.remove(&vertex);
+            }
+            self.resume.remove(&start);
+        }
+        result
+    }
+}
+
+/// Outcome of a ring walk.
+enum Walk {
+    /// The ring is complete.
+    Closed,
+    /// The ring waits on an upcoming parent, from the given vertex.
+    Blocked(VertexIndex, CellIndex),
+    /// The ring has already been walked.
+    Visited,
+}
+
+impl<I> Iterator for SortedRings<I>
+where
+    I: Iterator<Item = CellIndex>,
+{
+    type Item = Result<Vec<LatLng>, DissolutionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(ring) = self.ready.pop() {
+                return Some(Ok(ring.boundary()));
+            }
+            if self.is_done {
+                // Everything left is complete.
+                return self.graph.pop_ring().map(|ring| Ok(ring.boundary()));
+            }
+
+            let Some(cell) = self.cells.next() else {
+                self.is_done = true;
+                continue;
+            };
+            if let Err(err) = self.insert(cell) {
+                self.is_done = true;
+                self.graph = EdgeGraph::default();
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+impl<I> FusedIterator for SortedRings<I> where I: Iterator<Item = CellIndex> {}
@@ -0,0 +1,27 @@
+use core::{error::Error, fmt};
+
+/// Errors occurring while computing the neighborhoods of a set of cell
+/// values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AutocorrelationError {
+    /// Input cells don't share the same resolution.
+    ResolutionMismatch,
+    /// Distance band is negative, not finite or wider than the Earth.
+    InvalidDistanceBand,
+}
+
+impl fmt::Display for AutocorrelationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::ResolutionMismatch => write!(f, "resolution mismatch"),
+            Self::InvalidDistanceBand => write!(f, "invalid distance band"),
+        }
+    }
+}
+
+impl Error for AutocorrelationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
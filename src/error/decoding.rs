@@ -0,0 +1,37 @@
+use core::{error::Error, fmt};
+
+/// Errors occurring while decoding an encoded set of cell indices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodingError {
+    /// Input doesn't start with a valid header.
+    InvalidHeader,
+    /// Input has been encoded with an unsupported version of the format.
+    UnsupportedVersion(u8),
+    /// Input ends in the middle of the set.
+    UnexpectedEnd,
+    /// Input contains an entry that doesn't map to a valid cell.
+    InvalidEntry,
+    /// Input contains extra bytes after the set.
+    TrailingBytes,
+}
+
+impl fmt::Display for DecodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::InvalidHeader => write!(f, "invalid header"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported version {version}")
+            }
+            Self::UnexpectedEnd => write!(f, "unexpected end of input"),
+            Self::InvalidEntry => write!(f, "invalid entry"),
+            Self::TrailingBytes => write!(f, "trailing bytes"),
+        }
+    }
+}
+
+impl Error for DecodingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
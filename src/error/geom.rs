@@ -67,32 +67,3 @@ impl From<LocalIjError> for PlotterError {
         Self::LocalIj(value)
     }
 }
-
-// -----------------------------------------------------------------------------
-
-/// Errors occurring during the dissolution of a set of cell indexes.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-#[non_exhaustive]
-pub enum DissolutionError {
-    /// Input contains cell indexes of unsupported resolutions.
-    UnsupportedResolution,
-    /// Input set contains duplicate cell indexes.
-    DuplicateInput,
-}
-
-impl fmt::Display for DissolutionError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match *self {
-            Self::UnsupportedResolution => {
-                write!(f, "unsupported resolution")
-            }
-            Self::DuplicateInput => write!(f, "duplicate indices"),
-        }
-    }
-}
-
-impl Error for DissolutionError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        None
-    }
-}
@@ -1,10 +1,17 @@
 //! H3O error types.
 
+mod autocorrelation;
 mod compaction;
+mod decoding;
+mod dissolution;
 mod hex_grid;
 mod invalid_value;
 mod localij;
+mod parameter;
+mod parsing;
+mod raster;
 mod resolution_mismatch;
+mod unordered_timestamp;
 
 #[cfg(feature = "geo")]
 mod geom;
@@ -12,7 +19,10 @@ mod geom;
 #[cfg(test)]
 mod tests;
 
+pub use autocorrelation::AutocorrelationError;
 pub use compaction::CompactionError;
+pub use decoding::DecodingError;
+pub use dissolution::DissolutionError;
 pub use hex_grid::HexGridError;
 pub use invalid_value::{
     InvalidBaseCell, InvalidCellIndex, InvalidDirectedEdgeIndex,
@@ -20,7 +30,11 @@ pub use invalid_value::{
     InvalidResolution, InvalidVertex, InvalidVertexIndex,
 };
 pub use localij::LocalIjError;
+pub use parameter::InvalidParameter;
+pub use parsing::ParsingError;
+pub use raster::InvalidRaster;
 pub use resolution_mismatch::ResolutionMismatch;
+pub use unordered_timestamp::UnorderedTimestamp;
 
 #[cfg(feature = "geo")]
-pub use geom::{DissolutionError, InvalidGeometry, PlotterError};
+pub use geom::{InvalidGeometry, PlotterError};
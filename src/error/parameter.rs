@@ -0,0 +1,26 @@
+use core::{error::Error, fmt};
+
+/// Errors related to the settings of an algorithm (e.g. a negative distance).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidParameter {
+    reason: &'static str,
+}
+
+impl InvalidParameter {
+    /// Initializes a new [`InvalidParameter`] with the given error message.
+    pub(crate) const fn new(reason: &'static str) -> Self {
+        Self { reason }
+    }
+}
+
+impl fmt::Display for InvalidParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.reason)
+    }
+}
+
+impl Error for InvalidParameter {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
@@ -0,0 +1,60 @@
+use super::InvalidCellIndex;
+use core::{error::Error, fmt};
+
+/// Errors occurring while parsing a cell index from a string.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ParsingError {
+    /// Input is empty.
+    Empty,
+    /// Input contains a character that isn't allowed in its format.
+    InvalidCharacter {
+        /// Byte offset of the character in the input.
+        position: usize,
+        /// The unexpected character.
+        character: char,
+    },
+    /// Input length doesn't match its format.
+    InvalidLength(usize),
+    /// Input represents a number that doesn't fit in 64 bits.
+    Overflow,
+    /// Input is well-formed, but doesn't represent a valid cell index.
+    InvalidIndex(InvalidCellIndex),
+}
+
+impl fmt::Display for ParsingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Empty => write!(f, "empty input"),
+            Self::InvalidCharacter {
+                position,
+                character,
+            } => {
+                write!(f, "invalid character '{character}' at {position}")
+            }
+            Self::InvalidLength(length) => {
+                write!(f, "invalid length {length}")
+            }
+            Self::Overflow => write!(f, "number too large for 64 bits"),
+            Self::InvalidIndex(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for ParsingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            Self::Empty
+            | Self::InvalidCharacter { .. }
+            | Self::InvalidLength(_)
+            | Self::Overflow => None,
+            Self::InvalidIndex(ref err) => Some(err),
+        }
+    }
+}
+
+impl From<InvalidCellIndex> for ParsingError {
+    fn from(value: InvalidCellIndex) -> Self {
+        Self::InvalidIndex(value)
+    }
+}
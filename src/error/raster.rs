@@ -0,0 +1,26 @@
+use core::{error::Error, fmt};
+
+/// Errors related to the rasters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidRaster {
+    reason: &'static str,
+}
+
+impl InvalidRaster {
+    /// Initializes a new [`InvalidRaster`] with the given error message.
+    pub(crate) const fn new(reason: &'static str) -> Self {
+        Self { reason }
+    }
+}
+
+impl fmt::Display for InvalidRaster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.reason)
+    }
+}
+
+impl Error for InvalidRaster {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
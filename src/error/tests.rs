@@ -1,11 +1,13 @@
 use crate::error::{
-    CompactionError, HexGridError, InvalidBaseCell, InvalidCellIndex,
-    InvalidDirectedEdgeIndex, InvalidDirection, InvalidEdge, InvalidFace,
-    InvalidLatLng, InvalidResolution, InvalidVertex, InvalidVertexIndex,
-    LocalIjError, ResolutionMismatch,
+    AutocorrelationError, CompactionError, DecodingError, DissolutionError,
+    HexGridError, InvalidBaseCell, InvalidCellIndex, InvalidDirectedEdgeIndex,
+    InvalidDirection, InvalidEdge, InvalidFace, InvalidLatLng,
+    InvalidParameter, InvalidRaster, InvalidResolution, InvalidVertex,
+    InvalidVertexIndex, LocalIjError, ParsingError, ResolutionMismatch,
+    UnorderedTimestamp,
 };
 #[cfg(feature = "geo")]
-use crate::error::{DissolutionError, InvalidGeometry, PlotterError};
+use crate::error::{InvalidGeometry, PlotterError};
 use alloc::string::ToString;
 use core::error::Error as _;
 
@@ -56,18 +58,68 @@ fn display() {
 
     assert!(!ResolutionMismatch.to_string().is_empty());
 
+    assert!(!UnorderedTimestamp.to_string().is_empty());
+
+    assert!(
+        !InvalidRaster::new("invalid transform")
+            .to_string()
+            .is_empty()
+    );
+
+    assert!(
+        !InvalidParameter::new("invalid accuracy")
+            .to_string()
+            .is_empty()
+    );
+
+    assert!(
+        !AutocorrelationError::ResolutionMismatch
+            .to_string()
+            .is_empty()
+    );
+    assert!(
+        !AutocorrelationError::InvalidDistanceBand
+            .to_string()
+            .is_empty()
+    );
+
+    assert!(
+        !DissolutionError::UnsupportedResolution
+            .to_string()
+            .is_empty()
+    );
+    assert!(!DissolutionError::DuplicateInput.to_string().is_empty());
+    assert!(!DissolutionError::UnsortedInput.to_string().is_empty());
+
+    assert!(!DecodingError::InvalidHeader.to_string().is_empty());
+    assert!(!DecodingError::UnsupportedVersion(2).to_string().is_empty());
+    assert!(!DecodingError::UnexpectedEnd.to_string().is_empty());
+    assert!(!DecodingError::InvalidEntry.to_string().is_empty());
+    assert!(!DecodingError::TrailingBytes.to_string().is_empty());
+
+    assert!(!ParsingError::Empty.to_string().is_empty());
+    assert!(
+        !ParsingError::InvalidCharacter {
+            position: 0,
+            character: 'z'
+        }
+        .to_string()
+        .is_empty()
+    );
+    assert!(!ParsingError::InvalidLength(42).to_string().is_empty());
+    assert!(!ParsingError::Overflow.to_string().is_empty());
+    assert!(
+        !ParsingError::from(InvalidCellIndex::new(Some(0), "error"))
+            .to_string()
+            .is_empty()
+    );
+
     #[cfg(feature = "geo")]
     {
         let invalid_geometry = InvalidGeometry::new("error");
         let local_ij = LocalIjError::Pentagon;
 
         assert!(!invalid_geometry.to_string().is_empty());
-        assert!(
-            !DissolutionError::UnsupportedResolution
-                .to_string()
-                .is_empty()
-        );
-        assert!(!DissolutionError::DuplicateInput.to_string().is_empty());
 
         assert!(!PlotterError::from(invalid_geometry).to_string().is_empty());
         assert!(!PlotterError::from(local_ij).to_string().is_empty());
@@ -104,14 +156,48 @@ fn source() {
 
     assert!(ResolutionMismatch.source().is_none());
 
+    assert!(UnorderedTimestamp.source().is_none());
+
+    assert!(InvalidRaster::new("invalid transform").source().is_none());
+
+    assert!(InvalidParameter::new("invalid accuracy").source().is_none());
+
+    assert!(AutocorrelationError::ResolutionMismatch.source().is_none());
+    assert!(AutocorrelationError::InvalidDistanceBand.source().is_none());
+
+    assert!(DissolutionError::UnsupportedResolution.source().is_none());
+    assert!(DissolutionError::DuplicateInput.source().is_none());
+    assert!(DissolutionError::UnsortedInput.source().is_none());
+
+    assert!(DecodingError::InvalidHeader.source().is_none());
+    assert!(DecodingError::UnsupportedVersion(2).source().is_none());
+    assert!(DecodingError::UnexpectedEnd.source().is_none());
+    assert!(DecodingError::InvalidEntry.source().is_none());
+    assert!(DecodingError::TrailingBytes.source().is_none());
+
+    assert!(ParsingError::Empty.source().is_none());
+    assert!(
+        ParsingError::InvalidCharacter {
+            position: 0,
+            character: 'z'
+        }
+        .source()
+        .is_none()
+    );
+    assert!(ParsingError::InvalidLength(42).source().is_none());
+    assert!(ParsingError::Overflow.source().is_none());
+    assert!(
+        ParsingError::from(InvalidCellIndex::new(Some(0), "error"))
+            .source()
+            .is_some()
+    );
+
     #[cfg(feature = "geo")]
     {
         let invalid_geometry = InvalidGeometry::new("error");
         let local_ij = LocalIjError::Pentagon;
 
         assert!(invalid_geometry.source().is_none());
-        assert!(DissolutionError::UnsupportedResolution.source().is_none());
-        assert!(DissolutionError::DuplicateInput.source().is_none());
         assert!(PlotterError::from(invalid_geometry).source().is_some());
         assert!(PlotterError::from(local_ij).source().is_some());
     }
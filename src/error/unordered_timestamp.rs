@@ -0,0 +1,17 @@
+use core::{error::Error, fmt};
+
+/// A timestamp is lower than the previous one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnorderedTimestamp;
+
+impl fmt::Display for UnorderedTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timestamps must be non-decreasing")
+    }
+}
+
+impl Error for UnorderedTimestamp {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
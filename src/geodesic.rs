@@ -0,0 +1,1044 @@
+// This file is a port of GeographicLib, distributed under the MIT License:
+//
+// Copyright (c) 2008-2023, Charles Karney
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL
+// THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR
+// OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE,
+// ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR
+// OTHER DEALINGS IN THE SOFTWARE.
+
+//! Geodesics on the WGS84 ellipsoid.
+//!
+//! This is a port of the inverse problem (distance, azimuth and area between
+//! two points) from [GeographicLib](https://geographiclib.sourceforge.io),
+//! following C. F. F. Karney, "Algorithms for geodesics", J. Geodesy 87,
+//! 43–55 (2013): results are accurate to round-off (~15 nanometers).
+//!
+//! Notations and evaluation order are kept as close as possible to the
+//! reference implementation, to ease comparisons.
+#![expect(
+    clippy::float_cmp,
+    clippy::many_single_char_names,
+    clippy::similar_names,
+    clippy::too_many_arguments,
+    clippy::too_many_lines,
+    reason = "port of GeographicLib, keeping its notations"
+)]
+#![cfg_attr(
+    feature = "std",
+    expect(
+        clippy::suboptimal_flops,
+        reason = "keep the evaluation order of GeographicLib"
+    )
+)]
+
+use crate::{
+    LatLng,
+    math::{abs, atan2, cbrt, cos, hypot, round, sin, sqrt},
+};
+use core::{
+    f64::consts::{FRAC_1_SQRT_2, PI},
+    mem,
+};
+
+/// Model of the Earth's shape, used to compute distances and areas.
+///
+/// H3 relies on a spherical model. The ellipsoidal one is more accurate
+/// (the spherical distances are up to ~0.5% off), at the expense of speed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EarthModel {
+    /// Sphere of radius [`EARTH_RADIUS_KM`](crate::EARTH_RADIUS_KM), as used
+    /// by H3.
+    ///
+    /// This is the authalic sphere of WGS84: it has the same surface area as
+    /// the ellipsoid.
+    #[default]
+    Sphere,
+    /// WGS84 ellipsoid, along which distances follow the geodesics.
+    Wgs84,
+}
+
+/// Equatorial radius of WGS84, in meters.
+const WGS84_A: f64 = 6_378_137.;
+/// Flattening of WGS84.
+const WGS84_F: f64 = 1. / 298.257_223_563;
+
+/// Order of the series expansions.
+const ORDER: usize = 6;
+/// Size of the coefficient arrays for `C3` and `C4`.
+const C3_SIZE: usize = (ORDER * (ORDER - 1)) / 2;
+const C4_SIZE: usize = (ORDER * (ORDER + 1)) / 2;
+
+/// Maximum number of Newton iterations, and of iterations overall
+/// (bisection included).
+const MAX_IT1: usize = 20;
+const MAX_IT2: usize = MAX_IT1 + f64::MANTISSA_DIGITS as usize + 10;
+
+const TOL0: f64 = f64::EPSILON;
+const TOL1: f64 = 200. * TOL0;
+/// `sqrt(TOL0)`, exactly 2⁻²⁶.
+const TOL2: f64 = 1. / 67_108_864.;
+/// `sqrt(f64::MIN_POSITIVE)`, exactly 2⁻⁵¹¹.
+const TINY: f64 = f64::from_bits(512 << 52);
+
+/// Authalic radius² of WGS84, from `e * atanh(e)`:
+/// `(a² + b² * (e * ln((1 + e) / (1 - e)) / 2) / e²) / 2`.
+const WGS84_C2: f64 = 40_589_732_499_314.766;
+/// Tolerance for the Newton iterations on WGS84:
+/// `0.1 * sqrt(TOL0) / sqrt(max(|f|, 0.001) * min(1 - f / 2, 1) / 2)`.
+const WGS84_ETOL2: f64 = 3.642_461_148_878_852_4e-8;
+
+const COEFF_A3: [f64; 18] = [
+    -3., 128., -2., -3., 64., -1., -3., -1., 16., 3., -1., -2., 8., 1., -1.,
+    2., 1., 1.,
+];
+
+const COEFF_C3: [f64; 45] = [
+    3., 128., 2., 5., 128., -1., 3., 3., 64., -1., 0., 1., 8., -1., 1., 4., 5.,
+    256., 1., 3., 128., -3., -2., 3., 64., 1., -3., 2., 32., 7., 512., -10.,
+    9., 384., 5., -9., 5., 192., 7., 512., -14., 7., 512., 21., 2560.,
+];
+
+const COEFF_C4: [f64; 77] = [
+    97., 15015., 1088., 156., 45045., -224., -4784., 1573., 45045., -10656.,
+    14144., -4576., -858., 45045., 64., 624., -4576., 6864., -3003., 15015.,
+    100., 208., 572., 3432., -12012., 30030., 45045., 1., 9009., -2944., 468.,
+    135135., 5792., 1040., -1287., 135135., 5952., -11648., 9152., -2574.,
+    135135., -64., -624., 4576., -6864., 3003., 135135., 8., 10725., 1856.,
+    -936., 225225., -8448., 4992., -1144., 225225., -1440., 4160., -4576.,
+    1716., 225225., -136., 63063., 1024., -208., 105105., 3584., -3328., 1144.,
+    315315., -128., 135135., -2560., 832., 405405., 128., 99099.,
+];
+
+/// Solution of the inverse geodesic problem.
+#[derive(Debug, Clone, Copy)]
+pub struct Inverse {
+    /// Distance between the two points, in meters.
+    pub distance: f64,
+    /// Azimuth of the geodesic at the first point, in degrees.
+    pub azimuth: f64,
+    /// Area between the geodesic and the equator, in m².
+    pub area: f64,
+}
+
+/// An ellipsoid of revolution, and its derived constants.
+#[derive(Debug, Clone, Copy)]
+pub struct Geodesic {
+    a: f64,
+    f: f64,
+    f1: f64,
+    e2: f64,
+    ep2: f64,
+    n: f64,
+    b: f64,
+    c2: f64,
+    etol2: f64,
+    tiny: f64,
+    tolb: f64,
+    xthresh: f64,
+    a3x: [f64; ORDER],
+    c3x: [f64; C3_SIZE],
+    c4x: [f64; C4_SIZE],
+}
+
+/// The WGS84 ellipsoid, with its derived constants computed at compile time.
+pub static WGS84: Geodesic = Geodesic::wgs84();
+
+impl Geodesic {
+    /// Initializes the WGS84 ellipsoid.
+    const fn wgs84() -> Self {
+        let (a, f) = (WGS84_A, WGS84_F);
+        let tol2 = TOL2;
+        let f1 = 1. - f;
+        let e2 = f * (2. - f);
+        let ep2 = e2 / (f1 * f1);
+        let n = f / (2. - f);
+        let b = a * f1;
+
+        let mut a3x = [0.; ORDER];
+        let (mut o, mut k) = (0, 0);
+        while k < ORDER {
+            let j = ORDER - 1 - k;
+            let m = min(j, ORDER - j - 1);
+            a3x[k] =
+                polyval(m, COEFF_A3.split_at(o).1, n) / COEFF_A3[o + m + 1];
+            o += m + 2;
+            k += 1;
+        }
+
+        let mut c3x = [0.; C3_SIZE];
+        let (mut o, mut k) = (0, 0);
+        let mut l = 1;
+        while l < ORDER {
+            let mut j = ORDER - 1;
+            while j >= l {
+                let m = min(j, ORDER - j - 1);
+                c3x[k] =
+                    polyval(m, COEFF_C3.split_at(o).1, n) / COEFF_C3[o + m + 1];
+                k += 1;
+                o += m + 2;
+                j -= 1;
+            }
+            l += 1;
+        }
+
+        let mut c4x = [0.; C4_SIZE];
+        let (mut o, mut k) = (0, 0);
+        let mut l = 0;
+        while l < ORDER {
+            let mut j = ORDER - 1;
+            loop {
+                let m = ORDER - j - 1;
+                c4x[k] =
+                    polyval(m, COEFF_C4.split_at(o).1, n) / COEFF_C4[o + m + 1];
+                k += 1;
+                o += m + 2;
+                if j == l {
+                    break;
+                }
+                j -= 1;
+            }
+            l += 1;
+        }
+
+        Self {
+            a,
+            f,
+            f1,
+            e2,
+            ep2,
+            n,
+            b,
+            c2: WGS84_C2,
+            etol2: WGS84_ETOL2,
+            tiny: TINY,
+            tolb: TOL0 * tol2,
+            xthresh: 1000. * tol2,
+            a3x,
+            c3x,
+            c4x,
+        }
+    }
+
+    /// Returns the total area of the ellipsoid, in m².
+    pub fn total_area(&self) -> f64 {
+        4. * PI * self.c2
+    }
+
+    /// Computes the area of a polygon whose edges are geodesics, in m².
+    ///
+    /// The polygon can be in either orientation, but must cover less than half
+    /// of the ellipsoid.
+    pub fn polygon_area(&self, ring: &[LatLng]) -> f64 {
+        let mut area = 0.;
+        let mut crossings = 0;
+        for (i, from) in ring.iter().enumerate() {
+            let to = ring[(i + 1) % ring.len()];
+            area += self.inverse(*from, to).area;
+            crossings += transit(from.lng(), to.lng());
+        }
+
+        // Account for the crossings of the prime meridian, which tell whether
+        // a pole is enclosed.
+        let total = self.total_area();
+        area %= total;
+        if abs(area) > total / 2. {
+            area -= if area < 0. { -total } else { total };
+        }
+        if crossings % 2 != 0 {
+            area += if area < 0. { total / 2. } else { -total / 2. };
+        }
+        // Area is clockwise, put it in the counter-clockwise convention.
+        area = -area;
+        if area > total / 2. {
+            area -= total;
+        } else if area <= -total / 2. {
+            area += total;
+        }
+
+        abs(area)
+    }
+
+    /// Solves the inverse geodesic problem between two points.
+    pub fn inverse(&self, from: LatLng, to: LatLng) -> Inverse {
+        let (lat1, lon1, lat2, lon2) =
+            (from.lat(), from.lng(), to.lat(), to.lng());
+
+        // Compute longitude difference accurately.
+        let (lon12, lon12s) = ang_diff(lon1, lon2);
+        let mut lonsign = if lon12 >= 0. { 1. } else { -1. };
+        let lon12 = lonsign * ang_round(lon12);
+        let lon12s = ang_round((180. - lon12) - lonsign * lon12s);
+        let lam12 = lon12.to_radians();
+        let (slam12, clam12) = if lon12 > 90. {
+            let (s, c) = sincosd(lon12s);
+            (s, -c)
+        } else {
+            sincosd(lon12)
+        };
+
+        // Swap points so that point with higher (abs) latitude is point 1,
+        // and make lat1 <= -0.
+        let mut lat1 = ang_round(lat1.clamp(-90., 90.));
+        let mut lat2 = ang_round(lat2.clamp(-90., 90.));
+        let swapp = if abs(lat1) < abs(lat2) { -1. } else { 1. };
+        if swapp < 0. {
+            lonsign = -lonsign;
+            mem::swap(&mut lat1, &mut lat2);
+        }
+        let latsign = if lat1 < 0. { 1. } else { -1. };
+        lat1 *= latsign;
+        lat2 *= latsign;
+
+        let (sbet1, cbet1) = sincosd(lat1);
+        let (sbet1, cbet1) = norm(sbet1 * self.f1, cbet1);
+        let cbet1 = cbet1.max(self.tiny);
+        let (sbet2, cbet2) = sincosd(lat2);
+        let (mut sbet2, mut cbet2) = norm(sbet2 * self.f1, cbet2);
+        cbet2 = cbet2.max(self.tiny);
+
+        // Ensure symmetries when points are on the same parallel.
+        if cbet1 < -sbet1 {
+            if cbet2 == cbet1 {
+                sbet2 = if sbet2 < 0. { sbet1 } else { -sbet1 };
+            }
+        } else if abs(sbet2) == -sbet1 {
+            cbet2 = cbet1;
+        }
+
+        let dn1 = sqrt(1. + self.ep2 * sbet1 * sbet1);
+        let dn2 = sqrt(1. + self.ep2 * sbet2 * sbet2);
+
+        let mut meridian = lat1 == -90. || slam12 == 0.;
+        let (mut salp1, mut calp1, mut salp2, mut calp2) = (0., 0., 0., 0.);
+        let mut s12x = 0.;
+
+        if meridian {
+            // Endpoints are on a single full meridian.
+            (salp1, calp1, salp2, calp2) = (slam12, clam12, 0., 1.);
+            let (ssig1, csig1) = (sbet1, calp1 * cbet1);
+            let (ssig2, csig2) = (sbet2, calp2 * cbet2);
+
+            let sig12 = atan2(
+                (csig1 * ssig2 - ssig1 * csig2).max(0.),
+                csig1 * csig2 + ssig1 * ssig2,
+            );
+            let (s12b, m12b) = Self::lengths(
+                self.n, sig12, ssig1, csig1, dn1, ssig2, csig2, dn2,
+            );
+            // Otherwise, this isn't the shortest path.
+            if sig12 < 1. || m12b >= 0. {
+                s12x = if sig12 < 3. * self.tiny {
+                    0.
+                } else {
+                    s12b * self.b
+                };
+            } else {
+                meridian = false;
+            }
+        }
+
+        let (mut somg12, mut comg12, mut omg12) = (2., 0., 0.);
+        if !meridian && sbet1 == 0. && (self.f <= 0. || lon12s >= self.f * 180.)
+        {
+            // Geodesic runs along the equator.
+            (salp1, calp1, salp2, calp2) = (1., 0., 1., 0.);
+            s12x = self.a * lam12;
+            omg12 = lam12 / self.f1;
+        } else if !meridian {
+            let start = self.inverse_start(
+                sbet1, cbet1, dn1, sbet2, cbet2, dn2, lam12, slam12, clam12,
+            );
+            let (sig12, dnm);
+            (sig12, salp1, calp1, salp2, calp2, dnm) = start;
+
+            if sig12 >= 0. {
+                // Short lines, solved directly.
+                s12x = sig12 * self.b * dnm;
+                omg12 = lam12 / (self.f1 * dnm);
+            } else {
+                // Newton's method, with a fallback on bisection.
+                let (mut tripn, mut tripb) = (false, false);
+                let (mut salp1a, mut calp1a) = (self.tiny, 1.);
+                let (mut salp1b, mut calp1b) = (self.tiny, -1.);
+                let mut lambda = Lambda::default();
+                for numit in 0..MAX_IT2 {
+                    lambda = self.lambda12(
+                        sbet1,
+                        cbet1,
+                        dn1,
+                        sbet2,
+                        cbet2,
+                        dn2,
+                        salp1,
+                        calp1,
+                        slam12,
+                        clam12,
+                        numit < MAX_IT1,
+                    );
+                    let v = lambda.lam12;
+                    (salp2, calp2) = (lambda.salp2, lambda.calp2);
+
+                    if tripb
+                        || abs(v) < if tripn { 8. } else { 1. } * TOL0
+                        || v.is_nan()
+                    {
+                        break;
+                    }
+                    // Update bracketing values.
+                    if v > 0.
+                        && (numit > MAX_IT1 || calp1 / salp1 > calp1b / salp1b)
+                    {
+                        (salp1b, calp1b) = (salp1, calp1);
+                    } else if v < 0.
+                        && (numit > MAX_IT1 || calp1 / salp1 < calp1a / salp1a)
+                    {
+                        (salp1a, calp1a) = (salp1, calp1);
+                    }
+                    if numit < MAX_IT1 && lambda.dlam12 > 0. {
+                        let dalp1 = -v / lambda.dlam12;
+                        let (sdalp1, cdalp1) = (sin(dalp1), cos(dalp1));
+                        let nsalp1 = salp1 * cdalp1 + calp1 * sdalp1;
+                        if nsalp1 > 0. && abs(dalp1) < PI {
+                            (salp1, calp1) =
+                                norm(nsalp1, calp1 * cdalp1 - salp1 * sdalp1);
+                            // Avoid oscillations near the solution.
+                            tripn = abs(v) <= 16. * TOL0;
+                            continue;
+                        }
+                    }
+                    // Either dv was not positive or updated value was outside
+                    // the legal range: use the midpoint of the bracket.
+                    (salp1, calp1) = norm(
+                        f64::midpoint(salp1a, salp1b),
+                        f64::midpoint(calp1a, calp1b),
+                    );
+                    tripn = false;
+                    tripb = abs(salp1a - salp1) + (calp1a - calp1) < self.tolb
+                        || abs(salp1 - salp1b) + (calp1 - calp1b) < self.tolb;
+                }
+
+                let (s12b, _) = Self::lengths(
+                    lambda.eps,
+                    lambda.sig12,
+                    lambda.ssig1,
+                    lambda.csig1,
+                    dn1,
+                    lambda.ssig2,
+                    lambda.csig2,
+                    dn2,
+                );
+                s12x = s12b * self.b;
+                let (sdomg12, cdomg12) =
+                    (sin(lambda.domg12), cos(lambda.domg12));
+                somg12 = slam12 * cdomg12 - clam12 * sdomg12;
+                comg12 = clam12 * cdomg12 + slam12 * sdomg12;
+            }
+        }
+
+        let area = self.area(
+            sbet1, cbet1, sbet2, cbet2, salp1, calp1, salp2, calp2, meridian,
+            somg12, comg12, omg12,
+        ) * swapp
+            * lonsign
+            * latsign
+            + 0.;
+
+        // Convert calp, salp to head accounting for lonsign, swapp, latsign.
+        if swapp < 0. {
+            mem::swap(&mut salp1, &mut salp2);
+            mem::swap(&mut calp1, &mut calp2);
+        }
+        salp1 *= swapp * lonsign;
+        calp1 *= swapp * latsign;
+
+        Inverse {
+            distance: 0. + s12x,
+            azimuth: atan2d(salp1, calp1),
+            area,
+        }
+    }
+
+    /// Computes the area between the geodesic and the equator (unsigned
+    /// convention of the reduced problem).
+    fn area(
+        &self,
+        sbet1: f64,
+        cbet1: f64,
+        sbet2: f64,
+        cbet2: f64,
+        salp1: f64,
+        calp1: f64,
+        salp2: f64,
+        calp2: f64,
+        meridian: bool,
+        mut somg12: f64,
+        mut comg12: f64,
+        omg12: f64,
+    ) -> f64 {
+        let salp0 = salp1 * cbet1;
+        let calp0 = hypot(calp1, salp1 * sbet1);
+        let mut area = if calp0 != 0. && salp0 != 0. {
+            let (ssig1, csig1) = norm(sbet1, calp1 * cbet1);
+            let (ssig2, csig2) = norm(sbet2, calp2 * cbet2);
+            let k2 = calp0 * calp0 * self.ep2;
+            let eps = k2 / (2. * (1. + sqrt(1. + k2)) + k2);
+            let a4 = self.a * self.a * calp0 * salp0 * self.e2;
+            let c4a = self.c4f(eps);
+            let b41 = sin_cos_series(false, ssig1, csig1, &c4a);
+            let b42 = sin_cos_series(false, ssig2, csig2, &c4a);
+            a4 * (b42 - b41)
+        } else {
+            // Avoid problems with indeterminate sig1, sig2 on equator.
+            0.
+        };
+
+        if !meridian && somg12 > 1. {
+            (somg12, comg12) = (sin(omg12), cos(omg12));
+        }
+
+        let alp12 =
+            if !meridian && comg12 > -FRAC_1_SQRT_2 && sbet2 - sbet1 < 1.75 {
+                // Use tan(Gamma/2) = tan(omg12/2)
+                // * (tan(bet1/2)+tan(bet2/2))/(1+tan(bet1/2)*tan(bet2/2))
+                // with tan(x/2) = sin(x)/(1+cos(x))
+                let domg12 = 1. + comg12;
+                let dbet1 = 1. + cbet1;
+                let dbet2 = 1. + cbet2;
+                2. * atan2(
+                    somg12 * (sbet1 * dbet2 + sbet2 * dbet1),
+                    domg12 * (sbet1 * sbet2 + dbet1 * dbet2),
+                )
+            } else {
+                // alp12 = alp2 - alp1, used in atan2 so no need to normalize.
+                let mut salp12 = salp2 * calp1 - calp2 * salp1;
+                let mut calp12 = calp2 * calp1 + salp2 * salp1;
+                if salp12 == 0. && calp12 < 0. {
+                    salp12 = self.tiny * calp1;
+                    calp12 = -1.;
+                }
+                atan2(salp12, calp12)
+            };
+        area += self.c2 * alp12;
+
+        area
+    }
+
+    /// Computes the (scaled) distance and reduced length.
+    fn lengths(
+        eps: f64,
+        sig12: f64,
+        ssig1: f64,
+        csig1: f64,
+        dn1: f64,
+        ssig2: f64,
+        csig2: f64,
+        dn2: f64,
+    ) -> (f64, f64) {
+        let mut c1a = [0.; ORDER + 1];
+        let mut c2a = [0.; ORDER + 1];
+        let mut a1 = a1m1f(eps);
+        c1f(eps, &mut c1a);
+        let mut a2 = a2m1f(eps);
+        c2f(eps, &mut c2a);
+        let m0x = a1 - a2;
+        a1 += 1.;
+        a2 += 1.;
+
+        let b1 = sin_cos_series(true, ssig2, csig2, &c1a)
+            - sin_cos_series(true, ssig1, csig1, &c1a);
+        let s12b = a1 * (sig12 + b1);
+        let b2 = sin_cos_series(true, ssig2, csig2, &c2a)
+            - sin_cos_series(true, ssig1, csig1, &c2a);
+        let j12 = m0x * sig12 + (a1 * b1 - a2 * b2);
+        // Missing a factor of b.
+        let m12b =
+            dn2 * (csig1 * ssig2) - dn1 * (ssig1 * csig2) - csig1 * csig2 * j12;
+
+        (s12b, m12b)
+    }
+
+    /// Computes a starting point for Newton's method.
+    ///
+    /// Returns `(sig12, salp1, calp1, salp2, calp2, dnm)`, where `sig12` is
+    /// negative unless the problem was solved directly (short lines).
+    fn inverse_start(
+        &self,
+        sbet1: f64,
+        cbet1: f64,
+        dn1: f64,
+        sbet2: f64,
+        cbet2: f64,
+        dn2: f64,
+        lam12: f64,
+        slam12: f64,
+        clam12: f64,
+    ) -> (f64, f64, f64, f64, f64, f64) {
+        let _ = (dn1, dn2);
+        let mut sig12 = -1.;
+        let (mut salp2, mut calp2, mut dnm) = (f64::NAN, f64::NAN, f64::NAN);
+
+        let sbet12 = sbet2 * cbet1 - cbet2 * sbet1;
+        let cbet12 = cbet2 * cbet1 + sbet2 * sbet1;
+        let sbet12a = sbet2 * cbet1 + cbet2 * sbet1;
+
+        let shortline = cbet12 >= 0. && sbet12 < 0.5 && cbet2 * lam12 < 0.5;
+        let (mut somg12, mut comg12) = if shortline {
+            let mut sbetm2 = (sbet1 + sbet2) * (sbet1 + sbet2);
+            sbetm2 /= sbetm2 + (cbet1 + cbet2) * (cbet1 + cbet2);
+            dnm = sqrt(1. + self.ep2 * sbetm2);
+            let omg12 = lam12 / (self.f1 * dnm);
+            (sin(omg12), cos(omg12))
+        } else {
+            (slam12, clam12)
+        };
+
+        let mut salp1 = cbet2 * somg12;
+        let mut calp1 = if comg12 >= 0. {
+            sbet12 + cbet2 * sbet1 * somg12 * somg12 / (1. + comg12)
+        } else {
+            sbet12a - cbet2 * sbet1 * somg12 * somg12 / (1. - comg12)
+        };
+
+        let ssig12 = hypot(salp1, calp1);
+        let csig12 = sbet1 * sbet2 + cbet1 * cbet2 * comg12;
+
+        if shortline && ssig12 < self.etol2 {
+            // Really short lines.
+            let (s, c) = norm(
+                cbet1 * somg12,
+                sbet12
+                    - cbet1
+                        * sbet2
+                        * if comg12 >= 0. {
+                            somg12 * somg12 / (1. + comg12)
+                        } else {
+                            1. - comg12
+                        },
+            );
+            (salp2, calp2) = (s, c);
+            sig12 = atan2(ssig12, csig12);
+        } else if abs(self.n) > 0.1
+            || csig12 >= 0.
+            || ssig12 >= 6. * abs(self.n) * PI * cbet1 * cbet1
+        {
+            // Nothing to do, zeroth order spherical approximation is OK.
+        } else {
+            // Scale lam12 and bet2 to x, y coordinate system where antipodal
+            // point is at origin and singular point is at y = 0, x = -1.
+            let lam12x = atan2(-slam12, -clam12);
+            let k2 = sbet1 * sbet1 * self.ep2;
+            let eps = k2 / (2. * (1. + sqrt(1. + k2)) + k2);
+            let lamscale = self.f * cbet1 * self.a3f(eps) * PI;
+            let betscale = lamscale * cbet1;
+            let x = lam12x / lamscale;
+            let y = sbet12a / betscale;
+
+            if y > -TOL1 && x > -1. - self.xthresh {
+                salp1 = (-x).min(1.);
+                calp1 = -sqrt(1. - salp1 * salp1);
+            } else {
+                let k = astroid(x, y);
+                let omg12a = lamscale * (-x * k / (1. + k));
+                (somg12, comg12) = (sin(omg12a), -cos(omg12a));
+                // Update spherical estimate of alp1 using omg12 instead of
+                // lam12.
+                salp1 = cbet2 * somg12;
+                calp1 =
+                    sbet12a - cbet2 * sbet1 * somg12 * somg12 / (1. - comg12);
+            }
+        }
+
+        // Sanity check on starting guess.
+        if salp1 > 0. || salp1.is_nan() {
+            (salp1, calp1) = norm(salp1, calp1);
+        } else {
+            (salp1, calp1) = (1., 0.);
+        }
+
+        (sig12, salp1, calp1, salp2, calp2, dnm)
+    }
+
+    /// Computes the longitude difference for a given azimuth at point 1 (and
+    /// its derivative, if requested).
+    fn lambda12(
+        &self,
+        sbet1: f64,
+        cbet1: f64,
+        dn1: f64,
+        sbet2: f64,
+        cbet2: f64,
+        dn2: f64,
+        salp1: f64,
+        mut calp1: f64,
+        slam120: f64,
+        clam120: f64,
+        diffp: bool,
+    ) -> Lambda {
+        if sbet1 == 0. && calp1 == 0. {
+            // Break degeneracy of equatorial line.
+            calp1 = -self.tiny;
+        }
+        let salp0 = salp1 * cbet1;
+        let calp0 = hypot(calp1, salp1 * sbet1);
+
+        let somg1 = salp0 * sbet1;
+        let comg1 = calp1 * cbet1;
+        let (ssig1, csig1) = norm(sbet1, calp1 * cbet1);
+
+        let salp2 = if cbet2 == cbet1 { salp1 } else { salp0 / cbet2 };
+        let calp2 = if cbet2 != cbet1 || abs(sbet2) != -sbet1 {
+            sqrt(
+                (calp1 * cbet1) * (calp1 * cbet1)
+                    + if cbet1 < -sbet1 {
+                        (cbet2 - cbet1) * (cbet1 + cbet2)
+                    } else {
+                        (sbet1 - sbet2) * (sbet1 + sbet2)
+                    },
+            ) / cbet2
+        } else {
+            abs(calp1)
+        };
+        let somg2 = salp0 * sbet2;
+        let comg2 = calp2 * cbet2;
+        let (ssig2, csig2) = norm(sbet2, calp2 * cbet2);
+
+        let sig12 = atan2(
+            (csig1 * ssig2 - ssig1 * csig2).max(0.),
+            csig1 * csig2 + ssig1 * ssig2,
+        );
+        let somg12 = (comg1 * somg2 - somg1 * comg2).max(0.);
+        let comg12 = comg1 * comg2 + somg1 * somg2;
+        let eta = atan2(
+            somg12 * clam120 - comg12 * slam120,
+            comg12 * clam120 + somg12 * slam120,
+        );
+
+        let k2 = calp0 * calp0 * self.ep2;
+        let eps = k2 / (2. * (1. + sqrt(1. + k2)) + k2);
+        let c3a = self.c3f(eps);
+        let b312 = sin_cos_series(true, ssig2, csig2, &c3a)
+            - sin_cos_series(true, ssig1, csig1, &c3a);
+        let domg12 = -self.f * self.a3f(eps) * salp0 * (sig12 + b312);
+        let lam12 = eta + domg12;
+
+        let dlam12 = if !diffp {
+            f64::NAN
+        } else if calp2 == 0. {
+            -2. * self.f1 * dn1 / sbet1
+        } else {
+            let (_, m12b) =
+                Self::lengths(eps, sig12, ssig1, csig1, dn1, ssig2, csig2, dn2);
+            m12b * self.f1 / (calp2 * cbet2)
+        };
+
+        Lambda {
+            lam12,
+            salp2,
+            calp2,
+            sig12,
+            ssig1,
+            csig1,
+            ssig2,
+            csig2,
+            eps,
+            domg12,
+            dlam12,
+        }
+    }
+
+    const fn a3f(&self, eps: f64) -> f64 {
+        polyval(ORDER - 1, &self.a3x, eps)
+    }
+
+    fn c3f(&self, eps: f64) -> [f64; ORDER] {
+        let mut c = [0.; ORDER];
+        let (mut mult, mut o) = (1., 0);
+        for (l, coeff) in c.iter_mut().enumerate().skip(1) {
+            let m = ORDER - l - 1;
+            mult *= eps;
+            *coeff = mult * polyval(m, &self.c3x[o..], eps);
+            o += m + 1;
+        }
+        c
+    }
+
+    fn c4f(&self, eps: f64) -> [f64; ORDER] {
+        let mut c = [0.; ORDER];
+        let (mut mult, mut o) = (1., 0);
+        for (l, coeff) in c.iter_mut().enumerate() {
+            let m = ORDER - l - 1;
+            *coeff = mult * polyval(m, &self.c4x[o..], eps);
+            o += m + 1;
+            mult *= eps;
+        }
+        c
+    }
+}
+
+/// Intermediate results of `Geodesic::lambda12`.
+#[derive(Debug, Default, Clone, Copy)]
+struct Lambda {
+    lam12: f64,
+    salp2: f64,
+    calp2: f64,
+    sig12: f64,
+    ssig1: f64,
+    csig1: f64,
+    ssig2: f64,
+    csig2: f64,
+    eps: f64,
+    domg12: f64,
+    dlam12: f64,
+}
+
+// -----------------------------------------------------------------------------
+
+/// Evaluates a polynomial of degree `n` (Horner's method).
+const fn polyval(n: usize, p: &[f64], x: f64) -> f64 {
+    let mut y = p[0];
+    let mut i = 1;
+    while i <= n {
+        y = y * x + p[i];
+        i += 1;
+    }
+    y
+}
+
+/// Returns the minimum of two integers, in a const context.
+const fn min(x: usize, y: usize) -> usize {
+    if x < y { x } else { y }
+}
+
+/// Evaluates `y = sinp ? sum(c[i] * sin(2*i * x), i, 1, n) : sum(c[i] *
+/// cos((2*i+1) * x), i, 0, n-1)` (Clenshaw summation).
+fn sin_cos_series(sinp: bool, sinx: f64, cosx: f64, c: &[f64]) -> f64 {
+    let mut k = c.len();
+    let mut n = k - usize::from(sinp);
+    let ar = 2. * (cosx - sinx) * (cosx + sinx);
+    let mut y1 = 0.;
+    let mut y0 = if n & 1 == 1 {
+        k -= 1;
+        c[k]
+    } else {
+        0.
+    };
+    n /= 2;
+    while n > 0 {
+        n -= 1;
+        k -= 1;
+        y1 = ar * y0 - y1 + c[k];
+        k -= 1;
+        y0 = ar * y1 - y0 + c[k];
+    }
+
+    if sinp {
+        2. * sinx * cosx * y0
+    } else {
+        cosx * (y0 - y1)
+    }
+}
+
+/// Solves the astroid equation `k⁴+2k³-(x²+y²-1)k²-2y²k-y² = 0` for the
+/// positive root `k`.
+fn astroid(x: f64, y: f64) -> f64 {
+    let p = x * x;
+    let q = y * y;
+    let r = (p + q - 1.) / 6.;
+    if q == 0. && r <= 0. {
+        return 0.;
+    }
+    let s = p * q / 4.;
+    let r2 = r * r;
+    let r3 = r * r2;
+    let disc = s * (s + 2. * r3);
+    let mut u = r;
+    if disc >= 0. {
+        let mut t3 = s + r3;
+        t3 += if t3 < 0. { -sqrt(disc) } else { sqrt(disc) };
+        let t = cbrt(t3);
+        u += t + if t == 0. { 0. } else { r2 / t };
+    } else {
+        let ang = atan2(sqrt(-disc), -(s + r3));
+        u += 2. * r * cos(ang / 3.);
+    }
+    let v = sqrt(u * u + q);
+    let uv = if u < 0. { q / (v - u) } else { u + v };
+    let w = (uv - q) / (2. * v);
+
+    uv / (sqrt(uv + w * w) + w)
+}
+
+fn a1m1f(eps: f64) -> f64 {
+    const COEFF: [f64; 5] = [1., 4., 64., 0., 256.];
+    let m = ORDER / 2;
+    let t = polyval(m, &COEFF, eps * eps) / COEFF[m + 1];
+    (t + eps) / (1. - eps)
+}
+
+fn c1f(eps: f64, c: &mut [f64; ORDER + 1]) {
+    const COEFF: [f64; 18] = [
+        -1., 6., -16., 32., -9., 64., -128., 2048., 9., -16., 768., 3., -5.,
+        512., -7., 1280., -7., 2048.,
+    ];
+    series(eps, &COEFF, c);
+}
+
+fn a2m1f(eps: f64) -> f64 {
+    const COEFF: [f64; 5] = [-11., -28., -192., 0., 256.];
+    let m = ORDER / 2;
+    let t = polyval(m, &COEFF, eps * eps) / COEFF[m + 1];
+    (t - eps) / (1. + eps)
+}
+
+fn c2f(eps: f64, c: &mut [f64; ORDER + 1]) {
+    const COEFF: [f64; 18] = [
+        1., 2., 16., 32., 35., 64., 384., 2048., 15., 80., 768., 7., 35., 512.,
+        63., 1280., 77., 2048.,
+    ];
+    series(eps, &COEFF, c);
+}
+
+/// Evaluates the coefficients of the `C1` or `C2` series.
+fn series(eps: f64, coeffs: &[f64], c: &mut [f64; ORDER + 1]) {
+    let eps2 = eps * eps;
+    let mut d = eps;
+    let mut o = 0;
+    for (l, coeff) in c.iter_mut().enumerate().skip(1) {
+        let m = (ORDER - l) / 2;
+        *coeff = d * polyval(m, &coeffs[o..], eps2) / coeffs[o + m + 1];
+        o += m + 2;
+        d *= eps;
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Error-free transformation of a sum.
+fn sum(u: f64, v: f64) -> (f64, f64) {
+    let s = u + v;
+    let up = s - v;
+    let vpp = s - up;
+    (s, -((up - u) + (vpp - v)))
+}
+
+/// Rounds an angle so that small values underflow to 0.
+fn ang_round(x: f64) -> f64 {
+    // The smallest gap in x is 1/2^57, i.e. 0.7 pm on the Earth.
+    const Z: f64 = 1. / 16.;
+    let mut y = abs(x);
+    if y < Z {
+        y = Z - (Z - y);
+    }
+    if x == 0. {
+        0.
+    } else if x < 0. {
+        -y
+    } else {
+        y
+    }
+}
+
+/// Reduces an angle to `(-180, 180]`.
+fn ang_normalize(x: f64) -> f64 {
+    let z = if x == 0. { x } else { x % 360. };
+    let y = if z < -180. {
+        z + 360.
+    } else if z < 180. {
+        z
+    } else {
+        z - 360.
+    };
+    if y == -180. { 180. } else { y }
+}
+
+/// Computes `y - x`, reduced to `[-180, 180]`, accurately.
+fn ang_diff(x: f64, y: f64) -> (f64, f64) {
+    let (d, t) = sum(ang_normalize(-x), ang_normalize(y));
+    let d = ang_normalize(d);
+    if d == 180. && t > 0. {
+        sum(-180., t)
+    } else {
+        sum(d, t)
+    }
+}
+
+/// Computes the sine and cosine of an angle in degrees, with exact values
+/// for multiples of 90°.
+fn sincosd(x: f64) -> (f64, f64) {
+    let r = x % 360.;
+    let q = round(r / 90.);
+    let r = (r - 90. * q).to_radians();
+    let (s, c) = (sin(r), cos(r));
+    #[expect(clippy::cast_possible_truncation, reason = "q is in [-4, 4]")]
+    let (sinx, cosx) = match (q as i32).rem_euclid(4) {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    };
+
+    (if sinx == 0. { 0. } else { sinx }, cosx + 0.)
+}
+
+/// Computes `atan2(y, x)`, in degrees, with exact values for multiples of
+/// 90°.
+fn atan2d(y: f64, x: f64) -> f64 {
+    let (mut x, mut y) = (x, y);
+    let mut q = if abs(y) > abs(x) {
+        mem::swap(&mut x, &mut y);
+        2
+    } else {
+        0
+    };
+    if x < 0. {
+        q += 1;
+        x = -x;
+    }
+    let ang = atan2(y, x).to_degrees();
+    match q {
+        0 => ang,
+        1 => (if y >= 0. { 180. } else { -180. }) - ang,
+        2 => 90. - ang,
+        _ => -90. + ang,
+    }
+}
+
+/// Normalizes a two-vector.
+fn norm(x: f64, y: f64) -> (f64, f64) {
+    let r = hypot(x, y);
+    (x / r, y / r)
+}
+
+/// Returns 1 or -1 if crossing the prime meridian eastward or westward,
+/// otherwise 0.
+fn transit(lon1: f64, lon2: f64) -> i32 {
+    let (lon12, _) = ang_diff(lon1, lon2);
+    let lon1 = ang_normalize(lon1);
+    let lon2 = ang_normalize(lon2);
+    if lon12 > 0. && ((lon1 < 0. && lon2 >= 0.) || (lon1 > 0. && lon2 == 0.)) {
+        1
+    } else if lon12 < 0. && lon1 >= 0. && lon2 < 0. {
+        -1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+#[path = "./geodesic_tests.rs"]
+mod tests;
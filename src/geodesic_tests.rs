@@ -0,0 +1,20 @@
+use super::*;
+use crate::math::ln;
+use float_eq::assert_float_eq;
+
+#[test]
+fn wgs84_constants() {
+    let (a, f) = (WGS84_A, WGS84_F);
+    let e2 = f * (2. - f);
+    let b = a * (1. - f);
+    let e = sqrt(e2);
+    let c2 =
+        f64::midpoint(a * a, b * b * (e * ln((1. + e) / (1. - e)) / 2.) / e2);
+    let etol2 =
+        0.1 * sqrt(TOL0) / sqrt(abs(f).max(0.001) * (1. - f / 2.).min(1.) / 2.);
+
+    assert_eq!(TOL2, sqrt(TOL0));
+    assert_eq!(TINY, sqrt(f64::MIN_POSITIVE));
+    assert_float_eq!(WGS84.c2, c2, r2nd <= f64::EPSILON);
+    assert_float_eq!(WGS84.etol2, etol2, r2nd <= f64::EPSILON);
+}
@@ -0,0 +1,341 @@
+use super::{ContainmentMode, TilerBuilder};
+use crate::{CellIndex, LatLng, Resolution, error::InvalidGeometry};
+use ahash::{HashMap, HashMapExt, HashSet};
+use geo::{Coord, Intersects as _, MultiPolygon, Polygon};
+use std::ops::Range;
+
+/// An index of polygons, answering spatial joins through H3 cells.
+///
+/// Each polygon is covered by cells at the index resolution: cells fully
+/// contained in the polygon are compacted (i.e. stored at the coarsest
+/// possible resolution) and match points without any geometry test, while
+/// cells on the polygon boundary fall back to an exact test.
+///
+/// ```rust
+/// use geo::polygon;
+/// use h3o::{geom::PolygonIndexBuilder, LatLng, Resolution};
+///
+/// let mut index = PolygonIndexBuilder::new(Resolution::Nine).build();
+/// let id = index.add(polygon![
+///     (x: 2.2, y: 48.8), (x: 2.5, y: 48.8), (x: 2.5, y: 48.9), (x: 2.2, y: 48.9),
+/// ])?;
+///
+/// let point = LatLng::new(48.85, 2.35)?;
+/// assert_eq!(index.containing(point).collect::<Vec<_>>(), vec![id]);
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolygonIndex {
+    resolution: Resolution,
+    convert_to_rads: bool,
+    transmeridian_heuristic_enabled: bool,
+    /// Polygons, in radians and with transmeridian polygons split.
+    polygons: Vec<MultiPolygon>,
+    /// Polygons covering each cell.
+    cells: HashMap<CellIndex, Vec<Entry>>,
+    /// Bitset of the resolutions of the indexed cells.
+    resolutions: u16,
+}
+
+impl PolygonIndex {
+    /// Adds a polygon to the index.
+    ///
+    /// Returns the identifier of the polygon, i.e. its insertion rank.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidGeometry`] if the polygon is invalid.
+    pub fn add(&mut self, polygon: Polygon) -> Result<usize, InvalidGeometry> {
+        let mut builder = TilerBuilder::new(self.resolution)
+            .containment_mode(ContainmentMode::Covers);
+        if !self.convert_to_rads {
+            builder = builder.disable_radians_conversion();
+        }
+        if !self.transmeridian_heuristic_enabled {
+            builder = builder.disable_transmeridian_heuristic();
+        }
+        let mut tiler = builder.build();
+        tiler.add(polygon)?;
+
+        let id = self.polygons.len();
+        self.polygons.push(tiler.geometry().clone());
+
+        let mut interior = Vec::new();
+        let mut seen = HashSet::default();
+        for cell in tiler.into_annotated_coverage() {
+            if !seen.insert(cell.cell) {
+                continue;
+            }
+            if cell.is_fully_contained {
+                interior.push(cell.cell);
+            } else {
+                self.insert(cell.cell, Entry::new(id, false));
+            }
+        }
+        CellIndex::compact(&mut interior).expect("unique cells");
+        for cell in interior {
+            self.insert(cell, Entry::new(id, true));
+        }
+
+        Ok(id)
+    }
+
+    /// Adds a batch of polygons to the index.
+    ///
+    /// Returns the range of identifiers of the polygons.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidGeometry`] if one of the polygon is invalid. Polygons before
+    /// the invalid one are kept in the index.
+    pub fn add_batch(
+        &mut self,
+        polygons: impl IntoIterator<Item = Polygon>,
+    ) -> Result<Range<usize>, InvalidGeometry> {
+        let start = self.polygons.len();
+        for polygon in polygons {
+            self.add(polygon)?;
+        }
+
+        Ok(start..self.polygons.len())
+    }
+
+    /// Returns the resolution of the index.
+    #[must_use]
+    pub const fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// Returns the number of polygons in the index.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.polygons.len()
+    }
+
+    /// Returns true if the index contains no polygon.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.polygons.is_empty()
+    }
+
+    /// Returns the identifiers of the polygons containing the given point.
+    ///
+    /// Points on the boundary of a polygon are considered as contained.
+    pub fn containing(
+        &self,
+        point: LatLng,
+    ) -> impl Iterator<Item = usize> + '_ {
+        let coord = Coord {
+            x: point.lng_radians(),
+            y: point.lat_radians(),
+        };
+
+        self.lookup(point.to_cell(self.resolution))
+            .filter_map(move |entry| {
+                (entry.is_interior
+                    || self.polygons[entry.id].intersects(&coord))
+                .then_some(entry.id)
+            })
+    }
+
+    /// Joins points with the polygons containing them.
+    ///
+    /// Returns pairs of (point rank, polygon identifier).
+    pub fn join_points(
+        &self,
+        points: impl IntoIterator<Item = LatLng>,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        points.into_iter().enumerate().flat_map(|(rank, point)| {
+            self.containing(point).map(move |id| (rank, id))
+        })
+    }
+
+    /// Returns the pairs of polygons, from `self` and `other`, that may
+    /// overlap.
+    ///
+    /// Candidates are the pairs of polygons covering overlapping cells (at
+    /// any resolution). When two of those cells are fully contained in their
+    /// respective polygons, the polygons are known to overlap.
+    ///
+    /// The candidates are sorted by identifiers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use geo::polygon;
+    /// use h3o::{geom::PolygonIndexBuilder, Resolution};
+    ///
+    /// let mut lhs = PolygonIndexBuilder::new(Resolution::Seven).build();
+    /// lhs.add(polygon![
+    ///     (x: 2.2, y: 48.8), (x: 2.5, y: 48.8), (x: 2.5, y: 48.9), (x: 2.2, y: 48.9),
+    /// ])?;
+    /// let mut rhs = PolygonIndexBuilder::new(Resolution::Nine).build();
+    /// rhs.add(polygon![
+    ///     (x: 2.4, y: 48.85), (x: 2.6, y: 48.85), (x: 2.6, y: 49.), (x: 2.4, y: 49.),
+    /// ])?;
+    ///
+    /// let candidates = lhs.overlap_candidates(&rhs);
+    /// assert_eq!(candidates.len(), 1);
+    /// assert!(candidates[0].is_certain);
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn overlap_candidates(&self, other: &Self) -> Vec<OverlapCandidate> {
+        let mut candidates = HashMap::<(usize, usize), bool>::new();
+        let mut add = |lhs: Entry, rhs: Entry| {
+            let is_certain = lhs.is_interior && rhs.is_interior;
+            *candidates.entry((lhs.id, rhs.id)).or_default() |= is_certain;
+        };
+
+        // Cells overlap iff one is an ancestor of (or equal to) the other.
+        for (&cell, entries) in &self.cells {
+            for other_entries in other.ancestors(cell) {
+                for &lhs in entries {
+                    for &rhs in other_entries {
+                        add(lhs, rhs);
+                    }
+                }
+            }
+        }
+        for (&cell, entries) in &other.cells {
+            // Strict ancestors only, equal cells are already handled.
+            let ancestors = self
+                .ancestors(cell)
+                .skip(usize::from(self.cells.contains_key(&cell)));
+            for self_entries in ancestors {
+                for &rhs in entries {
+                    for &lhs in self_entries {
+                        add(lhs, rhs);
+                    }
+                }
+            }
+        }
+
+        let mut candidates = candidates
+            .into_iter()
+            .map(|((left, right), is_certain)| OverlapCandidate {
+                left,
+                right,
+                is_certain,
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_unstable_by_key(|candidate| {
+            (candidate.left, candidate.right)
+        });
+        candidates
+    }
+
+    /// Returns the polygons covering the given cell, or one of its ancestor.
+    fn lookup(&self, cell: CellIndex) -> impl Iterator<Item = Entry> + '_ {
+        self.ancestors(cell).flatten().copied()
+    }
+
+    /// Returns the entries of the indexed ancestors of the given cell,
+    /// starting with the cell itself.
+    fn ancestors(
+        &self,
+        cell: CellIndex,
+    ) -> impl Iterator<Item = &Vec<Entry>> + '_ {
+        Resolution::range(Resolution::Zero, cell.resolution())
+            .rev()
+            .filter(|&resolution| {
+                self.resolutions & (1 << u8::from(resolution)) != 0
+            })
+            .filter_map(move |resolution| {
+                let ancestor = cell.parent(resolution).expect("coarser");
+                self.cells.get(&ancestor)
+            })
+    }
+
+    /// Indexes a cell.
+    fn insert(&mut self, cell: CellIndex, entry: Entry) {
+        self.resolutions |= 1 << u8::from(cell.resolution());
+        self.cells.entry(cell).or_default().push(entry);
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A pair of polygons that may overlap.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct OverlapCandidate {
+    /// Identifier of the polygon in the first index.
+    pub left: usize,
+    /// Identifier of the polygon in the second index.
+    pub right: usize,
+    /// Whether the polygons are known to overlap, i.e. without requiring an
+    /// exact geometry test.
+    pub is_certain: bool,
+}
+
+/// A polygon covering a cell.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    /// Identifier of the polygon.
+    id: usize,
+    /// Whether the cell is fully contained in the polygon.
+    is_interior: bool,
+}
+
+impl Entry {
+    const fn new(id: usize, is_interior: bool) -> Self {
+        Self { id, is_interior }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A builder to configure a polygon index.
+#[derive(Debug, Clone)]
+pub struct PolygonIndexBuilder {
+    resolution: Resolution,
+    convert_to_rads: bool,
+    transmeridian_heuristic_enabled: bool,
+}
+
+impl PolygonIndexBuilder {
+    /// Initializes a new polygon index builder with default settings.
+    ///
+    /// The resolution controls the size of the boundary cells: finer
+    /// resolutions need more memory but trigger less geometry tests.
+    #[must_use]
+    pub const fn new(resolution: Resolution) -> Self {
+        Self {
+            resolution,
+            convert_to_rads: true,
+            transmeridian_heuristic_enabled: true,
+        }
+    }
+
+    /// Disable the degrees-to-radians conversion pre-processing.
+    #[must_use]
+    pub const fn disable_radians_conversion(mut self) -> Self {
+        self.convert_to_rads = false;
+        self
+    }
+
+    /// Disable the transmeridian heuristic.
+    ///
+    /// See [`TilerBuilder::disable_transmeridian_heuristic`].
+    #[must_use]
+    pub const fn disable_transmeridian_heuristic(mut self) -> Self {
+        self.transmeridian_heuristic_enabled = false;
+        self
+    }
+
+    /// Builds the polygon index.
+    #[must_use]
+    pub fn build(self) -> PolygonIndex {
+        PolygonIndex {
+            resolution: self.resolution,
+            convert_to_rads: self.convert_to_rads,
+            transmeridian_heuristic_enabled: self
+                .transmeridian_heuristic_enabled,
+            polygons: Vec::new(),
+            cells: HashMap::new(),
+            resolutions: 0,
+        }
+    }
+}
@@ -1,13 +1,21 @@
-use super::VertexGraph;
-use crate::{CellIndex, Resolution, error::DissolutionError};
-use geo::MultiPolygon;
+use super::{RingHierarchy, VertexGraph};
+use crate::{
+    CellIndex, EdgeRing, Resolution, ShapeMetrics, Simplifier,
+    error::{DissolutionError, InvalidParameter, ResolutionMismatch},
+    outline::{EdgeGraph, InputMode, InputOptions},
+    topology,
+};
+use geo::{LineString, MultiPolygon};
 
 /// A solvent that dissolves a set of H3 cell indexes into a `MultiPolygon`
 /// representing the outlines of the set.
+///
+/// See [`Dissolver`](crate::Dissolver) for a `geo`-free alternative, with
+/// support for out-of-core processing.
 #[derive(Debug, Clone, Copy)]
 pub struct Solvent {
-    input_mode: InputMode,
-    check_duplicate: bool,
+    input: InputOptions,
+    simplifier: Option<Simplifier>,
 }
 
 impl Solvent {
@@ -33,21 +41,132 @@ impl Solvent {
         &self,
         cells: impl IntoIterator<Item = CellIndex>,
     ) -> Result<MultiPolygon, DissolutionError> {
-        let graph = match self.input_mode {
+        if let Some(simplifier) = self.simplifier {
+            return self.dissolve_simplified(cells, simplifier);
+        }
+
+        let graph = match self.input.mode {
             InputMode::Homogeneous => {
-                VertexGraph::from_homogeneous(cells, self.check_duplicate)
+                VertexGraph::from_homogeneous(cells, self.input.check_duplicate)
             }
             InputMode::Heterogeneous(resolution) => {
                 VertexGraph::from_heterogeneous(
                     cells,
                     resolution,
-                    self.check_duplicate,
+                    self.input.check_duplicate,
                 )
             }
         }?;
 
         Ok(graph.into())
     }
+
+    /// Computes the outline(s) of a set of cells, as rings of directed edges.
+    ///
+    /// Unlike [`Self::dissolve`], the H3 topology is preserved: rings are made
+    /// of [`DirectedEdgeIndex`](crate::DirectedEdgeIndex) (whose origin is in
+    /// the set) and [`VertexIndex`](crate::VertexIndex).
+    ///
+    /// Rings are returned in a deterministic order, holes included (see
+    /// [`EdgeRing::is_hole`]).
+    ///
+    /// # Errors
+    ///
+    /// All cell indexes must be unique and have the expected resolution,
+    /// otherwise [`DissolutionError`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{geom::SolventBuilder, CellIndex, Resolution};
+    ///
+    /// let index = CellIndex::try_from(0x089283470803ffff)?;
+    /// let cells = index.children(Resolution::Twelve).collect::<Vec<_>>();
+    /// let solvent = SolventBuilder::new().build();
+    /// let rings = solvent.outline(cells)?;
+    /// let perimeter = rings.iter().map(|ring| ring.length_m()).sum::<f64>();
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn outline(
+        &self,
+        cells: impl IntoIterator<Item = CellIndex>,
+    ) -> Result<Vec<EdgeRing>, DissolutionError> {
+        self.input.edge_graph(cells).map(EdgeGraph::into_rings)
+    }
+
+    /// Computes the geodesic shape metrics of each connected region of a set
+    /// of cells.
+    ///
+    /// Regions are sorted by their smallest cell. Their perimeter and edges
+    /// include the boundaries of their holes. The simplification setting, if
+    /// any, is ignored: metrics are always computed on the exact outlines.
+    ///
+    /// In heterogeneous mode, metrics are computed cell by cell: coarse cells
+    /// are expanded into their children at the target resolution, thus the
+    /// cost grows sevenfold per resolution of difference.
+    ///
+    /// # Errors
+    ///
+    /// All cell indexes must be unique and have the expected resolution,
+    /// otherwise [`DissolutionError`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{geom::SolventBuilder, CellIndex};
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let solvent = SolventBuilder::new().build();
+    /// let regions = solvent.metrics(index.grid_ring::<Vec<_>>(1))?;
+    /// assert_eq!(regions.len(), 1);
+    /// assert!(regions[0].perimeter_m() > 6. * index.perimeter_m() / 2.);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn metrics(
+        &self,
+        cells: impl IntoIterator<Item = CellIndex>,
+    ) -> Result<Vec<ShapeMetrics>, DissolutionError> {
+        let cells = self.input.cells(cells)?;
+
+        topology::connected_components(cells)
+            .map_err(|ResolutionMismatch| {
+                DissolutionError::UnsupportedResolution
+            })?
+            .into_iter()
+            .map(|component| {
+                let rings = InputOptions::new()
+                    .disable_duplicate_detection()
+                    .edge_graph(component.cells().iter().copied())?
+                    .into_rings();
+                Ok(ShapeMetrics::from_region(component.cells(), &rings))
+            })
+            .collect()
+    }
+
+    /// Dissolves then simplifies a set of cells.
+    fn dissolve_simplified(
+        self,
+        cells: impl IntoIterator<Item = CellIndex>,
+        simplifier: Simplifier,
+    ) -> Result<MultiPolygon, DissolutionError> {
+        let rings = match self.input.mode {
+            InputMode::Homogeneous => simplifier.simplify(cells)?,
+            // The simplifier always checks for duplicates.
+            InputMode::Heterogeneous(_) => simplifier.simplify(
+                self.input.disable_duplicate_detection().cells(cells)?,
+            )?,
+        };
+        let rings = rings
+            .into_iter()
+            .map(|ring| {
+                ring.into_iter()
+                    .map(geo::Coord::from)
+                    .collect::<LineString>()
+            })
+            .collect::<Vec<_>>();
+
+        Ok(RingHierarchy::new(rings).into())
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -55,8 +174,8 @@ impl Solvent {
 /// A builder to configure a solvent.
 #[derive(Debug, Clone, Copy)]
 pub struct SolventBuilder {
-    input_mode: InputMode,
-    check_duplicate: bool,
+    input: InputOptions,
+    simplifier: Option<Simplifier>,
 }
 
 impl Default for SolventBuilder {
@@ -70,8 +189,8 @@ impl SolventBuilder {
     #[must_use]
     pub const fn new() -> Self {
         Self {
-            input_mode: InputMode::Homogeneous,
-            check_duplicate: true,
+            input: InputOptions::new(),
+            simplifier: None,
         }
     }
 
@@ -89,7 +208,7 @@ impl SolventBuilder {
     /// check.
     #[must_use]
     pub const fn disable_duplicate_detection(mut self) -> Self {
-        self.check_duplicate = false;
+        self.input = self.input.disable_duplicate_detection();
         self
     }
 
@@ -106,24 +225,33 @@ impl SolventBuilder {
         mut self,
         resolution: Resolution,
     ) -> Self {
-        self.input_mode = InputMode::Heterogeneous(resolution);
+        self.input = self.input.enable_heterogeneous_support(resolution);
         self
     }
 
+    /// Enable the simplification of the outlines, with a tolerance in radians.
+    ///
+    /// The simplification preserves the topology: rings never cross each
+    /// other (see [`Simplifier`] for more details). Duplicate detection is
+    /// always performed in this mode.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidParameter`] if the tolerance is negative or not finite.
+    pub fn enable_simplification(
+        mut self,
+        tolerance: f64,
+    ) -> Result<Self, InvalidParameter> {
+        self.simplifier = Some(Simplifier::new(tolerance)?);
+        Ok(self)
+    }
+
     /// Builds the plotter.
     #[must_use]
     pub const fn build(self) -> Solvent {
         Solvent {
-            input_mode: self.input_mode,
-            check_duplicate: self.check_duplicate,
+            input: self.input,
+            simplifier: self.simplifier,
         }
     }
 }
-
-#[derive(Debug, Clone, Copy)]
-enum InputMode {
-    /// An homogeneous set of cells.
-    Homogeneous,
-    /// An heterogeneous set of cells (e.g. compacted) with a max resolution.
-    Heterogeneous(Resolution),
-}
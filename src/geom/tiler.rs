@@ -1,5 +1,8 @@
 use super::neighbors;
-use crate::{CellIndex, LatLng, Resolution, TWO_PI, error::InvalidGeometry};
+use crate::{
+    CellBitmap, CellIndex, LatLng, Resolution, TWO_PI,
+    error::{InvalidGeometry, ResolutionMismatch},
+};
 use ahash::{HashSet, HashSetExt};
 use either::Either;
 use float_eq::float_eq;
@@ -16,6 +19,7 @@ use geo::{
 use std::{
     cmp,
     f64::consts::{FRAC_PI_2, PI},
+    ops::RangeInclusive,
 };
 
 /// A tiler that produces an H3 coverage of the given shapes.
@@ -86,6 +90,12 @@ impl Tiler {
         Ok(())
     }
 
+    /// Returns the geometries to tile, in radians and with transmeridian
+    /// polygons split.
+    pub(crate) const fn geometry(&self) -> &MultiPolygon {
+        &self.geom
+    }
+
     /// Returns an upper bound to the number of cells returned by `into_coverage`.
     ///
     /// # Example
@@ -168,6 +178,71 @@ impl Tiler {
         self.into_annotated_coverage().map(|value| value.cell)
     }
 
+    /// Computes the cell coverage of the geometries, as a bitmap.
+    ///
+    /// Unlike [`Self::into_coverage`], the output is free of duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use geo::{LineString, Polygon};
+    /// use h3o::{geom::TilerBuilder, Resolution};
+    ///
+    /// let polygon = Polygon::new(
+    ///     LineString::from(vec![(0., 0.), (1., 1.), (1., 0.), (0., 0.)]),
+    ///     vec![],
+    /// );
+    /// let mut tiler = TilerBuilder::new(Resolution::Six).build();
+    /// tiler.add(polygon)?;
+    ///
+    /// let bitmap = tiler.into_bitmap();
+    /// assert_eq!(bitmap.resolution(), Resolution::Six);
+    ///
+    /// # Ok::<(), h3o::error::InvalidGeometry>(())
+    /// ```
+    #[must_use]
+    pub fn into_bitmap(self) -> CellBitmap {
+        let resolution = self.resolution;
+        CellBitmap::from_cells(resolution, self.into_coverage())
+            .expect("coverage at the tiler resolution")
+    }
+
+    /// Computes the cell coverage of the geometries, as the minimal set of
+    /// ranges of keys (i.e. integer representations) spanned by its
+    /// descendants at `resolution`.
+    ///
+    /// See [`CellIndex::key_ranges`].
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if `resolution` is coarser than the tiler one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use geo::{LineString, Polygon};
+    /// use h3o::{geom::TilerBuilder, Resolution};
+    ///
+    /// let polygon = Polygon::new(
+    ///     LineString::from(vec![(0., 0.), (1., 1.), (1., 0.), (0., 0.)]),
+    ///     vec![],
+    /// );
+    /// let mut tiler = TilerBuilder::new(Resolution::Six).build();
+    /// tiler.add(polygon)?;
+    ///
+    /// for range in tiler.into_key_ranges(Resolution::Nine)? {
+    ///     // Scan the store from `range.start()` to `range.end()`.
+    /// }
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn into_key_ranges(
+        self,
+        resolution: Resolution,
+    ) -> Result<Vec<RangeInclusive<u64>>, ResolutionMismatch> {
+        CellIndex::key_ranges(resolution, self.into_coverage())
+    }
+
     /// Computes the annotated cell coverage of the geometries.
     ///
     /// The output may contain duplicate indexes in case of overlapping input
@@ -1,15 +1,8 @@
-use crate::{CellIndex, Direction};
+use crate::{CellIndex, Direction, collections::Set};
 use alloc::collections::VecDeque;
 
 #[cfg(feature = "std")]
-use ahash::{HashSet, HashSetExt};
-#[cfg(not(feature = "std"))]
-use alloc::collections::BTreeSet;
-
-#[cfg(not(feature = "std"))]
-type Set<K> = BTreeSet<K>;
-#[cfg(feature = "std")]
-type Set<K> = HashSet<K>;
+use ahash::HashSetExt;
 
 /// Direction to the next ring.
 const NEXT_RING_DIRECTION: Direction = Direction::I;
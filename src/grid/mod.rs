@@ -3,3 +3,30 @@ mod iterator;
 
 pub use algo::{direction_for_neighbor, neighbor_rotations};
 pub use iterator::{DiskDistancesSafe, DiskDistancesUnsafe, RingUnsafe};
+
+// Return the immediate neighbors, no memory allocations.
+pub fn neighbors(cell: crate::CellIndex, scratchpad: &mut [u64]) -> usize {
+    let mut count = 0;
+
+    // Don't use `grid_disk` to avoid the allocation,
+    // use the pre-allocated scratchpad memory instead.
+    for candidate in cell.grid_disk_fast(1) {
+        if let Some(neighbor) = candidate {
+            scratchpad[count] = neighbor.into();
+            count += 1;
+        } else {
+            count = 0;
+            break;
+        }
+    }
+
+    // Unsafe version failed, fallback on the safe version.
+    if count == 0 {
+        for candidate in cell.grid_disk_safe(1) {
+            scratchpad[count] = candidate.into();
+            count += 1;
+        }
+    }
+
+    count
+}
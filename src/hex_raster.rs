@@ -0,0 +1,767 @@
+//! Dense hexagonal rasters over a local `IJ` coordinate space.
+//!
+//! A [`HexRaster`] stores one optional value per cell of a rectangular window
+//! of [`LocalIJ`](crate::LocalIJ) coordinates anchored by an origin cell.
+//! Neighborhood operations are then simple offsets in this window, which is a
+//! lot cheaper than repeated neighbor lookups through hash maps.
+//!
+//! Since it relies on local `IJ` coordinates, the same limitations apply: the
+//! raster is only meaningful near its anchor (practically, within the same
+//! base cell or a neighboring one) and pentagon distortion may result in
+//! conversion errors.
+
+use crate::{
+    CellIndex, CoordIJ, LocalIJ,
+    coord::CoordIJK,
+    error::{HexGridError, InvalidParameter, LocalIjError},
+};
+use alloc::{vec, vec::Vec};
+use core::{cmp, iter};
+
+/// `IJ` offsets of the six neighbors of a cell, in CCW order.
+const NEIGHBORS: [(i32, i32); 6] =
+    [(0, 1), (-1, 0), (-1, -1), (0, -1), (1, 0), (1, 1)];
+
+// -----------------------------------------------------------------------------
+
+/// A dense 2D raster of values on the hexagonal grid.
+///
+/// Values are stored on a rectangular window of local `IJ` coordinates, each
+/// slot being either empty or holding a value.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{CellIndex, HexRaster};
+///
+/// let anchor = CellIndex::try_from(0x8a1fb46622dffff)?;
+/// let cells = anchor.grid_disk::<Vec<_>>(2);
+/// let raster = HexRaster::from_cells(anchor, cells.iter().map(|&cell| (cell, 1)))?;
+/// assert_eq!(raster.count(), 19);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexRaster<T> {
+    /// Anchor cell of the local `IJ` coordinate space.
+    anchor: CellIndex,
+    /// Coordinate of the first slot.
+    min: CoordIJ,
+    /// Number of slots along the `i` axis.
+    width: usize,
+    /// Number of slots along the `j` axis.
+    height: usize,
+    /// Row-major (along `i`) storage.
+    values: Vec<Option<T>>,
+}
+
+impl<T> HexRaster<T> {
+    /// Initializes an empty raster covering `width` x `height` slots, starting
+    /// at `min`.
+    ///
+    /// # Errors
+    ///
+    /// [`HexGridError`] if the extent goes beyond the `IJ` coordinate space, or
+    /// if the number of slots cannot be represented.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, CoordIJ, HexRaster};
+    ///
+    /// let anchor = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let raster = HexRaster::<f64>::new(anchor, CoordIJ::new(-2, -2), 5, 5)?;
+    /// assert_eq!(raster.count(), 0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(
+        anchor: CellIndex,
+        min: CoordIJ,
+        width: usize,
+        height: usize,
+    ) -> Result<Self, HexGridError> {
+        let count = width.checked_mul(height).ok_or_else(overflow)?;
+        if count != 0 {
+            // The last slot must be addressable.
+            coord_at(min, width, count - 1).ok_or_else(overflow)?;
+        }
+
+        Ok(Self {
+            anchor,
+            min,
+            width,
+            height,
+            values: iter::repeat_with(|| None).take(count).collect(),
+        })
+    }
+
+    /// Builds a raster from a sparse set of cells.
+    ///
+    /// The raster extent is the bounding box of the input cells in the local
+    /// `IJ` coordinate space of `anchor`.
+    /// If a cell appears more than once, the last value wins.
+    ///
+    /// # Errors
+    ///
+    /// [`LocalIjError`] if a cell cannot be expressed in the local `IJ`
+    /// coordinate space of the anchor (resolution mismatch, too far away,
+    /// pentagon distortion, …).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, HexRaster};
+    ///
+    /// let anchor = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let cells = anchor.grid_disk::<Vec<_>>(1);
+    /// let raster = HexRaster::from_cells(anchor, cells.iter().map(|&cell| (cell, 1.)))?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_cells(
+        anchor: CellIndex,
+        cells: impl IntoIterator<Item = (CellIndex, T)>,
+    ) -> Result<Self, LocalIjError> {
+        let values = cells
+            .into_iter()
+            .map(|(cell, value)| {
+                cell.to_local_ij(anchor).map(|local| (local.coord, value))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let Some(((min_i, min_j), (max_i, max_j))) =
+            values.iter().fold(None, |acc, (coord, _)| {
+                let ((min_i, min_j), (max_i, max_j)) =
+                    acc.unwrap_or(((coord.i, coord.j), (coord.i, coord.j)));
+                Some((
+                    (cmp::min(min_i, coord.i), cmp::min(min_j, coord.j)),
+                    (cmp::max(max_i, coord.i), cmp::max(max_j, coord.j)),
+                ))
+            })
+        else {
+            let origin = anchor.to_local_ij(anchor)?.coord;
+            return Ok(Self::new(anchor, origin, 0, 0)?);
+        };
+
+        let width = extent(min_i, max_i)?;
+        let height = extent(min_j, max_j)?;
+        let mut raster =
+            Self::new(anchor, CoordIJ::new(min_i, min_j), width, height)?;
+        for (coord, value) in values {
+            let offset = raster.offset(coord).expect("in bounds");
+            raster.values[offset] = Some(value);
+        }
+
+        Ok(raster)
+    }
+
+    /// Returns the anchor cell of the raster.
+    #[must_use]
+    pub const fn anchor(&self) -> CellIndex {
+        self.anchor
+    }
+
+    /// Returns the coordinate of the lower corner of the raster.
+    #[must_use]
+    pub const fn min(&self) -> CoordIJ {
+        self.min
+    }
+
+    /// Returns the number of slots along the `i` axis.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of slots along the `j` axis.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the number of non-empty slots.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.values.iter().filter(|value| value.is_some()).count()
+    }
+
+    /// Returns true if the coordinate lies inside the raster extent.
+    #[must_use]
+    pub fn contains(&self, coord: CoordIJ) -> bool {
+        self.offset(coord).is_some()
+    }
+
+    /// Returns a reference to the value at the given coordinate, if any.
+    #[must_use]
+    pub fn get(&self, coord: CoordIJ) -> Option<&T> {
+        self.offset(coord)
+            .and_then(|offset| self.values[offset].as_ref())
+    }
+
+    /// Returns a mutable reference to the value at the given coordinate, if
+    /// any.
+    pub fn get_mut(&mut self, coord: CoordIJ) -> Option<&mut T> {
+        self.offset(coord)
+            .and_then(|offset| self.values[offset].as_mut())
+    }
+
+    /// Sets the value at the given coordinate, growing the raster extent if
+    /// needed.
+    ///
+    /// Returns the previous value, if any.
+    ///
+    /// # Errors
+    ///
+    /// [`HexGridError`] if the new extent cannot be represented.
+    pub fn insert(
+        &mut self,
+        coord: CoordIJ,
+        value: T,
+    ) -> Result<Option<T>, HexGridError> {
+        if !self.contains(coord) {
+            self.grow(coord)?;
+        }
+        let offset = self.offset(coord).expect("in bounds");
+        Ok(self.values[offset].replace(value))
+    }
+
+    /// Removes the value at the given coordinate, returning it if any.
+    pub fn remove(&mut self, coord: CoordIJ) -> Option<T> {
+        self.offset(coord)
+            .and_then(|offset| self.values[offset].take())
+    }
+
+    /// Returns the cell index at the given coordinate.
+    ///
+    /// # Errors
+    ///
+    /// [`LocalIjError`] if the coordinate cannot be mapped back to a cell.
+    pub fn cell_at(&self, coord: CoordIJ) -> Result<CellIndex, LocalIjError> {
+        CellIndex::try_from(LocalIJ::new(self.anchor, coord))
+    }
+
+    /// Returns the coordinate of the given cell index.
+    ///
+    /// # Errors
+    ///
+    /// [`LocalIjError`] if the cell cannot be expressed in the local `IJ`
+    /// coordinate space of the anchor.
+    pub fn coord_of(&self, cell: CellIndex) -> Result<CoordIJ, LocalIjError> {
+        cell.to_local_ij(self.anchor).map(|local| local.coord)
+    }
+
+    /// Iterates over the non-empty slots.
+    pub fn iter(&self) -> impl Iterator<Item = (CoordIJ, &T)> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(offset, value)| {
+                value.as_ref().map(|value| (self.coord(offset), value))
+            })
+    }
+
+    /// Iterates over the non-empty slots, as cell indexes.
+    ///
+    /// # Errors
+    ///
+    /// Items are [`LocalIjError`] when a slot cannot be mapped back to a cell.
+    pub fn cells(
+        &self,
+    ) -> impl Iterator<Item = Result<(CellIndex, &T), LocalIjError>> {
+        self.iter()
+            .map(|(coord, value)| self.cell_at(coord).map(|cell| (cell, value)))
+    }
+
+    /// Converts the raster back into a sparse set of cells.
+    ///
+    /// # Errors
+    ///
+    /// Items are [`LocalIjError`] when a slot cannot be mapped back to a cell.
+    pub fn into_cells(
+        self,
+    ) -> impl Iterator<Item = Result<(CellIndex, T), LocalIjError>> {
+        let Self {
+            anchor,
+            min,
+            width,
+            values,
+            ..
+        } = self;
+        values
+            .into_iter()
+            .enumerate()
+            .filter_map(move |(offset, value)| {
+                value.map(|value| {
+                    let coord = coord_at(min, width, offset)
+                        .expect("extent validated on construction");
+                    CellIndex::try_from(LocalIJ::new(anchor, coord))
+                        .map(|cell| (cell, value))
+                })
+            })
+    }
+
+    /// Applies a function on every value, producing a new raster with the
+    /// same extent.
+    #[must_use]
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> HexRaster<U> {
+        HexRaster {
+            anchor: self.anchor,
+            min: self.min,
+            width: self.width,
+            height: self.height,
+            values: self
+                .values
+                .iter()
+                .map(|value| value.as_ref().map(&mut f))
+                .collect(),
+        }
+    }
+
+    /// Iterates over the non-empty neighbors of the given coordinate.
+    pub fn neighbors(
+        &self,
+        coord: CoordIJ,
+    ) -> impl Iterator<Item = (CoordIJ, &T)> {
+        neighbors(coord).filter_map(|neighbor| {
+            self.get(neighbor).map(|value| (neighbor, value))
+        })
+    }
+
+    /// Applies a convolution kernel on the raster.
+    ///
+    /// The output has the same extent and the same non-empty slots as the
+    /// input. Each output value is the weighted sum of the non-empty slots
+    /// covered by the kernel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, HexKernel, HexRaster};
+    ///
+    /// let anchor = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let cells = anchor.grid_disk::<Vec<_>>(3);
+    /// let raster = HexRaster::from_cells(anchor, cells.iter().map(|&cell| (cell, 1.)))?;
+    /// let kernel = HexKernel::radial(1, |distance| if distance == 0 { 0.5 } else { 0.5 / 6. })?;
+    /// let smoothed = raster.convolve(&kernel);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn convolve(&self, kernel: &HexKernel) -> HexRaster<f64>
+    where
+        T: Copy + Into<f64>,
+    {
+        let values = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(offset, value)| {
+                value.map(|_| {
+                    let center = self.coord(offset);
+                    kernel
+                        .iter()
+                        .filter_map(|(delta, weight)| {
+                            let coord = translate(center, delta)?;
+                            self.get(coord).map(|&value| weight * value.into())
+                        })
+                        .sum()
+                })
+            })
+            .collect();
+
+        HexRaster {
+            anchor: self.anchor,
+            min: self.min,
+            width: self.width,
+            height: self.height,
+            values,
+        }
+    }
+
+    /// Morphological dilation by the unit disk (i.e. `grid_disk(1)`).
+    ///
+    /// A slot is non-empty in the output if it, or one of its neighbors, is
+    /// non-empty in the input. Its value is the maximum of those values.
+    ///
+    /// The output extent is one slot larger than the input in every direction.
+    ///
+    /// # Errors
+    ///
+    /// [`HexGridError`] if the new extent cannot be represented.
+    pub fn dilate(&self) -> Result<Self, HexGridError>
+    where
+        T: Copy + PartialOrd,
+    {
+        let (min_i, min_j) = (
+            self.min.i.checked_sub(1).ok_or_else(overflow)?,
+            self.min.j.checked_sub(1).ok_or_else(overflow)?,
+        );
+        let width = self.width.checked_add(2).ok_or_else(overflow)?;
+        let height = self.height.checked_add(2).ok_or_else(overflow)?;
+        // Also checks that the max side stays within the coordinate space.
+        let mut output =
+            Self::new(self.anchor, CoordIJ::new(min_i, min_j), width, height)?;
+        for offset in 0..output.values.len() {
+            let center = output.coord(offset);
+            output.values[offset] = iter::once(center)
+                .chain(neighbors(center))
+                .filter_map(|coord| self.get(coord).copied())
+                .reduce(|acc, value| if value > acc { value } else { acc });
+        }
+
+        Ok(output)
+    }
+
+    /// Morphological erosion by the unit disk (i.e. `grid_disk(1)`).
+    ///
+    /// A slot is non-empty in the output if it and all of its neighbors are
+    /// non-empty in the input. Its value is the minimum of those values.
+    ///
+    /// The output has the same extent as the input.
+    #[must_use]
+    pub fn erode(&self) -> Self
+    where
+        T: Copy + PartialOrd,
+    {
+        let values = (0..self.values.len())
+            .map(|offset| {
+                let center = self.coord(offset);
+                iter::once(center).chain(neighbors(center)).try_fold(
+                    None,
+                    |acc: Option<T>, coord| {
+                        let value = *self.get(coord)?;
+                        Some(Some(match acc {
+                            Some(acc) if acc < value => acc,
+                            _ => value,
+                        }))
+                    },
+                )?
+            })
+            .collect();
+
+        Self {
+            anchor: self.anchor,
+            min: self.min,
+            width: self.width,
+            height: self.height,
+            values,
+        }
+    }
+
+    /// Labels the connected components of non-empty slots.
+    ///
+    /// Two slots are connected if they are neighbors (6-connectivity, i.e.
+    /// `grid_disk(1)`).
+    ///
+    /// Returns a raster with the same extent where each non-empty slot holds
+    /// its component label (starting from 0), and the number of components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, HexRaster};
+    ///
+    /// let anchor = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let cells = anchor.grid_disk::<Vec<_>>(1);
+    /// let raster = HexRaster::from_cells(anchor, cells.iter().map(|&cell| (cell, ())))?;
+    /// let (_labels, count) = raster.label_components();
+    /// assert_eq!(count, 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn label_components(&self) -> (HexRaster<usize>, usize) {
+        let mut labels = HexRaster {
+            anchor: self.anchor,
+            min: self.min,
+            width: self.width,
+            height: self.height,
+            values: vec![None; self.values.len()],
+        };
+        let mut count = 0;
+        let mut stack = Vec::new();
+
+        for offset in 0..self.values.len() {
+            if self.values[offset].is_none() || labels.values[offset].is_some()
+            {
+                continue;
+            }
+            labels.values[offset] = Some(count);
+            stack.push(offset);
+            while let Some(offset) = stack.pop() {
+                for neighbor in neighbors(self.coord(offset)) {
+                    let Some(neighbor) = self.offset(neighbor) else {
+                        continue;
+                    };
+                    if self.values[neighbor].is_some()
+                        && labels.values[neighbor].is_none()
+                    {
+                        labels.values[neighbor] = Some(count);
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            count += 1;
+        }
+
+        (labels, count)
+    }
+
+    /// Flood-fills the region connected to `seed` with `value`.
+    ///
+    /// The region is made of the slots (empty or not) connected to the seed,
+    /// through neighbors, that hold the same value as the seed. The fill never
+    /// goes past the raster extent.
+    ///
+    /// Returns the number of slots updated.
+    pub fn flood_fill(&mut self, seed: CoordIJ, value: &T) -> usize
+    where
+        T: Clone + PartialEq,
+    {
+        let Some(start) = self.offset(seed) else {
+            return 0;
+        };
+        let target = self.values[start].clone();
+        if target.as_ref() == Some(value) {
+            return 0;
+        }
+
+        let mut count = 0;
+        let mut stack = vec![start];
+        self.values[start] = Some(value.clone());
+        while let Some(offset) = stack.pop() {
+            count += 1;
+            for neighbor in neighbors(self.coord(offset)) {
+                let Some(neighbor) = self.offset(neighbor) else {
+                    continue;
+                };
+                if self.values[neighbor] == target {
+                    self.values[neighbor] = Some(value.clone());
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Returns the storage offset of the given coordinate, if in bounds.
+    fn offset(&self, coord: CoordIJ) -> Option<usize> {
+        let i = usize::try_from(coord.i.checked_sub(self.min.i)?).ok()?;
+        let j = usize::try_from(coord.j.checked_sub(self.min.j)?).ok()?;
+        (i < self.width && j < self.height).then(|| j * self.width + i)
+    }
+
+    /// Returns the coordinate of the given storage offset.
+    fn coord(&self, offset: usize) -> CoordIJ {
+        coord_at(self.min, self.width, offset)
+            .expect("extent validated on construction")
+    }
+
+    /// Grows the raster extent to include the given coordinate.
+    fn grow(&mut self, coord: CoordIJ) -> Result<(), HexGridError> {
+        let (min_i, min_j, max_i, max_j) = if self.values.is_empty() {
+            (coord.i, coord.j, coord.i, coord.j)
+        } else {
+            let max = self.coord(self.values.len() - 1);
+            (
+                cmp::min(self.min.i, coord.i),
+                cmp::min(self.min.j, coord.j),
+                cmp::max(max.i, coord.i),
+                cmp::max(max.j, coord.j),
+            )
+        };
+        let width = extent(min_i, max_i)?;
+        let height = extent(min_j, max_j)?;
+        let mut raster =
+            Self::new(self.anchor, CoordIJ::new(min_i, min_j), width, height)?;
+        let values = core::mem::take(&mut self.values);
+        for (offset, value) in values.into_iter().enumerate() {
+            let target =
+                raster.offset(self.coord(offset)).expect("in new bounds");
+            raster.values[target] = value;
+        }
+        *self = raster;
+
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A convolution kernel on the hexagonal grid.
+///
+/// The kernel covers every cell within a grid distance `radius` of the center.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HexKernel {
+    radius: u32,
+    /// Weights, stored as a square of `2 * radius + 1` side in `IJ` space.
+    ///
+    /// Slots outside the hexagon are left to zero.
+    weights: Vec<f64>,
+}
+
+impl HexKernel {
+    /// Maximum radius of a kernel.
+    ///
+    /// Weights are stored densely, so the memory footprint grows with the
+    /// square of the radius.
+    pub const MAX_RADIUS: u32 = 512;
+
+    /// Builds a kernel from a weight function of the `IJ` offset to the
+    /// center.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidParameter`] if the radius is larger than
+    /// [`Self::MAX_RADIUS`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::HexKernel;
+    ///
+    /// // Only weight the cells along the `i` axis.
+    /// let kernel = HexKernel::from_fn(2, |offset| if offset.j == 0 { 1. } else { 0. })?;
+    /// # Ok::<(), h3o::error::InvalidParameter>(())
+    /// ```
+    pub fn from_fn(
+        radius: u32,
+        mut f: impl FnMut(CoordIJ) -> f64,
+    ) -> Result<Self, InvalidParameter> {
+        if radius > Self::MAX_RADIUS {
+            return Err(InvalidParameter::new("kernel radius too large"));
+        }
+        let side = 2 * radius as usize + 1;
+        let weights = (0..side * side)
+            .map(|offset| match square_coord(radius, side, offset) {
+                Some(delta)
+                    if grid_distance(delta).is_some_and(|d| d <= radius) =>
+                {
+                    f(delta)
+                }
+                _ => 0.,
+            })
+            .collect();
+
+        Ok(Self { radius, weights })
+    }
+
+    /// Builds an isotropic kernel from a weight function of the grid distance
+    /// to the center.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidParameter`] if the radius is larger than
+    /// [`Self::MAX_RADIUS`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::HexKernel;
+    ///
+    /// let kernel = HexKernel::radial(2, |distance| 1. / f64::from(distance + 1))?;
+    /// # Ok::<(), h3o::error::InvalidParameter>(())
+    /// ```
+    pub fn radial(
+        radius: u32,
+        mut f: impl FnMut(u32) -> f64,
+    ) -> Result<Self, InvalidParameter> {
+        Self::from_fn(radius, |delta| grid_distance(delta).map_or(0., &mut f))
+    }
+
+    /// Returns the radius, in grid distance, of the kernel.
+    #[must_use]
+    pub const fn radius(&self) -> u32 {
+        self.radius
+    }
+
+    /// Returns the weight for the given `IJ` offset to the center, if covered
+    /// by the kernel.
+    #[must_use]
+    pub fn weight(&self, offset: CoordIJ) -> Option<f64> {
+        if grid_distance(offset)? > self.radius {
+            return None;
+        }
+        let radius = i32::try_from(self.radius).ok()?;
+        let side = 2 * self.radius as usize + 1;
+        let i = usize::try_from(offset.i.checked_add(radius)?).ok()?;
+        let j = usize::try_from(offset.j.checked_add(radius)?).ok()?;
+        Some(self.weights[j * side + i])
+    }
+
+    /// Returns a copy of the kernel whose weights sum to 1.
+    ///
+    /// Kernels whose weights sum to zero are returned unchanged.
+    #[must_use]
+    pub fn normalized(mut self) -> Self {
+        let sum = self.weights.iter().sum::<f64>();
+        if sum != 0. {
+            for weight in &mut self.weights {
+                *weight /= sum;
+            }
+        }
+        self
+    }
+
+    /// Iterates over the `IJ` offsets covered by the kernel, and their weight.
+    pub fn iter(&self) -> impl Iterator<Item = (CoordIJ, f64)> + '_ {
+        let side = 2 * self.radius as usize + 1;
+        self.weights
+            .iter()
+            .enumerate()
+            .filter_map(move |(offset, &weight)| {
+                let delta = square_coord(self.radius, side, offset)?;
+                grid_distance(delta)
+                    .is_some_and(|d| d <= self.radius)
+                    .then_some((delta, weight))
+            })
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Iterates over the six neighbors of the coordinate.
+fn neighbors(coord: CoordIJ) -> impl Iterator<Item = CoordIJ> {
+    NEIGHBORS
+        .iter()
+        .filter_map(move |&(i, j)| translate(coord, CoordIJ::new(i, j)))
+}
+
+/// Translates a coordinate, guarding against overflows.
+fn translate(coord: CoordIJ, delta: CoordIJ) -> Option<CoordIJ> {
+    Some(CoordIJ::new(
+        coord.i.checked_add(delta.i)?,
+        coord.j.checked_add(delta.j)?,
+    ))
+}
+
+/// Grid distance between the origin and the given `IJ` offset, if
+/// representable.
+fn grid_distance(delta: CoordIJ) -> Option<u32> {
+    // Once normalized, every component is positive and the largest one is the
+    // distance to the origin.
+    let ijk = CoordIJK::try_from(delta).ok()?;
+    Some(ijk.i().max(ijk.j()).max(ijk.k()).unsigned_abs())
+}
+
+/// Returns the coordinate of an offset in a raster, if representable.
+fn coord_at(min: CoordIJ, width: usize, offset: usize) -> Option<CoordIJ> {
+    let i = i32::try_from(offset % width).ok()?;
+    let j = i32::try_from(offset / width).ok()?;
+    translate(min, CoordIJ::new(i, j))
+}
+
+/// Returns the offset to the center of an offset in a kernel.
+fn square_coord(radius: u32, side: usize, offset: usize) -> Option<CoordIJ> {
+    let radius = i32::try_from(radius).ok()?;
+    coord_at(CoordIJ::new(-radius, -radius), side, offset)
+}
+
+/// Number of slots between two bounds (inclusive).
+fn extent(min: i32, max: i32) -> Result<usize, HexGridError> {
+    max.checked_sub(min)
+        .and_then(|delta| usize::try_from(delta).ok())
+        .and_then(|delta| delta.checked_add(1))
+        .ok_or_else(overflow)
+}
+
+const fn overflow() -> HexGridError {
+    HexGridError::new("raster extent overflow")
+}
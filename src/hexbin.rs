@@ -0,0 +1,450 @@
+//! Aggregation of point values into cells.
+//!
+//! Statistics are mergeable: those of a cell can be combined into those of
+//! its parent, and rolling up gives the same results (up to floating-point
+//! rounding) as aggregating the values of the descendants directly.
+//!
+//! Note that, since the H3 hierarchy is not geometrically exact, the ancestor
+//! of the cell containing a point is not always the coarser cell containing
+//! that point: rolled-up statistics follow the hierarchy.
+
+use crate::{
+    CellIndex, LatLng, Resolution,
+    collections::Map,
+    error::ResolutionMismatch,
+    math::{ceil, exp, ln, sqrt},
+};
+use alloc::collections::BTreeMap;
+
+/// Relative accuracy of the quantile estimates.
+const RELATIVE_ACCURACY: f64 = 0.01;
+/// Base of the logarithmic buckets of the quantile sketch.
+const GAMMA: f64 = (1. + RELATIVE_ACCURACY) / (1. - RELATIVE_ACCURACY);
+/// Values with a lower magnitude are counted as zero by the quantile sketch.
+const MIN_MAGNITUDE: f64 = 1e-9;
+
+/// Per-cell statistics of point values.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{HexBin, LatLng, Resolution};
+///
+/// let bins = HexBin::from_points(Resolution::Seven, [
+///     (LatLng::new(48.8566, 2.3522)?, 1.),
+///     (LatLng::new(48.8567, 2.3523)?, 3.),
+///     (LatLng::new(45.7640, 4.8357)?, 10.),
+/// ]);
+/// assert_eq!(bins.len(), 2);
+///
+/// let paris = LatLng::new(48.8566, 2.3522)?.to_cell(Resolution::Seven);
+/// let stats = bins.get(paris).expect("Paris");
+/// assert_eq!(stats.count(), 2);
+/// assert_eq!(stats.mean(), Some(2.));
+///
+/// let coarse = bins.roll_up(Resolution::Zero)?;
+/// assert_eq!(coarse.iter().map(|(_, stats)| stats.count()).sum::<u64>(), 3);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct HexBin {
+    resolution: Resolution,
+    cells: Map<CellIndex, CellStats>,
+}
+
+impl HexBin {
+    /// Initializes an empty aggregation at the given resolution.
+    #[must_use]
+    pub fn new(resolution: Resolution) -> Self {
+        Self {
+            resolution,
+            cells: Map::default(),
+        }
+    }
+
+    /// Aggregates a set of points and their values.
+    #[must_use]
+    pub fn from_points(
+        resolution: Resolution,
+        points: impl IntoIterator<Item = (LatLng, f64)>,
+    ) -> Self {
+        let mut bins = Self::new(resolution);
+        bins.insert_batch(points);
+        bins
+    }
+
+    /// Returns the resolution of the cells.
+    #[must_use]
+    pub const fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// Returns the number of non-empty cells.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns true if no value has been aggregated.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Adds a value, located at `point`, to the aggregation.
+    ///
+    /// Non-finite values (NaN and infinities) are ignored.
+    pub fn insert(&mut self, point: LatLng, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+        let cell = point.to_cell(self.resolution);
+        self.cells.entry(cell).or_default().insert(value);
+    }
+
+    /// Adds a batch of values to the aggregation.
+    ///
+    /// Non-finite values (NaN and infinities) are ignored.
+    pub fn insert_batch(
+        &mut self,
+        points: impl IntoIterator<Item = (LatLng, f64)>,
+    ) {
+        for (point, value) in points {
+            self.insert(point, value);
+        }
+    }
+
+    /// Returns the statistics of the given cell, if non-empty.
+    #[must_use]
+    pub fn get(&self, cell: CellIndex) -> Option<&CellStats> {
+        self.cells.get(&cell)
+    }
+
+    /// Returns an iterator over the non-empty cells and their statistics, in
+    /// no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (CellIndex, &CellStats)> {
+        self.cells.iter().map(|(cell, stats)| (*cell, stats))
+    }
+
+    /// Merges the statistics of another aggregation into this one.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if the aggregations have different resolutions.
+    pub fn merge(&mut self, other: &Self) -> Result<(), ResolutionMismatch> {
+        if other.resolution != self.resolution {
+            return Err(ResolutionMismatch);
+        }
+        for (&cell, stats) in &other.cells {
+            self.cells.entry(cell).or_default().merge(stats);
+        }
+
+        Ok(())
+    }
+
+    /// Aggregates the statistics at a coarser resolution.
+    ///
+    /// The statistics of each coarse cell are those of all the values in its
+    /// descendants (which can differ slightly from binning the points at that
+    /// resolution, near the cell boundaries).
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if `resolution` is finer than the resolution of
+    /// the aggregation.
+    pub fn roll_up(
+        &self,
+        resolution: Resolution,
+    ) -> Result<Self, ResolutionMismatch> {
+        if resolution > self.resolution {
+            return Err(ResolutionMismatch);
+        }
+
+        let mut bins = Self::new(resolution);
+        for (cell, stats) in &self.cells {
+            let parent = cell.parent(resolution).expect("coarser resolution");
+            bins.cells.entry(parent).or_default().merge(stats);
+        }
+
+        Ok(bins)
+    }
+
+    /// Returns the statistics of the given cell, at any resolution coarser
+    /// than or equal to the aggregation resolution.
+    ///
+    /// For coarser cells, the statistics of the descendants are merged, which
+    /// scans the whole aggregation: prefer [`Self::roll_up`] for bulk queries.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if the cell is finer than the resolution of the
+    /// aggregation.
+    pub fn stats(
+        &self,
+        cell: CellIndex,
+    ) -> Result<CellStats, ResolutionMismatch> {
+        let resolution = cell.resolution();
+        if resolution > self.resolution {
+            return Err(ResolutionMismatch);
+        }
+        if resolution == self.resolution {
+            return Ok(self.cells.get(&cell).cloned().unwrap_or_default());
+        }
+
+        let mut result = CellStats::default();
+        for (child, stats) in &self.cells {
+            if child.parent(resolution) == Some(cell) {
+                result.merge(stats);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Statistics of the values aggregated in a cell.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CellStats {
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+    mean: f64,
+    /// Sum of squared differences from the mean.
+    m2: f64,
+    sketch: QuantileSketch,
+}
+
+impl CellStats {
+    /// Returns the number of values.
+    #[must_use]
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the sum of the values.
+    #[must_use]
+    pub const fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Returns the lowest value, if any.
+    #[must_use]
+    pub const fn min(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.min)
+        }
+    }
+
+    /// Returns the highest value, if any.
+    #[must_use]
+    pub const fn max(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.max)
+        }
+    }
+
+    /// Returns the mean of the values, if any.
+    #[must_use]
+    pub const fn mean(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.mean)
+        }
+    }
+
+    /// Returns the (population) variance of the values, if any.
+    #[must_use]
+    #[expect(clippy::cast_precision_loss, reason = "counts are small enough")]
+    pub fn variance(&self) -> Option<f64> {
+        (self.count != 0).then(|| self.m2 / self.count as f64)
+    }
+
+    /// Returns the (population) standard deviation of the values, if any.
+    #[must_use]
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(sqrt)
+    }
+
+    /// Returns an estimate of the `q`-quantile of the values, if any.
+    ///
+    /// The estimate is within 1% (relative error) of the exact value, except
+    /// for values with a magnitude below 1e-9, which are estimated as zero.
+    /// Returns `None` if `q` is outside `[0, 1]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{HexBin, LatLng, Resolution};
+    ///
+    /// let point = LatLng::new(48.8566, 2.3522)?;
+    /// let bins = HexBin::from_points(
+    ///     Resolution::Seven,
+    ///     (1..=100).map(|value| (point, f64::from(value))),
+    /// );
+    /// let stats = bins.get(point.to_cell(Resolution::Seven)).expect("stats");
+    /// let median = stats.quantile(0.5).expect("median");
+    /// assert!((median - 50.).abs() <= 0.5);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss,
+        reason = "rank is between 0 and count"
+    )]
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.count == 0 || !(0. ..=1.).contains(&q) {
+            return None;
+        }
+        // Extremes are known exactly.
+        if q <= 0. {
+            return Some(self.min);
+        }
+        if q >= 1. {
+            return Some(self.max);
+        }
+        let rank = (q * (self.count - 1) as f64) as u64;
+        let value = self.sketch.value_at(rank);
+
+        Some(value.clamp(self.min, self.max))
+    }
+
+    /// Merges the statistics of another set of values into these ones.
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            self.clone_from(other);
+            return;
+        }
+
+        // Chan et al. parallel algorithm.
+        #[expect(clippy::cast_precision_loss, reason = "counts are small")]
+        let (lhs, rhs) = (self.count as f64, other.count as f64);
+        let total = lhs + rhs;
+        let delta = other.mean - self.mean;
+        self.mean += delta * rhs / total;
+        self.m2 += other.m2 + delta * delta * lhs * rhs / total;
+
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.sketch.merge(&other.sketch);
+    }
+
+    /// Adds a value.
+    fn insert(&mut self, value: f64) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+
+        // Welford online algorithm.
+        self.count += 1;
+        self.sum += value;
+        let delta = value - self.mean;
+        #[expect(clippy::cast_precision_loss, reason = "counts are small")]
+        let count = self.count as f64;
+        self.mean += delta / count;
+        self.m2 += delta * (value - self.mean);
+
+        self.sketch.insert(value);
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A mergeable quantile sketch, with a bounded relative error.
+///
+/// Values are counted in buckets whose bounds grow geometrically, so that
+/// every value in a bucket is within the relative accuracy of its center
+/// (see "`DDSketch`: A fast and fully-mergeable quantile sketch with
+/// relative-error guarantees", Masson et al.).
+#[derive(Debug, Clone, Default, PartialEq)]
+struct QuantileSketch {
+    /// Counts of the positive values, by bucket.
+    positives: BTreeMap<i32, u64>,
+    /// Counts of the negative values, by bucket of their magnitude.
+    negatives: BTreeMap<i32, u64>,
+    /// Count of the values close to zero.
+    zeros: u64,
+}
+
+impl QuantileSketch {
+    fn insert(&mut self, value: f64) {
+        if value > MIN_MAGNITUDE {
+            *self.positives.entry(bucket(value)).or_default() += 1;
+        } else if value < -MIN_MAGNITUDE {
+            *self.negatives.entry(bucket(-value)).or_default() += 1;
+        } else {
+            self.zeros += 1;
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        for (&index, &count) in &other.positives {
+            *self.positives.entry(index).or_default() += count;
+        }
+        for (&index, &count) in &other.negatives {
+            *self.negatives.entry(index).or_default() += count;
+        }
+        self.zeros += other.zeros;
+    }
+
+    /// Returns the estimated value of the given rank.
+    fn value_at(&self, rank: u64) -> f64 {
+        let negatives = self
+            .negatives
+            .iter()
+            .rev()
+            .map(|(&index, &count)| (-center(index), count));
+        let zeros = core::iter::once((0., self.zeros));
+        let positives = self
+            .positives
+            .iter()
+            .map(|(&index, &count)| (center(index), count));
+
+        let mut seen = 0;
+        let mut last = 0.;
+        for (value, count) in negatives.chain(zeros).chain(positives) {
+            seen += count;
+            if count != 0 {
+                last = value;
+            }
+            if seen > rank {
+                return value;
+            }
+        }
+
+        last
+    }
+}
+
+/// Returns the bucket of a positive value.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "bounded by the range of f64 exponents"
+)]
+fn bucket(value: f64) -> i32 {
+    ceil(ln(value) / ln(GAMMA)) as i32
+}
+
+/// Returns the center of a bucket, i.e. the value with the lowest relative
+/// error to every value of the bucket.
+fn center(index: i32) -> f64 {
+    2. * exp(f64::from(index) * ln(GAMMA)) / (GAMMA + 1.)
+}
@@ -0,0 +1,230 @@
+//! Safe construction of indexes from their components.
+//!
+//! This spares callers the manual bit-twiddling with [`bits`](super::bits):
+//! indexes are assembled from typed components, then validated as a whole.
+
+use super::{IndexMode, bits};
+use crate::{
+    BaseCell, CellIndex, DirectedEdgeIndex, Direction, Edge, Resolution,
+    Vertex, VertexIndex,
+    error::{InvalidCellIndex, InvalidDirectedEdgeIndex, InvalidVertexIndex},
+};
+
+/// Maximum number of directions in a cell index.
+const MAX_DIRECTIONS: usize = h3o_bit::MAX_RESOLUTION as usize;
+
+// -----------------------------------------------------------------------------
+
+/// A builder to construct a cell index from a base cell and its directions.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{BaseCell, CellIndex, CellIndexBuilder, Direction, Resolution};
+///
+/// let index = CellIndexBuilder::new(BaseCell::try_from(15)?)
+///     .directions([Direction::IJ, Direction::IJ])
+///     .direction(Direction::I)
+///     .build()?;
+/// assert_eq!(index, CellIndex::try_from(0x831fb4fffffffff)?);
+///
+/// // Missing directions are filled with the center child.
+/// let index = CellIndexBuilder::new(BaseCell::try_from(15)?)
+///     .resolution(Resolution::Five)
+///     .direction(Direction::IJ)
+///     .build()?;
+/// assert_eq!(index.to_path_string(), "15/60000");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CellIndexBuilder {
+    base_cell: BaseCell,
+    resolution: Option<Resolution>,
+    directions: [Direction; MAX_DIRECTIONS],
+    /// Number of directions pushed (may exceed the capacity).
+    count: usize,
+}
+
+impl CellIndexBuilder {
+    /// Initializes a new cell index builder, starting from a base cell.
+    #[must_use]
+    pub const fn new(base_cell: BaseCell) -> Self {
+        Self {
+            base_cell,
+            resolution: None,
+            directions: [Direction::Center; MAX_DIRECTIONS],
+            count: 0,
+        }
+    }
+
+    /// Set the resolution of the cell.
+    ///
+    /// The directions that aren't explicitly specified, up to this resolution,
+    /// are set to [`Direction::Center`].
+    ///
+    /// By default, the resolution is the number of directions.
+    #[must_use]
+    pub const fn resolution(mut self, resolution: Resolution) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+
+    /// Appends the direction of the next resolution.
+    #[must_use]
+    pub fn direction(mut self, direction: Direction) -> Self {
+        if let Some(slot) = self.directions.get_mut(self.count) {
+            *slot = direction;
+        }
+        self.count += 1;
+        self
+    }
+
+    /// Appends the directions of the next resolutions.
+    #[must_use]
+    pub fn directions(
+        self,
+        directions: impl IntoIterator<Item = Direction>,
+    ) -> Self {
+        directions.into_iter().fold(self, Self::direction)
+    }
+
+    /// Builds the cell index.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidCellIndex`] when there are more directions than the
+    /// resolution allows, or when the directions hit the deleted subsequence
+    /// of a pentagon (i.e. the first non-center direction of a pentagonal base
+    /// cell is [`Direction::K`]).
+    pub fn build(self) -> Result<CellIndex, InvalidCellIndex> {
+        let resolution = self.resolution.map_or(self.count, usize::from);
+        if self.count > MAX_DIRECTIONS {
+            return Err(InvalidCellIndex::new(None, "too many directions"));
+        }
+        if self.count > resolution {
+            return Err(InvalidCellIndex::new(
+                None,
+                "more directions than the resolution",
+            ));
+        }
+
+        CellIndex::from_directions(
+            self.base_cell,
+            &self.directions[..resolution],
+        )
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A builder to construct a directed edge index from its origin cell and the
+/// edge it goes through.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{CellIndex, DirectedEdgeIndex, DirectedEdgeIndexBuilder, Edge};
+///
+/// let origin = CellIndex::try_from(0x8a194e699ab7fff)?;
+/// let index = DirectedEdgeIndexBuilder::new(origin, Edge::try_from(3)?)
+///     .build()?;
+/// assert_eq!(index, DirectedEdgeIndex::try_from(0x13a194e699ab7fff)?);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DirectedEdgeIndexBuilder {
+    origin: CellIndex,
+    edge: Edge,
+}
+
+impl DirectedEdgeIndexBuilder {
+    /// Initializes a new directed edge index builder.
+    #[must_use]
+    pub const fn new(origin: CellIndex, edge: Edge) -> Self {
+        Self { origin, edge }
+    }
+
+    /// Set the origin cell of the edge.
+    #[must_use]
+    pub const fn origin(mut self, origin: CellIndex) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Set the edge of the origin cell.
+    #[must_use]
+    pub const fn edge(mut self, edge: Edge) -> Self {
+        self.edge = edge;
+        self
+    }
+
+    /// Builds the directed edge index.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidDirectedEdgeIndex`] when the origin is a pentagon and the edge
+    /// goes through its deleted `K` axis (i.e. edge 1).
+    pub fn build(self) -> Result<DirectedEdgeIndex, InvalidDirectedEdgeIndex> {
+        let bits = bits::set_mode(self.origin.into(), IndexMode::DirectedEdge);
+        DirectedEdgeIndex::try_from(bits::set_edge(bits, self.edge))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A builder to construct a vertex index from a cell and one of its vertexes.
+///
+/// Since a vertex is shared by up to three cells, the resulting index is
+/// expressed relative to its canonical owner (the cell with the lowest index),
+/// which may differ from the input cell.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{CellIndex, Vertex, VertexIndex, VertexIndexBuilder};
+///
+/// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+/// let index = VertexIndexBuilder::new(cell, Vertex::try_from(3)?).build()?;
+/// assert_eq!(index, VertexIndex::try_from(0x25a1fb464492ffff)?);
+/// assert_ne!(index.owner(), cell);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct VertexIndexBuilder {
+    cell: CellIndex,
+    vertex: Vertex,
+}
+
+impl VertexIndexBuilder {
+    /// Initializes a new vertex index builder.
+    #[must_use]
+    pub const fn new(cell: CellIndex, vertex: Vertex) -> Self {
+        Self { cell, vertex }
+    }
+
+    /// Set the cell the vertex belongs to.
+    #[must_use]
+    pub const fn cell(mut self, cell: CellIndex) -> Self {
+        self.cell = cell;
+        self
+    }
+
+    /// Set the vertex of the cell.
+    #[must_use]
+    pub const fn vertex(mut self, vertex: Vertex) -> Self {
+        self.vertex = vertex;
+        self
+    }
+
+    /// Builds the vertex index, relative to its canonical owner.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidVertexIndex`] when the cell is a pentagon and the vertex is
+    /// out of range (i.e. vertex 5).
+    pub fn build(self) -> Result<VertexIndex, InvalidVertexIndex> {
+        self.cell
+            .vertex(self.vertex)
+            .ok_or(InvalidVertexIndex::new(None, "invalid vertex number"))
+    }
+}
@@ -1,17 +1,25 @@
-use super::{Children, GridPathCells, Triangle};
+use super::{
+    CellSetDecoder, Children, GridPathCells, ShortCode, Triangle, codec,
+    key_range, locality, text,
+};
 use crate::{
-    BaseCell, Boundary, CCW, CW, DEFAULT_CELL_INDEX, DirectedEdgeIndex,
-    Direction, EARTH_RADIUS_KM, Edge, ExtendedResolution, FaceSet, LatLng,
-    LocalIJ, NUM_HEX_VERTS, NUM_PENT_VERTS, Resolution, Vertex, VertexIndex,
+    BaseCell, Boundary, CCW, CW, CellComponent, DEFAULT_CELL_INDEX,
+    DirectedEdgeIndex, Direction, EARTH_RADIUS_KM, EarthModel, Edge, EdgeRing,
+    ExtendedResolution, FaceSet, LatLng, LocalIJ, NUM_HEX_VERTS,
+    NUM_PENT_VERTS, Resolution, ShapeMetrics, Vertex, VertexIndex,
     coord::{CoordIJ, CoordIJK, FaceIJK, LocalIJK, Overage},
     error::{
-        CompactionError, HexGridError, InvalidCellIndex, LocalIjError,
-        ResolutionMismatch,
+        CompactionError, DecodingError, DissolutionError, HexGridError,
+        InvalidCellIndex, LocalIjError, ParsingError, ResolutionMismatch,
     },
-    grid,
+    geodesic, grid,
     index::{IndexMode, bits},
+    metrics, outline, topology,
+};
+use alloc::{
+    string::{String, ToString as _},
+    vec::Vec,
 };
-use alloc::vec::Vec;
 use core::{
     cmp::Ordering,
     fmt, iter,
@@ -173,7 +181,6 @@ const BASE_CELL: BaseCell = BaseCell::new_unchecked(2);
 /// - [H3 Index Bit Layout](https://observablehq.com/@nrabinowitz/h3-index-bit-layout?collection=@nrabinowitz/h3)
 /// - [H3 Index Inspector](https://observablehq.com/@nrabinowitz/h3-index-inspector?collection=@nrabinowitz/h3)
 #[derive(Clone, Copy, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 pub struct CellIndex(NonZeroU64);
 
@@ -270,6 +277,172 @@ impl CellIndex {
         self.area_km2() * 1000. * 1000.
     }
 
+    /// Computes the area of this H3 cell on the specified Earth model, in km².
+    ///
+    /// With [`EarthModel::Sphere`], this is the same as [`Self::area_km2`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let index = h3o::CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// float_eq::assert_float_eq!(
+    ///     index.area_km2_with(h3o::EarthModel::Wgs84),
+    ///     0.013449816300630569,
+    ///     abs <= 1e-11
+    /// );
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    #[must_use]
+    pub fn area_km2_with(self, model: EarthModel) -> f64 {
+        match model {
+            EarthModel::Sphere => self.area_km2(),
+            EarthModel::Wgs84 => self.area_m2_with(model) / (1000. * 1000.),
+        }
+    }
+
+    /// Computes the area of this H3 cell on the specified Earth model, in m².
+    ///
+    /// On the ellipsoid, the edges of the cell are approximated by geodesics
+    /// between its vertexes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let index = h3o::CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// float_eq::assert_float_eq!(
+    ///     index.area_m2_with(h3o::EarthModel::Wgs84),
+    ///     13449.81630063057,
+    ///     abs <= 1e-5
+    /// );
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    #[must_use]
+    pub fn area_m2_with(self, model: EarthModel) -> f64 {
+        match model {
+            EarthModel::Sphere => self.area_m2(),
+            EarthModel::Wgs84 => geodesic::WGS84.polygon_area(&self.boundary()),
+        }
+    }
+
+    /// Computes the perimeter of this H3 cell, in radians.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let index = h3o::CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// float_eq::assert_float_eq!(
+    ///     index.perimeter_rads(),
+    ///     6.778327274654177e-5,
+    ///     abs <= 1e-11
+    /// );
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    #[must_use]
+    pub fn perimeter_rads(self) -> f64 {
+        metrics::perimeter(&self.boundary())
+    }
+
+    /// Computes the perimeter of this H3 cell, in kilometers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let index = h3o::CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// float_eq::assert_float_eq!(
+    ///     index.perimeter_km(),
+    ///     0.4318477174143731,
+    ///     abs <= 1e-11
+    /// );
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    #[must_use]
+    pub fn perimeter_km(self) -> f64 {
+        self.perimeter_rads() * EARTH_RADIUS_KM
+    }
+
+    /// Computes the perimeter of this H3 cell, in meters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let index = h3o::CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// float_eq::assert_float_eq!(
+    ///     index.perimeter_m(),
+    ///     431.8477174143731,
+    ///     abs <= 1e-8
+    /// );
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    #[must_use]
+    pub fn perimeter_m(self) -> f64 {
+        self.perimeter_km() * 1000.
+    }
+
+    /// Parses a cell index from its hexadecimal (with or without `0x`
+    /// prefix), decimal, short code or path representation,
+    /// case-insensitively.
+    ///
+    /// Unlike the [`FromStr`] implementation, which only accepts the
+    /// hexadecimal representation, the format is deduced from the input.
+    ///
+    /// # Errors
+    ///
+    /// [`ParsingError`] if the input isn't a valid cell index in any of the
+    /// supported formats.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    ///
+    /// assert_eq!(CellIndex::parse("8a1fb46622dffff"), Ok(index));
+    /// assert_eq!(CellIndex::parse("0x8A1FB46622DFFFF"), Ok(index));
+    /// assert_eq!(CellIndex::parse("622054503267303423"), Ok(index));
+    /// assert_eq!(CellIndex::parse("a3yt6c8pr"), Ok(index));
+    /// assert_eq!(CellIndex::parse("15/6643142133"), Ok(index));
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, ParsingError> {
+        text::parse(s)
+    }
+
+    /// Returns the short code of this cell index: a compact, URL-safe,
+    /// textual representation.
+    ///
+    /// See [`ShortCode`] for the details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let index = h3o::CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// assert_eq!(index.short_code().to_string(), "a3yt6c8pr");
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    #[must_use]
+    pub fn short_code(self) -> ShortCode {
+        ShortCode::new(self)
+    }
+
+    /// Computes the geodesic shape metrics of this H3 cell.
+    ///
+    /// See [`ShapeMetrics`] for the details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let index = h3o::CellIndex::try_from(0x8009fffffffffff)?;
+    /// let metrics = index.metrics();
+    /// // Pentagons are the most compact cells.
+    /// assert!(metrics.compactness() > 0.85);
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    #[must_use]
+    pub fn metrics(self) -> ShapeMetrics {
+        ShapeMetrics::from_cell(self)
+    }
+
     /// Finds all icosahedron faces intersected this cell index
     ///
     /// # Example
@@ -405,6 +578,112 @@ impl CellIndex {
             })
     }
 
+    /// Returns the directions of the cell, from the coarsest resolution to the
+    /// finest one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Direction, Resolution};
+    ///
+    /// let index = CellIndex::try_from(0x831fb4fffffffff)?;
+    /// assert_eq!(index.directions().collect::<Vec<_>>(), vec![
+    ///     (Resolution::One, Direction::IJ),
+    ///     (Resolution::Two, Direction::IJ),
+    ///     (Resolution::Three, Direction::I),
+    /// ]);
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    #[must_use]
+    pub fn directions(
+        self,
+    ) -> impl DoubleEndedIterator<Item = (Resolution, Direction)> {
+        Resolution::range(Resolution::One, self.resolution()).map(
+            move |resolution| {
+                let value = bits::get_direction(self.0.get(), resolution);
+                (resolution, Direction::new_unchecked(value))
+            },
+        )
+    }
+
+    /// Initializes a cell index from a base cell and its directions, from the
+    /// coarsest resolution to the finest one.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidCellIndex`] when there are more than 15 directions, or when the
+    /// directions hit the deleted subsequence of a pentagon (i.e. the first
+    /// non-center direction of a pentagonal base cell is `K`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{BaseCell, CellIndex, Direction};
+    ///
+    /// let base_cell = BaseCell::try_from(15)?;
+    /// let directions = [Direction::IJ, Direction::IJ, Direction::I];
+    ///
+    /// assert_eq!(
+    ///     CellIndex::from_directions(base_cell, &directions),
+    ///     CellIndex::try_from(0x831fb4fffffffff)
+    /// );
+    ///
+    /// let pentagon = BaseCell::try_from(4)?;
+    /// let directions = [Direction::Center, Direction::K];
+    /// assert!(CellIndex::from_directions(pentagon, &directions).is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_directions(
+        base_cell: BaseCell,
+        directions: &[Direction],
+    ) -> Result<Self, InvalidCellIndex> {
+        let resolution = u8::try_from(directions.len())
+            .ok()
+            .and_then(|resolution| Resolution::try_from(resolution).ok())
+            .ok_or_else(|| {
+                InvalidCellIndex::new(None, "too many directions")
+            })?;
+
+        let bits = h3o_bit::set_base_cell(DEFAULT_CELL_INDEX, base_cell.into());
+        let bits = bits::set_resolution(bits, resolution);
+        let bits = Resolution::range(Resolution::One, resolution)
+            .zip(directions)
+            .fold(bits, |bits, (resolution, &direction)| {
+                bits::set_direction(bits, direction.into(), resolution)
+            });
+
+        Self::try_from(bits)
+    }
+
+    /// Returns the hierarchical path of the cell: its base cell, followed by
+    /// its directions (e.g. `20/1230`).
+    ///
+    /// Paths are parsed back by [`Self::parse`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    ///
+    /// let index = CellIndex::try_from(0x831fb4fffffffff)?;
+    /// assert_eq!(index.to_path_string(), "15/664");
+    /// assert_eq!(CellIndex::parse("15/664"), Ok(index));
+    ///
+    /// let base_cell = CellIndex::try_from(0x8015fffffffffff)?;
+    /// assert_eq!(base_cell.to_path_string(), "10/");
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    #[must_use]
+    pub fn to_path_string(self) -> String {
+        let mut path = self.base_cell().to_string();
+        path.push('/');
+        path.extend(
+            self.directions()
+                .map(|(_, direction)| char::from(b'0' + u8::from(direction))),
+        );
+        path
+    }
+
     /// Returns the parent, at the specified resolution, of the cell.
     ///
     /// # Example
@@ -631,6 +910,81 @@ impl CellIndex {
         Some(Self::new_unchecked(child))
     }
 
+    /// Returns the position of the cell among all the cells of its resolution,
+    /// in `0..resolution.cell_count()`.
+    ///
+    /// Ordinals follow the cell ordering (i.e. [`Self::succ`] increments the
+    /// ordinal by one), which makes them suitable to index dense arrays or
+    /// bitmaps covering a whole resolution.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// assert_eq!(index.ordinal(), 4_423_355_056);
+    /// assert_eq!(CellIndex::first(Resolution::Ten).ordinal(), 0);
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    #[must_use]
+    pub fn ordinal(self) -> u64 {
+        let position = self
+            .child_position(Resolution::Zero)
+            .expect("resolution 0 parent");
+        base_cell_offset(self.base_cell(), self.resolution()) + position
+    }
+
+    /// Returns the cell at the given position among all the cells of the
+    /// specified resolution.
+    ///
+    /// Returns `None` if the ordinal is out of bound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    ///
+    /// assert_eq!(
+    ///     CellIndex::from_ordinal(Resolution::Ten, 4_423_355_056),
+    ///     CellIndex::try_from(0x8a1fb46622dffff).ok()
+    /// );
+    /// assert_eq!(
+    ///     CellIndex::from_ordinal(Resolution::Zero, 122),
+    ///     None
+    /// );
+    /// ```
+    #[must_use]
+    pub fn from_ordinal(resolution: Resolution, ordinal: u64) -> Option<Self> {
+        if ordinal >= resolution.cell_count() {
+            return None;
+        }
+
+        // Pentagons have fewer children than hexagons, thus the base cell
+        // is at least the one we would get with only hexagons, and the
+        // pentagons preceding it can only shift it by a few base cells.
+        let hexagon_count = HEXAGON_CHILDREN_COUNTS[usize::from(resolution)];
+        let mut base_cell = u8::try_from(ordinal / hexagon_count)
+            .unwrap_or(BaseCell::count())
+            .min(BaseCell::count() - 1);
+        while base_cell + 1 < BaseCell::count()
+            && base_cell_offset(
+                BaseCell::new_unchecked(base_cell + 1),
+                resolution,
+            ) <= ordinal
+        {
+            base_cell += 1;
+        }
+        let base_cell = BaseCell::new_unchecked(base_cell);
+
+        let position = ordinal - base_cell_offset(base_cell, resolution);
+        Self::new_unchecked(h3o_bit::set_base_cell(
+            DEFAULT_CELL_INDEX,
+            base_cell.into(),
+        ))
+        .child_at(position, resolution)
+    }
+
     /// Return the children, at the specified resolution, of the cell index.
     ///
     /// # Example
@@ -770,6 +1124,310 @@ impl CellIndex {
             .flat_map(move |index| index.children(resolution))
     }
 
+    /// Encodes a set of unique cell indexes, all at the same resolution, into
+    /// a compact binary representation.
+    ///
+    /// The set is compacted, and each compacted cell is stored as the gap
+    /// between its first child and the previous compacted cell, which makes
+    /// the encoding of dense sets very compact (one or two bytes per compacted
+    /// cell).
+    ///
+    /// The format (version 1) is:
+    /// - the magic bytes `H3CS`;
+    /// - the version of the format, on one byte;
+    /// - the resolution of the set, on one byte;
+    /// - the number of compacted cells, as an unsigned LEB128 integer;
+    /// - for each compacted cell, in order, an unsigned LEB128 integer whose
+    ///   4 lowest bits hold the difference between the set resolution and the
+    ///   cell resolution, and the remaining bits hold the gap between the
+    ///   ordinal of the first child of the cell and the ordinal following the
+    ///   last child of the previous cell (or 0 for the first cell).
+    ///
+    /// See [`Self::ordinal`] for the definition of ordinals.
+    ///
+    /// # Errors
+    ///
+    /// All cell indexes must be unique and have the same resolution, otherwise
+    /// an [`CompactionError`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let cells = index.grid_disk::<Vec<_>>(10);
+    /// let bytes = CellIndex::encode_set(cells.iter().copied())?;
+    /// assert!(bytes.len() < cells.len() * 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn encode_set(
+        cells: impl IntoIterator<Item = Self>,
+    ) -> Result<Vec<u8>, CompactionError> {
+        codec::encode(cells)
+    }
+
+    /// Decodes a set of cells encoded with [`Self::encode_set`].
+    ///
+    /// Only the header is decoded upfront, cells are decoded (and yielded in
+    /// order) while iterating.
+    ///
+    /// # Errors
+    ///
+    /// [`DecodingError`] if the header is invalid. Errors in the rest of the
+    /// input are reported while iterating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let mut cells = index.grid_disk::<Vec<_>>(10);
+    /// let bytes = CellIndex::encode_set(cells.iter().copied())?;
+    /// let decoded = CellIndex::decode_set(&bytes)?
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// cells.sort_unstable();
+    /// assert_eq!(decoded, cells);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn decode_set(
+        bytes: &[u8],
+    ) -> Result<CellSetDecoder<'_>, DecodingError> {
+        CellSetDecoder::new(bytes)
+    }
+
+    /// Returns the range of keys (i.e. integer representations) spanned by the
+    /// descendants of the cell at the given resolution.
+    ///
+    /// Since cells are ordered hierarchically, no other cell at this
+    /// resolution lies within the range: it can be used as is to scan a sorted
+    /// key-value store for the descendants of the cell.
+    ///
+    /// Returns `None` if the resolution is coarser than the cell's one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let range = index.key_range(Resolution::Eleven).expect("range");
+    /// assert_eq!(range, 0x8b1fb46622d8fff..=0x8b1fb46622defff);
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    #[must_use]
+    pub fn key_range(
+        self,
+        resolution: Resolution,
+    ) -> Option<core::ops::RangeInclusive<u64>> {
+        key_range::range(self, resolution)
+    }
+
+    /// Returns the minimal set of ranges of keys (i.e. integer
+    /// representations) covering the descendants of a set of cells at the
+    /// given resolution.
+    ///
+    /// Input cells can be at any resolution coarser than or equal to
+    /// `resolution` (e.g. a compacted set), no uncompaction is performed.
+    /// Ranges are sorted and disjoint: overlapping ranges and ranges with no
+    /// cell in-between are merged.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if a cell is finer than `resolution`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let mut cells = index.grid_disk::<Vec<_>>(2);
+    /// CellIndex::compact(&mut cells)?;
+    /// let ranges = CellIndex::key_ranges(Resolution::Twelve, cells)?;
+    /// assert_eq!(ranges.len(), 9);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn key_ranges(
+        resolution: Resolution,
+        cells: impl IntoIterator<Item = Self>,
+    ) -> Result<Vec<core::ops::RangeInclusive<u64>>, ResolutionMismatch> {
+        key_range::ranges(resolution, cells)
+    }
+
+    /// Returns the position of the cell along a locality-preserving curve.
+    ///
+    /// Unlike the native ordering, which keeps the descendants of a cell
+    /// together, this key follows a Hilbert curve over the cells of each
+    /// icosahedron face, and faces are visited along a path of adjacent faces:
+    /// cells close to each other tend to have close keys, even across base
+    /// cells boundaries.
+    ///
+    /// Keys are unique among the cells of a given resolution, but are not
+    /// comparable across resolutions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let mut cells = index.grid_disk::<Vec<_>>(10);
+    /// cells.sort_by_cached_key(|cell| cell.locality_key());
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    #[must_use]
+    pub fn locality_key(self) -> u64 {
+        locality::key(self)
+    }
+
+    /// Compares two cells along the locality-preserving curve (see
+    /// [`Self::locality_key`]).
+    ///
+    /// Cells with the same key (i.e. at different resolutions) are ordered
+    /// natively, hence this is a total order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let mut cells = index.grid_disk::<Vec<_>>(10);
+    /// cells.sort_by(CellIndex::cmp_locality);
+    /// # Ok::<(), h3o::error::InvalidCellIndex>(())
+    /// ```
+    #[must_use]
+    pub fn cmp_locality(&self, other: &Self) -> Ordering {
+        self.locality_key()
+            .cmp(&other.locality_key())
+            .then_with(|| self.cmp(other))
+    }
+
+    /// Returns the minimal set of ranges of locality keys (see
+    /// [`Self::locality_key`]) covering the descendants of a set of cells at
+    /// the given resolution.
+    ///
+    /// Input cells can be coarser than `resolution` by up to 7 resolutions.
+    /// Ranges are sorted and disjoint, consecutive keys are merged. Note that
+    /// some keys, outside of the icosahedron faces, don't map to any cell: a
+    /// range may thus be split even though no cell lies between its
+    /// neighbors.
+    ///
+    /// Unlike [`Self::key_ranges`], the descendants of a cell don't follow
+    /// each other along the curve: every descendant is enumerated, hence the
+    /// cost grows sevenfold per resolution of difference.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if a cell is finer than `resolution`, or more
+    /// than 7 resolutions coarser.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let ranges = CellIndex::locality_ranges(Resolution::Twelve, [index])?;
+    /// assert!(ranges.len() < 49);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn locality_ranges(
+        resolution: Resolution,
+        cells: impl IntoIterator<Item = Self>,
+    ) -> Result<Vec<core::ops::RangeInclusive<u64>>, ResolutionMismatch> {
+        locality::ranges(resolution, cells)
+    }
+
+    /// Splits a set of cells into its connected components (i.e. islands).
+    ///
+    /// Two cells are connected if they are neighbors. For each component, the
+    /// cells touching the outside, the cells touching a hole and the holes
+    /// themselves are computed as well.
+    ///
+    /// Duplicate cells are ignored.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if the cells don't all have the same resolution.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// // A ring and its center: two islands, the first one having a hole.
+    /// let cells = index.grid_ring::<Vec<_>>(2).into_iter()
+    ///     .chain(std::iter::once(index));
+    /// let components = CellIndex::connected_components(cells)?;
+    /// assert_eq!(components.len(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn connected_components(
+        cells: impl IntoIterator<Item = Self>,
+    ) -> Result<Vec<CellComponent>, ResolutionMismatch> {
+        topology::connected_components(cells)
+    }
+
+    /// Returns true if the set of cells is made of a single connected
+    /// component without any hole.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if the cells don't all have the same resolution.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// assert!(CellIndex::is_simply_connected(index.grid_disk::<Vec<_>>(2))?);
+    /// assert!(!CellIndex::is_simply_connected(index.grid_ring::<Vec<_>>(2))?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn is_simply_connected(
+        cells: impl IntoIterator<Item = Self>,
+    ) -> Result<bool, ResolutionMismatch> {
+        let components = Self::connected_components(cells)?;
+        Ok(components.len() == 1 && components[0].is_simply_connected())
+    }
+
+    /// Computes the outline(s) of a set of cells, as rings of directed edges.
+    ///
+    /// Each connected region yields one outer ring, plus one ring per hole.
+    /// Rings are returned in a deterministic order.
+    ///
+    /// All the cells must have the same resolution: heterogeneous (e.g.
+    /// compacted) sets are supported by [`crate::Dissolver`] and
+    /// `geom::Solvent::outline`, where the target resolution is explicit.
+    ///
+    /// # Errors
+    ///
+    /// [`DissolutionError::DuplicateInput`] if the set contains duplicate
+    /// cells, [`DissolutionError::UnsupportedResolution`] if the cells have
+    /// different resolutions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let rings = CellIndex::outline(index.grid_ring::<Vec<_>>(1))?;
+    /// assert_eq!(rings.len(), 2);
+    /// assert_eq!(rings.iter().filter(|ring| ring.is_hole()).count(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn outline(
+        cells: impl IntoIterator<Item = Self>,
+    ) -> Result<Vec<EdgeRing>, DissolutionError> {
+        outline::outline(cells)
+    }
+
     /// Computes the cell boundary, in spherical coordinates, of this index.
     ///
     /// # Example
@@ -1782,7 +2440,7 @@ impl CellIndex {
         })
     }
 
-    fn vertex_count(self) -> u8 {
+    pub(crate) fn vertex_count(self) -> u8 {
         if self.is_pentagon() {
             NUM_PENT_VERTS
         } else {
@@ -2106,6 +2764,17 @@ const fn has_unused_direction(dirs: u64) -> bool {
     ((!dirs - LO_MAGIC) & (dirs & HI_MAGIC)) != 0
 }
 
+/// Returns the number of cells, at the given resolution, in the base cells
+/// preceding `base_cell`.
+fn base_cell_offset(base_cell: BaseCell, resolution: Resolution) -> u64 {
+    let pentagons = u64::from(base_cell.pentagon_rank());
+    let hexagons = u64::from(u8::from(base_cell)) - pentagons;
+    let resolution = usize::from(resolution);
+
+    hexagons * HEXAGON_CHILDREN_COUNTS[resolution]
+        + pentagons * PENTAGON_CHILDREN_COUNTS[resolution]
+}
+
 // Compute the last sibling.
 //
 // This returns the last sibling needed to be able to compact the given cell
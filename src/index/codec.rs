@@ -0,0 +1,225 @@
+//! Compact binary encoding of cell sets.
+//!
+//! Cells are identified by their ordinal at the set resolution (see
+//! `CellIndex::ordinal`). Since this ordering follows the hierarchical bit
+//! layout, nearby cells (i.e. sharing a base cell and a prefix of directions)
+//! have close ordinals.
+//!
+//! The set is compacted first, then each compacted cell is encoded as the gap
+//! between its first child (at the set resolution) and the end of the previous
+//! compacted cell, hence dense sets are encoded with one or two bytes per
+//! compacted cell.
+
+use super::{CellIndex, iterator::Children};
+use crate::{Resolution, error::DecodingError};
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Magic bytes, at the start of every encoded set.
+const MAGIC: [u8; 4] = *b"H3CS";
+/// Current version of the format.
+const VERSION: u8 = 1;
+/// Number of bits used to store the resolution delta in each entry.
+const RESOLUTION_BITS: u32 = 4;
+
+/// Encodes a set of cells.
+pub fn encode(
+    cells: impl IntoIterator<Item = CellIndex>,
+) -> Result<Vec<u8>, crate::error::CompactionError> {
+    let mut cells = cells.into_iter().collect::<Vec<_>>();
+    let resolution = cells
+        .first()
+        .map_or(Resolution::Zero, |cell| cell.resolution());
+    CellIndex::compact(&mut cells)?;
+
+    let mut bytes = Vec::with_capacity(MAGIC.len() + 12 + cells.len() * 2);
+    bytes.extend_from_slice(&MAGIC);
+    bytes.push(VERSION);
+    bytes.push(resolution.into());
+    write_varint(&mut bytes, cells.len() as u64);
+
+    let mut end = 0;
+    for cell in cells {
+        let first = cell.center_child(resolution).expect("finer resolution");
+        let start = first.ordinal();
+        // Compacted cells are sorted and disjoint.
+        let gap = start.checked_sub(end).expect("sorted compacted cells");
+        let delta = u8::from(resolution) - u8::from(cell.resolution());
+        write_varint(&mut bytes, (gap << RESOLUTION_BITS) | u64::from(delta));
+        end = start + cell.children_count(resolution);
+    }
+
+    Ok(bytes)
+}
+
+// -----------------------------------------------------------------------------
+
+/// A streaming decoder of encoded cell sets.
+///
+/// Cells are yielded in order, at the resolution of the encoded set. Decoding
+/// stops at the first error.
+#[derive(Debug, Clone)]
+pub struct CellSetDecoder<'a> {
+    /// Remaining bytes.
+    bytes: &'a [u8],
+    /// Resolution of the set.
+    resolution: Resolution,
+    /// Number of compacted cells left to decode.
+    remaining: u64,
+    /// Ordinal following the last decoded compacted cell.
+    end: u64,
+    /// Cells of the current compacted cell.
+    children: Option<Children>,
+    is_done: bool,
+}
+
+impl<'a> CellSetDecoder<'a> {
+    /// Parses the header of an encoded set.
+    pub(crate) fn new(bytes: &'a [u8]) -> Result<Self, DecodingError> {
+        let mut bytes = bytes
+            .strip_prefix(&MAGIC)
+            .ok_or(DecodingError::InvalidHeader)?;
+        let (&version, rest) =
+            bytes.split_first().ok_or(DecodingError::UnexpectedEnd)?;
+        if version != VERSION {
+            return Err(DecodingError::UnsupportedVersion(version));
+        }
+        let (&resolution, rest) =
+            rest.split_first().ok_or(DecodingError::UnexpectedEnd)?;
+        let resolution = Resolution::try_from(resolution)
+            .map_err(|_| DecodingError::InvalidHeader)?;
+        bytes = rest;
+        let remaining = read_varint(&mut bytes)?;
+
+        Ok(Self {
+            bytes,
+            resolution,
+            remaining,
+            end: 0,
+            children: None,
+            is_done: false,
+        })
+    }
+
+    /// Returns the resolution of the encoded set.
+    #[must_use]
+    pub const fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// Returns an iterator over the compacted cells of the encoded set,
+    /// without expanding them.
+    ///
+    /// If the decoder was in the middle of a compacted cell, its remaining
+    /// children are skipped.
+    pub fn compacted(
+        mut self,
+    ) -> impl Iterator<Item = Result<CellIndex, DecodingError>> + 'a {
+        self.children = None;
+        core::iter::from_fn(move || self.next_compacted())
+    }
+
+    /// Decodes the next compacted cell.
+    fn next_compacted(&mut self) -> Option<Result<CellIndex, DecodingError>> {
+        if self.is_done {
+            return None;
+        }
+        let result = self.decode_entry();
+        if !matches!(result, Ok(Some(_))) {
+            self.is_done = true;
+        }
+
+        result.transpose()
+    }
+
+    /// Decodes an entry, checking for trailing bytes at the end of the set.
+    fn decode_entry(&mut self) -> Result<Option<CellIndex>, DecodingError> {
+        if self.remaining == 0 {
+            return if self.bytes.is_empty() {
+                Ok(None)
+            } else {
+                Err(DecodingError::TrailingBytes)
+            };
+        }
+        let value = read_varint(&mut self.bytes)?;
+        let gap = value >> RESOLUTION_BITS;
+        let delta = (value & ((1 << RESOLUTION_BITS) - 1)) as u8;
+
+        let resolution = u8::from(self.resolution)
+            .checked_sub(delta)
+            .and_then(|res| Resolution::try_from(res).ok())
+            .ok_or(DecodingError::InvalidEntry)?;
+        let start = self
+            .end
+            .checked_add(gap)
+            .ok_or(DecodingError::InvalidEntry)?;
+        let first = CellIndex::from_ordinal(self.resolution, start)
+            .ok_or(DecodingError::InvalidEntry)?;
+        let cell = first.parent(resolution).expect("coarser resolution");
+        // The ordinal must point to the first child of the compacted cell.
+        if cell.center_child(self.resolution) != Some(first) {
+            return Err(DecodingError::InvalidEntry);
+        }
+
+        self.end = start + cell.children_count(self.resolution);
+        self.remaining -= 1;
+        Ok(Some(cell))
+    }
+}
+
+impl Iterator for CellSetDecoder<'_> {
+    type Item = Result<CellIndex, DecodingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(cell) = self.children.as_mut().and_then(Iterator::next)
+            {
+                return Some(Ok(cell));
+            }
+            match self.next_compacted()? {
+                Ok(cell) => {
+                    self.children = Some(Children::new(cell, self.resolution));
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+impl FusedIterator for CellSetDecoder<'_> {}
+
+// -----------------------------------------------------------------------------
+
+/// Writes an unsigned LEB128 integer.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "only the 7 lowest bits are kept"
+)]
+pub fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Reads an unsigned LEB128 integer.
+pub fn read_varint(bytes: &mut &[u8]) -> Result<u64, DecodingError> {
+    let mut value = 0;
+    for shift in (0..u64::BITS).step_by(7) {
+        let (&byte, rest) =
+            bytes.split_first().ok_or(DecodingError::UnexpectedEnd)?;
+        *bytes = rest;
+        let bits = u64::from(byte & 0x7f);
+        // Reject bits overflowing a 64-bit integer.
+        if bits << shift >> shift != bits {
+            return Err(DecodingError::InvalidEntry);
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(DecodingError::InvalidEntry)
+}
@@ -1,7 +1,7 @@
 use super::{IndexMode, bits};
 use crate::{
-    Boundary, CellIndex, Direction, EARTH_RADIUS_KM, coord::FaceIJK, error,
-    grid,
+    Boundary, CellIndex, Direction, EARTH_RADIUS_KM, EarthModel,
+    coord::FaceIJK, error, geodesic, grid,
 };
 use core::{cmp::Ordering, fmt, num::NonZeroU64, str::FromStr};
 
@@ -96,7 +96,6 @@ impl fmt::Display for Edge {
 /// - [H3 Index Bit Layout](https://observablehq.com/@nrabinowitz/h3-index-bit-layout?collection=@nrabinowitz/h3)
 /// - [H3 Index Inspector](https://observablehq.com/@nrabinowitz/h3-index-inspector?collection=@nrabinowitz/h3)
 #[derive(Clone, Copy, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DirectedEdgeIndex(NonZeroU64);
 
 impl DirectedEdgeIndex {
@@ -255,6 +254,60 @@ impl DirectedEdgeIndex {
         self.length_km() * 1000.
     }
 
+    /// Computes the length of this directed edge on the specified Earth model,
+    /// in kilometers.
+    ///
+    /// With [`EarthModel::Sphere`], this is the same as [`Self::length_km`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let index = h3o::DirectedEdgeIndex::try_from(0x13a194e699ab7fff)?;
+    /// float_eq::assert_float_eq!(
+    ///     index.length_km_with(h3o::EarthModel::Wgs84),
+    ///     0.07529316138368757,
+    ///     abs <= 1e-11
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn length_km_with(self, model: EarthModel) -> f64 {
+        match model {
+            EarthModel::Sphere => self.length_km(),
+            EarthModel::Wgs84 => self.length_m_with(model) / 1000.,
+        }
+    }
+
+    /// Computes the length of this directed edge on the specified Earth model,
+    /// in meters.
+    ///
+    /// With [`EarthModel::Sphere`], this is the same as [`Self::length_m`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let index = h3o::DirectedEdgeIndex::try_from(0x13a194e699ab7fff)?;
+    /// float_eq::assert_float_eq!(
+    ///     index.length_m_with(h3o::EarthModel::Wgs84),
+    ///     75.29316138368756,
+    ///     abs <= 1e-8
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn length_m_with(self, model: EarthModel) -> f64 {
+        match model {
+            EarthModel::Sphere => self.length_m(),
+            EarthModel::Wgs84 => {
+                let wgs84 = &geodesic::WGS84;
+                self.boundary()
+                    .windows(2)
+                    .map(|pair| wgs84.inverse(pair[0], pair[1]).distance)
+                    .sum()
+            }
+        }
+    }
+
     /// Initializes a new edge index a value that may be invalid.
     ///
     /// # Safety
@@ -8,6 +8,7 @@ use crate::{
 use core::cmp::max;
 
 /// Iterator over a children cell index at a given resolution.
+#[derive(Debug, Clone)]
 pub struct Children {
     /// Starting cell resolution.
     parent_resolution: Resolution,
@@ -0,0 +1,68 @@
+//! Decomposition of cell sets into ranges of keys.
+//!
+//! Cell indexes are ordered hierarchically (see `impl Ord for CellIndex`):
+//! the descendants of a cell, at a given resolution, are consecutive in that
+//! order. Hence, a store keyed by cell indexes (all at the same resolution)
+//! can be scanned for the descendants of any cell with a single range query,
+//! and a whole cell set with a handful of them.
+
+use super::CellIndex;
+use crate::{Resolution, error::ResolutionMismatch};
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+/// Directions bitmask, where every direction is IJ (6).
+const IJ_DIRECTIONS: u64 = 0o666_666_666_666_666;
+
+/// Returns the keys of the first and last descendants of `cell` at
+/// `resolution`.
+pub fn range(
+    cell: CellIndex,
+    resolution: Resolution,
+) -> Option<RangeInclusive<u64>> {
+    let first = cell.center_child(resolution)?;
+    let start = cell.resolution().direction_offset();
+    let stop = resolution.direction_offset();
+    let mask = ((1 << (start - stop)) - 1) << stop;
+    // The last child always follows the IJ directions, even for pentagons.
+    let last = u64::from(first) | (IJ_DIRECTIONS & mask);
+
+    Some(u64::from(first)..=last)
+}
+
+/// Returns the minimal set of sorted and disjoint ranges of keys covering the
+/// descendants of `cells` at `resolution`.
+pub fn ranges(
+    resolution: Resolution,
+    cells: impl IntoIterator<Item = CellIndex>,
+) -> Result<Vec<RangeInclusive<u64>>, ResolutionMismatch> {
+    let mut ranges = cells
+        .into_iter()
+        .map(|cell| range(cell, resolution).ok_or(ResolutionMismatch))
+        .collect::<Result<Vec<_>, _>>()?;
+    ranges.sort_unstable_by_key(|range| *range.start());
+
+    let mut result: Vec<RangeInclusive<u64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        if let Some(last) = result.last_mut()
+            && is_contiguous(*last.end(), *range.start())
+        {
+            if range.end() > last.end() {
+                *last = *last.start()..=*range.end();
+            }
+            continue;
+        }
+        result.push(range);
+    }
+
+    Ok(result)
+}
+
+/// Returns true if no cell lies strictly between the two keys, i.e. if the
+/// range ending at `end` can be extended up to `start`.
+fn is_contiguous(end: u64, start: u64) -> bool {
+    start <= end
+        || CellIndex::new_unchecked(end)
+            .succ()
+            .is_some_and(|next| u64::from(next) == start)
+}
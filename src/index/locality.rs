@@ -0,0 +1,108 @@
+//! Locality-preserving linear order of cells.
+//!
+//! The native order of cell indexes keeps the descendants of a cell together,
+//! but neighbors on either side of a parent boundary can be far apart. This
+//! order instead follows a Hilbert curve over the `IJ` coordinates of the cell
+//! centers, face by face, and visits the icosahedron faces along a path where
+//! each face shares an edge with the next one (north cap, middle band then
+//! south cap).
+
+use super::CellIndex;
+use crate::{Resolution, coord::FaceIJK, error::ResolutionMismatch};
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+/// Number of bits per coordinate along the curve.
+///
+/// Coordinates are bounded by the size of a face at the finest Class II
+/// resolution (i.e. 2 * 7^8), which fits in 24 bits once centered.
+const ORDER: u32 = 25;
+/// Offset applied to the coordinates to make them positive.
+const OFFSET: i32 = 1 << (ORDER - 1);
+
+/// Maximum difference of resolution between an input cell and the resolution
+/// of the ranges.
+///
+/// The descendants of a cell aren't aligned on the Hilbert curve, thus every
+/// one of them is enumerated: this bounds the work at 7^7 keys per cell.
+const MAX_RESOLUTION_GAP: u8 = 7;
+
+/// Rank of each face along the path (i.e. inverse of the path).
+///
+/// The path is 0, 1, 2, 3, 4, 9, 13, 8, 12, 7, 11, 6, 10, 5, 14, 19, 15, 16,
+/// 17, 18.
+const FACE_RANK: [u8; 20] = [
+    0, 1, 2, 3, 4, 13, 11, 9, 7, 5, 12, 10, 8, 6, 14, 16, 17, 18, 19, 15,
+];
+
+/// Returns the position of the cell along the curve.
+pub fn key(cell: CellIndex) -> u64 {
+    let fijk = FaceIJK::from(cell);
+    let coord = fijk.coord;
+    let x = u32::try_from(coord.i() - coord.k() + OFFSET)
+        .expect("positive coordinate");
+    let y = u32::try_from(coord.j() - coord.k() + OFFSET)
+        .expect("positive coordinate");
+    debug_assert!(x < 1 << ORDER && y < 1 << ORDER, "coordinates overflow");
+
+    let rank = u64::from(FACE_RANK[usize::from(fijk.face)]);
+    (rank << (2 * ORDER)) | hilbert(x, y)
+}
+
+/// Returns the sorted and disjoint ranges of keys covering the descendants of
+/// `cells` at `resolution`.
+pub fn ranges(
+    resolution: Resolution,
+    cells: impl IntoIterator<Item = CellIndex>,
+) -> Result<Vec<RangeInclusive<u64>>, ResolutionMismatch> {
+    let mut keys = Vec::new();
+    for cell in cells {
+        let gap = u8::from(resolution)
+            .checked_sub(u8::from(cell.resolution()))
+            .ok_or(ResolutionMismatch)?;
+        if gap > MAX_RESOLUTION_GAP {
+            return Err(ResolutionMismatch);
+        }
+        keys.extend(cell.children(resolution).map(key));
+    }
+    keys.sort_unstable();
+
+    let mut result: Vec<RangeInclusive<u64>> = Vec::new();
+    for key in keys {
+        if let Some(last) = result.last_mut()
+            && *last.end() + 1 >= key
+        {
+            *last = *last.start()..=key;
+            continue;
+        }
+        result.push(key..=key);
+    }
+
+    Ok(result)
+}
+
+/// Maps 2D coordinates to their distance along a Hilbert curve.
+fn hilbert(mut x: u32, mut y: u32) -> u64 {
+    let mut distance = 0;
+    let mut size = 1 << (ORDER - 1);
+    while size > 0 {
+        let rx = u32::from(x & size != 0);
+        let ry = u32::from(y & size != 0);
+        distance +=
+            u64::from(size) * u64::from(size) * u64::from((3 * rx) ^ ry);
+
+        // Rotate the quadrant.
+        if ry == 0 {
+            if rx == 1 {
+                x = size - 1 - (x & (size - 1));
+                y = size - 1 - (y & (size - 1));
+            }
+            core::mem::swap(&mut x, &mut y);
+        }
+        x &= size - 1;
+        y &= size - 1;
+        size >>= 1;
+    }
+
+    distance
+}
@@ -1,17 +1,28 @@
 //! H3 index types
 
 pub mod bits;
+mod builder;
 mod cell;
+mod codec;
 mod edge;
 mod iterator;
+mod key_range;
+mod locality;
 mod mode;
+mod text;
 mod triangle;
 mod vertex;
 
+pub use builder::{
+    CellIndexBuilder, DirectedEdgeIndexBuilder, VertexIndexBuilder,
+};
 pub use cell::CellIndex;
+pub use codec::CellSetDecoder;
+pub use codec::{read_varint, write_varint};
 pub use edge::{DirectedEdgeIndex, Edge};
 pub use mode::IndexMode;
+pub use text::ShortCode;
 pub use vertex::{Vertex, VertexIndex};
 
 pub use iterator::{Children, GridPathCells};
-use triangle::Triangle;
+pub use triangle::Triangle;
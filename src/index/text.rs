@@ -0,0 +1,315 @@
+//! Textual representations of cell indexes.
+//!
+//! Besides the canonical hexadecimal form, cell indexes are often exchanged
+//! as decimal numbers (e.g. databases without unsigned 64-bit integers), or
+//! with a `0x` prefix. The short code is a compact, URL-safe, alternative,
+//! and the path (e.g. `20/1230`) spells out the base cell and the directions.
+
+use super::{IndexMode, bits};
+use crate::{
+    BaseCell, CellIndex, Direction, Resolution,
+    error::{InvalidCellIndex, ParsingError},
+};
+use core::{fmt, str};
+
+/// Alphabet of the short codes: Crockford's base32, lowercase (no `i`, `l`,
+/// `o` or `u`, to avoid confusions).
+const ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
+/// Number of bits per short code character.
+const CHAR_BITSIZE: usize = 5;
+
+/// Bit size of the base cell.
+const BASE_CELL_BITSIZE: usize = 7;
+
+/// Offset of the base cell in the index.
+const BASE_CELL_OFFSET: usize = 45;
+
+/// Length of a short code at resolution 0.
+const RES0_SHORT_CODE_LEN: usize = 3;
+
+/// Maximum length of a short code (at resolution 15).
+const MAX_SHORT_CODE_LEN: usize = 12;
+
+/// Maximum number of significant digits in the hexadecimal form of a 64-bit
+/// number.
+const MAX_HEX_DIGITS: usize = 16;
+
+// -----------------------------------------------------------------------------
+
+/// Compact, URL-safe, textual representation of a cell index.
+///
+/// The fixed bits (mode and reserved bits) and the unused trailing directions
+/// are dropped, and the remaining ones are encoded in base32: the first
+/// character is the resolution, followed by the base cell and the directions.
+/// This takes from 3 characters (resolution 0) to 12 (resolution 15), against
+/// 15 for the hexadecimal form.
+///
+/// Short codes are parsed back by [`CellIndex::parse`].
+///
+/// # Example
+///
+/// ```
+/// use h3o::CellIndex;
+///
+/// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+/// let code = index.short_code();
+///
+/// assert_eq!(code.as_str(), "a3yt6c8pr");
+/// assert_eq!(CellIndex::parse(code.as_str()), Ok(index));
+/// # Ok::<(), h3o::error::InvalidCellIndex>(())
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ShortCode {
+    bytes: [u8; MAX_SHORT_CODE_LEN],
+    len: u8,
+}
+
+impl ShortCode {
+    /// Encodes the short code of a cell index.
+    pub(crate) fn new(index: CellIndex) -> Self {
+        let resolution = index.resolution();
+        let bitsize = payload_bitsize(resolution);
+        let offset = BASE_CELL_OFFSET - directions_bitsize(resolution);
+        let payload = (u64::from(index) >> offset) & ((1 << bitsize) - 1);
+        // Pad on the right, to a whole number of characters.
+        let len = bitsize.div_ceil(CHAR_BITSIZE);
+        let payload = payload << (len * CHAR_BITSIZE - bitsize);
+
+        let mut bytes = [0; MAX_SHORT_CODE_LEN];
+        bytes[0] = ALPHABET[usize::from(resolution)];
+        for (i, byte) in bytes[1..=len].iter_mut().enumerate() {
+            let shift = (len - i - 1) * CHAR_BITSIZE;
+            *byte = ALPHABET[((payload >> shift) & 0b1_1111) as usize];
+        }
+
+        Self {
+            bytes,
+            #[expect(clippy::cast_possible_truncation, reason = "len <= 11")]
+            len: (len + 1) as u8,
+        }
+    }
+
+    /// Returns the short code as a string slice.
+    #[expect(unsafe_code, reason = "the buffer is always valid ASCII")]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: the buffer only contains ASCII characters from `ALPHABET`.
+        unsafe {
+            str::from_utf8_unchecked(&self.bytes[..usize::from(self.len)])
+        }
+    }
+}
+
+impl fmt::Display for ShortCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for ShortCode {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Parses a cell index from one of the supported textual representations.
+///
+/// The format is deduced from the shape of the input:
+/// - a `/` separates the base cell from the directions of a path.
+/// - a `0x` (or `0X`) prefix introduces an hexadecimal number, unless the
+///   input is a short code at resolution 0 (3 characters, and the first one is
+///   a `0`).
+/// - more than 16 decimal digits is a decimal number (valid cell indexes
+///   have 18 or 19 digits in base 10).
+/// - more than 12 characters is an hexadecimal number (15 digits for valid
+///   cell indexes, maybe with some leading zeroes).
+/// - otherwise, this is a short code.
+///
+/// Every format is case-insensitive.
+pub fn parse(s: &str) -> Result<CellIndex, ParsingError> {
+    if s.is_empty() {
+        return Err(ParsingError::Empty);
+    }
+    if let Some((base_cell, directions)) = s.split_once('/') {
+        return parse_path(base_cell, directions);
+    }
+
+    let prefixed = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X"));
+    let value = if let Some(digits) =
+        prefixed.filter(|_| s.len() > RES0_SHORT_CODE_LEN)
+    {
+        parse_hex(digits, 2)?
+    } else if s.len() > MAX_HEX_DIGITS
+        && s.bytes().all(|byte| byte.is_ascii_digit())
+    {
+        parse_decimal(s)?
+    } else if s.len() > MAX_SHORT_CODE_LEN {
+        parse_hex(s, 0)?
+    } else {
+        parse_short_code(s)?
+    };
+
+    CellIndex::try_from(value).map_err(Into::into)
+}
+
+/// Parses an hexadecimal number, whose first digit is at the given offset
+/// in the input.
+fn parse_hex(digits: &str, offset: usize) -> Result<u64, ParsingError> {
+    let mut value = 0_u64;
+    let mut significant = 0;
+    for (i, character) in digits.char_indices() {
+        let digit = character.to_digit(16).ok_or({
+            ParsingError::InvalidCharacter {
+                position: offset + i,
+                character,
+            }
+        })?;
+        if value != 0 || digit != 0 {
+            significant += 1;
+        }
+        if significant > MAX_HEX_DIGITS {
+            return Err(ParsingError::Overflow);
+        }
+        value = (value << 4) | u64::from(digit);
+    }
+
+    Ok(value)
+}
+
+/// Parses a decimal number (only made of ASCII digits).
+fn parse_decimal(digits: &str) -> Result<u64, ParsingError> {
+    digits.bytes().try_fold(0_u64, |value, byte| {
+        value
+            .checked_mul(10)
+            .and_then(|value| value.checked_add(u64::from(byte - b'0')))
+            .ok_or(ParsingError::Overflow)
+    })
+}
+
+/// Parses a short code back into the cell index bits.
+fn parse_short_code(code: &str) -> Result<u64, ParsingError> {
+    let mut symbols = code.char_indices().map(|(position, character)| {
+        decode_symbol(character).ok_or(ParsingError::InvalidCharacter {
+            position,
+            character,
+        })
+    });
+
+    // The first symbol is the resolution.
+    let resolution = symbols.next().ok_or(ParsingError::Empty)??;
+    let resolution = Resolution::try_from(resolution).map_err(|_| {
+        ParsingError::InvalidCharacter {
+            position: 0,
+            character: code.chars().next().unwrap_or_default(),
+        }
+    })?;
+    let bitsize = payload_bitsize(resolution);
+    let len = bitsize.div_ceil(CHAR_BITSIZE);
+    if code.len() != len + 1 {
+        return Err(ParsingError::InvalidLength(code.len()));
+    }
+
+    let payload = symbols.try_fold(0_u64, |payload, symbol| {
+        symbol.map(|symbol| (payload << CHAR_BITSIZE) | u64::from(symbol))
+    })?;
+    // Padding must be zeroed, otherwise several codes would map to the same
+    // index.
+    let padding = len * CHAR_BITSIZE - bitsize;
+    if payload & ((1 << padding) - 1) != 0 {
+        return Err(ParsingError::InvalidCharacter {
+            position: code.len() - 1,
+            character: code.chars().last().unwrap_or_default(),
+        });
+    }
+
+    let offset = BASE_CELL_OFFSET - directions_bitsize(resolution);
+    let bits = bits::set_mode(0, IndexMode::Cell);
+    let bits = bits::set_resolution(bits, resolution);
+
+    Ok(bits | ((payload >> padding) << offset) | ((1 << offset) - 1))
+}
+
+/// Parses a path, already split between its base cell and its directions.
+fn parse_path(
+    base_cell: &str,
+    directions: &str,
+) -> Result<CellIndex, ParsingError> {
+    // Directions start after the base cell and the separator.
+    let offset = base_cell.len() + 1;
+    let value = base_cell.char_indices().try_fold(
+        0_u8,
+        |value, (position, character)| {
+            character
+                .to_digit(10)
+                .and_then(|digit| {
+                    let digit = u8::try_from(digit).ok()?;
+                    value.checked_mul(10)?.checked_add(digit)
+                })
+                .ok_or(ParsingError::InvalidCharacter {
+                    position,
+                    character,
+                })
+        },
+    )?;
+    if base_cell.is_empty() {
+        return Err(ParsingError::InvalidCharacter {
+            position: 0,
+            character: '/',
+        });
+    }
+    let base_cell = BaseCell::try_from(value).map_err(|_| {
+        ParsingError::InvalidIndex(InvalidCellIndex::new(
+            None,
+            "invalid base cell",
+        ))
+    })?;
+
+    let max_len = usize::from(h3o_bit::MAX_RESOLUTION);
+    if directions.len() > max_len {
+        return Err(ParsingError::InvalidLength(directions.len()));
+    }
+    let mut buffer = [Direction::Center; h3o_bit::MAX_RESOLUTION as usize];
+    for ((position, character), direction) in
+        directions.char_indices().zip(buffer.iter_mut())
+    {
+        *direction = character
+            .to_digit(10)
+            .and_then(|digit| {
+                Direction::try_from(u8::try_from(digit).ok()?).ok()
+            })
+            .ok_or(ParsingError::InvalidCharacter {
+                position: offset + position,
+                character,
+            })?;
+    }
+
+    CellIndex::from_directions(base_cell, &buffer[..directions.len()])
+        .map_err(Into::into)
+}
+
+/// Decodes a short code character into its 5-bit value.
+fn decode_symbol(character: char) -> Option<u8> {
+    let byte = u8::try_from(character.to_ascii_lowercase()).ok()?;
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "alphabet has 32 items"
+    )]
+    ALPHABET
+        .iter()
+        .position(|&symbol| symbol == byte)
+        .map(|value| value as u8)
+}
+
+/// Returns the number of bits used by the directions at the given resolution.
+fn directions_bitsize(resolution: Resolution) -> usize {
+    usize::from(resolution) * h3o_bit::DIRECTION_BITSIZE
+}
+
+/// Returns the number of meaningful bits (base cell and directions) at the
+/// given resolution.
+fn payload_bitsize(resolution: Resolution) -> usize {
+    BASE_CELL_BITSIZE + directions_bitsize(resolution)
+}
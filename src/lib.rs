@@ -17,7 +17,8 @@
 //!   into geometric shapes. Also enables the `GeoJSON` support. Requires `std`.
 //!
 //! * **serde** -
-//!   When enabled, H3 index types (cell, vertex and edge) derive serde traits.
+//!   When enabled, H3 index types (cell, vertex and edge) implement serde
+//!   traits (see the `serde` module for the available representations).
 //!
 //! ## H3 to H3O mapping
 //!
@@ -247,17 +248,35 @@
 
 extern crate alloc;
 
+mod autocorrelation;
 mod base_cell;
 mod boundary;
+mod cell_bitmap;
+mod collections;
 mod coord;
 mod direction;
+mod dissolver;
 pub mod error;
 mod face;
+mod geodesic;
 #[cfg(feature = "geo")]
 pub mod geom;
 mod grid;
+mod hex_raster;
+mod hexbin;
 mod index;
+mod map_matching;
+mod metrics;
+mod outline;
+mod point_index;
+mod raster;
 mod resolution;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod simplify;
+mod smoothing;
+mod topology;
+mod trajectory;
 
 #[cfg(not(feature = "std"))]
 #[path = "math-libm.rs"]
@@ -266,15 +285,36 @@ mod math;
 #[path = "math-std.rs"]
 mod math;
 
+pub use autocorrelation::{
+    GlobalMoran, SpatialAutocorrelation, SpatialWeights,
+};
 pub use base_cell::BaseCell;
 pub use boundary::Boundary;
+pub use cell_bitmap::CellBitmap;
 pub use coord::{CoordIJ, LatLng, LocalIJ};
 pub use direction::Direction;
+pub use dissolver::{Dissolver, DissolverBuilder, Rings, SortedRings};
 pub use face::{Face, FaceSet};
+pub use geodesic::EarthModel;
+pub use hex_raster::{HexKernel, HexRaster};
+pub use hexbin::{CellStats, HexBin};
 pub use index::{
-    CellIndex, DirectedEdgeIndex, Edge, IndexMode, Vertex, VertexIndex,
+    CellIndex, CellIndexBuilder, CellSetDecoder, DirectedEdgeIndex,
+    DirectedEdgeIndexBuilder, Edge, IndexMode, ShortCode, Vertex, VertexIndex,
+    VertexIndexBuilder,
 };
+pub use map_matching::{MapMatcher, MapMatcherBuilder};
+pub use metrics::ShapeMetrics;
+pub use outline::EdgeRing;
+pub use point_index::{Neighbor, PointIndex};
+pub use raster::{GeoTransform, Raster};
 pub use resolution::Resolution;
+pub use simplify::Simplifier;
+pub use smoothing::{
+    KernelDistance, KernelSmoother, KernelSmootherBuilder, SmoothingKernel,
+};
+pub use topology::CellComponent;
+pub use trajectory::{CellVisit, Trajectory, TrajectoryBuilder};
 
 use resolution::ExtendedResolution;
 
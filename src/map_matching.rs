@@ -0,0 +1,308 @@
+//! Map-matching of noisy traces to contiguous cell paths.
+//!
+//! The trace is modeled as a hidden Markov model whose states are the cells
+//! around each point: emission probabilities decrease with the distance
+//! between the point and the cell center (Gaussian noise), and transition
+//! probabilities decrease with the difference between the distance traveled
+//! on the trace (minus the uncertainty of the points) and the distance
+//! between the cell centers (exponential noise). The most likely sequence of
+//! cells is then found with the Viterbi algorithm, and filled into a path of
+//! neighboring cells.
+
+use crate::{
+    CellIndex, LatLng, Resolution,
+    collections::Map,
+    error::InvalidParameter,
+    math::{abs, hypot},
+    trajectory,
+};
+use alloc::{collections::VecDeque, vec::Vec};
+
+/// Default accuracy of the points, in meters.
+const DEFAULT_ACCURACY: f64 = 10.;
+/// Default maximum radius, in rings, of the candidate sets.
+const DEFAULT_MAX_RADIUS: u32 = 2;
+
+/// A map-matcher, snapping noisy traces to contiguous paths of cells.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{LatLng, MapMatcherBuilder, Resolution};
+///
+/// let matcher = MapMatcherBuilder::new(Resolution::Ten).build()?;
+/// let path = matcher.match_trace([
+///     LatLng::new(48.8566, 2.3522)?,
+///     LatLng::new(48.8570, 2.3530)?,
+///     LatLng::new(48.8580, 2.3550)?,
+/// ]);
+///
+/// for pair in path.windows(2) {
+///     assert!(pair[0].is_neighbor_with(pair[1])?);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MapMatcher {
+    resolution: Resolution,
+    accuracy: f64,
+    max_radius: u32,
+}
+
+impl MapMatcher {
+    /// Returns the resolution of the matched cells.
+    #[must_use]
+    pub const fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// Matches a trace, using the default accuracy for every point.
+    ///
+    /// Returns the most likely path of cells, where each cell is a neighbor
+    /// of the previous one.
+    #[must_use]
+    pub fn match_trace(
+        &self,
+        trace: impl IntoIterator<Item = LatLng>,
+    ) -> Vec<CellIndex> {
+        self.match_trace_with_accuracy(
+            trace.into_iter().map(|point| (point, self.accuracy)),
+        )
+    }
+
+    /// Matches a trace of points with their accuracy (i.e. the standard
+    /// deviation of their position error), in meters.
+    ///
+    /// Returns the most likely path of cells, where each cell is a neighbor
+    /// of the previous one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{LatLng, MapMatcherBuilder, Resolution};
+    ///
+    /// let matcher = MapMatcherBuilder::new(Resolution::Ten).build()?;
+    /// let path = matcher.match_trace_with_accuracy([
+    ///     (LatLng::new(48.8566, 2.3522)?, 5.),
+    ///     (LatLng::new(48.8570, 2.3530)?, 50.),
+    ///     (LatLng::new(48.8580, 2.3550)?, 5.),
+    /// ]);
+    /// assert!(!path.is_empty());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn match_trace_with_accuracy(
+        &self,
+        trace: impl IntoIterator<Item = (LatLng, f64)>,
+    ) -> Vec<CellIndex> {
+        let mut states: Vec<Vec<State>> = Vec::new();
+        let mut previous: Option<(LatLng, f64)> = None;
+
+        for (point, accuracy) in trace {
+            let accuracy = accuracy.max(f64::EPSILON);
+            let candidates = point
+                .to_cell(self.resolution)
+                .grid_disk::<Vec<_>>(self.radius(accuracy));
+            let layer = candidates
+                .into_iter()
+                .map(|cell| {
+                    let center = LatLng::from(cell);
+                    let distance = point.distance_m(center);
+                    let emission =
+                        distance * distance / (2. * accuracy * accuracy);
+                    let (cost, parent) = match (states.last(), previous) {
+                        (Some(layer), Some((previous, previous_accuracy))) => {
+                            // Movements within the noise are not significant.
+                            let noise = hypot(accuracy, previous_accuracy);
+                            let traveled =
+                                (previous.distance_m(point) - noise).max(0.);
+                            best_parent(layer, center, traveled, noise)
+                        }
+                        _ => (0., 0),
+                    };
+                    State {
+                        cell,
+                        center,
+                        cost: cost + emission,
+                        parent,
+                    }
+                })
+                .collect();
+            states.push(layer);
+            previous = Some((point, accuracy));
+        }
+
+        // Backtrack from the cheapest final state.
+        let Some(last) = states.last() else {
+            return Vec::new();
+        };
+        let mut index = last
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.cost.total_cmp(&b.cost))
+            .map(|(index, _)| index)
+            .expect("non-empty candidates");
+        let mut cells = Vec::with_capacity(states.len());
+        for layer in states.iter().rev() {
+            cells.push(layer[index].cell);
+            index = layer[index].parent;
+        }
+        cells.reverse();
+        cells.dedup();
+
+        // Fill the gaps between non-neighboring cells.
+        let mut path = Vec::with_capacity(cells.len());
+        for cell in cells {
+            if let Some(&last) = path.last() {
+                path.extend(connect(last, cell));
+            }
+            path.push(cell);
+        }
+
+        path
+    }
+
+    /// Returns the radius, in rings, of the candidate set for the given
+    /// accuracy.
+    fn radius(&self, accuracy: f64) -> u32 {
+        let edge_length = self.resolution.edge_length_m();
+        // Cover three standard deviations, within the configured limits.
+        let mut radius = 1;
+        while radius < self.max_radius
+            && f64::from(radius) * edge_length < 3. * accuracy
+        {
+            radius += 1;
+        }
+        radius
+    }
+}
+
+/// A candidate cell for a point of the trace.
+#[derive(Debug, Clone, Copy)]
+struct State {
+    cell: CellIndex,
+    center: LatLng,
+    /// Cost (i.e. negative log-likelihood) of the best path ending here.
+    cost: f64,
+    /// Index of the previous state on the best path.
+    parent: usize,
+}
+
+/// Returns the cells strictly between `from` and `to`, forming a path of
+/// neighboring cells.
+///
+/// The direct path may not be computable (e.g. when distortions around
+/// pentagons derail the walk), in which case a breadth-first search over the
+/// grid is used: it always succeeds, since the grid is connected.
+fn connect(from: CellIndex, to: CellIndex) -> Vec<CellIndex> {
+    let path = trajectory::path(from, to, u32::MAX);
+    if !path.is_empty() || from.is_neighbor_with(to).unwrap_or(true) {
+        return path;
+    }
+
+    // Maps every visited cell to the one it was reached from.
+    let mut parents = Map::default();
+    parents.insert(from, from);
+    let mut queue = VecDeque::from([from]);
+    while let Some(cell) = queue.pop_front() {
+        for neighbor in cell.grid_disk::<Vec<_>>(1) {
+            if parents.contains_key(&neighbor) {
+                continue;
+            }
+            parents.insert(neighbor, cell);
+            if neighbor == to {
+                let mut path = Vec::new();
+                let mut current = cell;
+                while current != from {
+                    path.push(current);
+                    current = parents[&current];
+                }
+                path.reverse();
+                return path;
+            }
+            queue.push_back(neighbor);
+        }
+    }
+
+    Vec::new()
+}
+
+/// Returns the cost of the best path reaching `center`, and the index of its
+/// previous state in `layer`.
+fn best_parent(
+    layer: &[State],
+    center: LatLng,
+    traveled: f64,
+    scale: f64,
+) -> (f64, usize) {
+    layer
+        .iter()
+        .enumerate()
+        .map(|(index, state)| {
+            let hop = state.center.distance_m(center);
+            let transition = abs(traveled - hop) / scale;
+            (state.cost + transition, index)
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .expect("non-empty layer")
+}
+
+// -----------------------------------------------------------------------------
+
+/// A builder to configure a map-matcher.
+#[derive(Debug, Clone, Copy)]
+pub struct MapMatcherBuilder {
+    resolution: Resolution,
+    accuracy: f64,
+    max_radius: u32,
+}
+
+impl MapMatcherBuilder {
+    /// Initializes a new map-matcher builder with default settings.
+    #[must_use]
+    pub const fn new(resolution: Resolution) -> Self {
+        Self {
+            resolution,
+            accuracy: DEFAULT_ACCURACY,
+            max_radius: DEFAULT_MAX_RADIUS,
+        }
+    }
+
+    /// Set the accuracy of the points without one, in meters.
+    ///
+    /// Defaults to 10 meters, which is typical for consumer GPS receivers.
+    /// Must be positive and finite.
+    #[must_use]
+    pub const fn accuracy(mut self, accuracy: f64) -> Self {
+        self.accuracy = accuracy;
+        self
+    }
+
+    /// Set the maximum radius, in rings around the cell containing each
+    /// point, of the candidate cells.
+    ///
+    /// Larger radii tolerate noisier points but make the matching
+    /// quadratically slower. Defaults to 2, and can't be lower than 1.
+    #[must_use]
+    pub fn max_radius(mut self, max_radius: u32) -> Self {
+        self.max_radius = max_radius.max(1);
+        self
+    }
+
+    /// Builds the map-matcher.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidParameter`] if the accuracy isn't positive and finite.
+    pub fn build(self) -> Result<MapMatcher, InvalidParameter> {
+        if !(self.accuracy.is_finite() && self.accuracy > 0.) {
+            return Err(InvalidParameter::new("invalid accuracy"));
+        }
+
+        Ok(MapMatcher {
+            resolution: self.resolution,
+            accuracy: self.accuracy,
+            max_radius: self.max_radius,
+        })
+    }
+}
@@ -48,6 +48,26 @@ pub fn sqrt(x: f64) -> f64 {
     libm::sqrt(x)
 }
 
+#[inline]
+pub fn cbrt(x: f64) -> f64 {
+    libm::cbrt(x)
+}
+
+#[inline]
+pub fn ln(x: f64) -> f64 {
+    libm::log(x)
+}
+
+#[inline]
+pub fn exp(x: f64) -> f64 {
+    libm::exp(x)
+}
+
+#[inline]
+pub fn ceil(x: f64) -> f64 {
+    libm::ceil(x)
+}
+
 #[inline]
 pub fn round(x: f64) -> f64 {
     libm::round(x)
@@ -48,12 +48,32 @@ pub fn sqrt(x: f64) -> f64 {
     x.sqrt()
 }
 
+#[inline]
+pub fn cbrt(x: f64) -> f64 {
+    x.cbrt()
+}
+
+#[inline]
+pub fn ln(x: f64) -> f64 {
+    x.ln()
+}
+
+#[inline]
+pub fn exp(x: f64) -> f64 {
+    x.exp()
+}
+
+#[inline]
+pub const fn ceil(x: f64) -> f64 {
+    x.ceil()
+}
+
 #[inline]
 pub const fn round(x: f64) -> f64 {
     x.round()
 }
 
 #[inline]
-pub fn mul_add(a: f64, b: f64, c: f64) -> f64 {
+pub const fn mul_add(a: f64, b: f64, c: f64) -> f64 {
     a.mul_add(b, c)
 }
@@ -0,0 +1,287 @@
+//! Geodesic shape metrics of cells and cell regions.
+//!
+//! Every metric is computed on the sphere, from the exact boundary: edges are
+//! great-circle arcs between the vertexes, distortion vertexes included.
+
+use crate::{
+    CellIndex, DirectedEdgeIndex, EARTH_RADIUS_KM, LatLng,
+    coord::Vec3d,
+    index::Triangle,
+    math::{atan2, mul_add, sqrt},
+};
+use core::f64::consts::PI;
+
+/// Perimeter (in radians) above which the moment of a cell is computed from
+/// its boundary arcs.
+///
+/// Below, the rounding errors of the exact formula exceed the error of the
+/// triangle approximation (well below a millimeter around this threshold).
+const EXACT_MOMENT_MIN_PERIMETER: f64 = 1e-2;
+
+/// Geodesic metrics of a cell, or of a connected region of cells.
+///
+/// Useful to quantify the distortion of the grid: an undistorted regular
+/// hexagon has an edge ratio of 1 and a compactness of ~0.907.
+///
+/// # Example
+///
+/// ```
+/// let index = h3o::CellIndex::try_from(0x8a1fb46622dffff)?;
+/// let metrics = index.metrics();
+///
+/// assert!(metrics.edge_ratio() < 1.1);
+/// assert!(metrics.compactness() > 0.9);
+/// assert!(metrics.centroid().distance_m(index.into()) < 0.1);
+/// # Ok::<(), h3o::error::InvalidCellIndex>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeMetrics {
+    /// Area, in radians².
+    area: f64,
+    /// Perimeter, in radians.
+    perimeter: f64,
+    centroid: LatLng,
+    /// Length of the shortest edge, in radians.
+    min_edge: f64,
+    /// Length of the longest edge, in radians.
+    max_edge: f64,
+}
+
+impl ShapeMetrics {
+    /// Computes the metrics of a cell.
+    pub(crate) fn from_cell(cell: CellIndex) -> Self {
+        let boundary = cell.boundary();
+        let perimeter = perimeter(&boundary);
+        let (min_edge, max_edge) =
+            edge_range(cell.edges().map(DirectedEdgeIndex::length_rads));
+
+        Self {
+            area: cell.area_rads2(),
+            perimeter,
+            centroid: centroid(moment(cell, &boundary, perimeter))
+                .unwrap_or_else(|| cell.into()),
+            min_edge,
+            max_edge,
+        }
+    }
+
+    /// Computes the metrics of a connected region, from its (non-empty) cells
+    /// and its outline (outer ring and holes).
+    #[cfg(feature = "geo")]
+    pub(crate) fn from_region(
+        cells: &[CellIndex],
+        rings: &[crate::EdgeRing],
+    ) -> Self {
+        // Moments are additive, and computing them cell by cell avoids the
+        // loss of precision of the boundary formula on large rings of small
+        // edges.
+        let (area, moment) = cells.iter().fold(
+            (0., Vec3d::new(0., 0., 0.)),
+            |(area, acc), &cell| {
+                let boundary = cell.boundary();
+                let m = moment(cell, &boundary, perimeter(&boundary));
+                (
+                    area + cell.area_rads2(),
+                    Vec3d::new(acc.x + m.x, acc.y + m.y, acc.z + m.z),
+                )
+            },
+        );
+        let (min_edge, max_edge) = edge_range(
+            rings
+                .iter()
+                .flat_map(|ring| ring.edges().iter().copied())
+                .map(DirectedEdgeIndex::length_rads),
+        );
+
+        Self {
+            area,
+            perimeter: rings.iter().map(crate::EdgeRing::length_rads).sum(),
+            // A region covering the whole sphere has no centroid: any point
+            // will do.
+            centroid: centroid(moment).unwrap_or_else(|| cells[0].into()),
+            min_edge,
+            max_edge,
+        }
+    }
+
+    /// Returns the area, in radians².
+    #[must_use]
+    pub const fn area_rads2(&self) -> f64 {
+        self.area
+    }
+
+    /// Returns the area, in km².
+    #[must_use]
+    pub const fn area_km2(&self) -> f64 {
+        self.area * EARTH_RADIUS_KM * EARTH_RADIUS_KM
+    }
+
+    /// Returns the area, in m².
+    #[must_use]
+    pub const fn area_m2(&self) -> f64 {
+        self.area_km2() * 1000. * 1000.
+    }
+
+    /// Returns the perimeter (holes included), in radians.
+    #[must_use]
+    pub const fn perimeter_rads(&self) -> f64 {
+        self.perimeter
+    }
+
+    /// Returns the perimeter (holes included), in kilometers.
+    #[must_use]
+    pub const fn perimeter_km(&self) -> f64 {
+        self.perimeter * EARTH_RADIUS_KM
+    }
+
+    /// Returns the perimeter (holes included), in meters.
+    #[must_use]
+    pub const fn perimeter_m(&self) -> f64 {
+        self.perimeter_km() * 1000.
+    }
+
+    /// Returns the centroid of the surface, on the sphere.
+    ///
+    /// Unlike the H3 center of a cell, this is the center of mass of the
+    /// spherical polygon.
+    #[must_use]
+    pub const fn centroid(&self) -> LatLng {
+        self.centroid
+    }
+
+    /// Returns the length of the shortest edge, in radians.
+    #[must_use]
+    pub const fn min_edge_length_rads(&self) -> f64 {
+        self.min_edge
+    }
+
+    /// Returns the length of the shortest edge, in kilometers.
+    #[must_use]
+    pub const fn min_edge_length_km(&self) -> f64 {
+        self.min_edge * EARTH_RADIUS_KM
+    }
+
+    /// Returns the length of the shortest edge, in meters.
+    #[must_use]
+    pub const fn min_edge_length_m(&self) -> f64 {
+        self.min_edge_length_km() * 1000.
+    }
+
+    /// Returns the length of the longest edge, in radians.
+    #[must_use]
+    pub const fn max_edge_length_rads(&self) -> f64 {
+        self.max_edge
+    }
+
+    /// Returns the length of the longest edge, in kilometers.
+    #[must_use]
+    pub const fn max_edge_length_km(&self) -> f64 {
+        self.max_edge * EARTH_RADIUS_KM
+    }
+
+    /// Returns the length of the longest edge, in meters.
+    #[must_use]
+    pub const fn max_edge_length_m(&self) -> f64 {
+        self.max_edge_length_km() * 1000.
+    }
+
+    /// Returns the ratio between the longest and the shortest edge.
+    ///
+    /// The value is 1 for a shape whose edges all have the same length.
+    #[must_use]
+    pub fn edge_ratio(&self) -> f64 {
+        if self.min_edge > 0. {
+            self.max_edge / self.min_edge
+        } else {
+            1.
+        }
+    }
+
+    /// Returns the isoperimetric quotient of the shape, on the sphere.
+    ///
+    /// The value is in `(0, 1]`: 1 for a spherical cap (i.e. a circle), and
+    /// lower values for elongated or convoluted shapes. This generalizes the
+    /// Polsby-Popper score (`4πA/P²`) to the sphere.
+    #[must_use]
+    pub fn compactness(&self) -> f64 {
+        if self.perimeter > 0. {
+            mul_add(4. * PI, self.area, -self.area * self.area)
+                / (self.perimeter * self.perimeter)
+        } else {
+            1.
+        }
+    }
+}
+
+/// Computes the length of a closed spherical polygon.
+pub fn perimeter(boundary: &[LatLng]) -> f64 {
+    (0..boundary.len())
+        .map(|i| boundary[i].distance_rads(boundary[(i + 1) % boundary.len()]))
+        .sum()
+}
+
+/// Computes the first moment of area of a cell (i.e. the integral of the
+/// position vector over its surface).
+fn moment(cell: CellIndex, boundary: &[LatLng], perimeter: f64) -> Vec3d {
+    let mut moment = Vec3d::new(0., 0., 0.);
+
+    // The moment can be computed exactly from the boundary alone: each arc
+    // contributes its unit normal, scaled by half its length. But the arcs
+    // mostly cancel each other out, which is too imprecise for small cells.
+    if perimeter > EXACT_MOMENT_MIN_PERIMETER {
+        let points = boundary.iter().copied().map(Vec3d::from);
+        let next = points.clone().cycle().skip(1);
+        for (from, to) in points.zip(next) {
+            let normal = from.cross(&to);
+            let norm = sqrt(normal.dot(&normal));
+            let scale = atan2(norm, from.dot(&to)) / (2. * norm);
+            moment = Vec3d::new(
+                mul_add(normal.x, scale, moment.x),
+                mul_add(normal.y, scale, moment.y),
+                mul_add(normal.z, scale, moment.z),
+            );
+        }
+        return moment;
+    }
+
+    // Small cells are split into triangles around their center, small enough
+    // to be approximated by their area at the direction of their vertexes mean.
+    let center = LatLng::from(cell);
+    let c = Vec3d::from(center);
+    for i in 0..boundary.len() {
+        let (a, b) = (boundary[i], boundary[(i + 1) % boundary.len()]);
+        let area = Triangle::new(a, b, center).area();
+        let (a, b) = (Vec3d::from(a), Vec3d::from(b));
+        let mean =
+            Vec3d::new(a.x + b.x + c.x, a.y + b.y + c.y, a.z + b.z + c.z);
+        let scale = area / sqrt(mean.dot(&mean));
+        moment = Vec3d::new(
+            mul_add(mean.x, scale, moment.x),
+            mul_add(mean.y, scale, moment.y),
+            mul_add(mean.z, scale, moment.z),
+        );
+    }
+    moment
+}
+
+/// Converts a moment of area into the centroid of the surface.
+///
+/// Returns `None` if the moment is null (e.g. the whole sphere).
+fn centroid(moment: Vec3d) -> Option<LatLng> {
+    let norm = sqrt(moment.dot(&moment));
+    (norm > f64::EPSILON).then(|| LatLng::from(moment))
+}
+
+/// Returns the shortest and longest lengths, or zeroes if there is none.
+fn edge_range(lengths: impl Iterator<Item = f64>) -> (f64, f64) {
+    lengths
+        .fold(None, |range, length| {
+            Some(match range {
+                None => (length, length),
+                Some((min, max)) => {
+                    (f64::min(min, length), f64::max(max, length))
+                }
+            })
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,558 @@
+//! Outlines of cell sets, expressed in terms of H3 topology.
+//!
+//! Every cell contributes its directed edges, in counter-clockwise order, and
+//! edges shared by two cells of the set cancel each other out. What remains
+//! are the borders of the set, which are then chained into closed rings.
+
+use crate::{
+    CellIndex, DirectedEdgeIndex, Edge, LatLng, Resolution, Vertex,
+    VertexIndex,
+    collections::{Map, Set},
+    error::DissolutionError,
+    grid::neighbors,
+    index::{IndexMode, bits},
+};
+use alloc::vec::Vec;
+use core::iter;
+
+/// A closed ring of directed edges, outlining a set of cells.
+///
+/// Edges are oriented so that the cells of the set are on their left: an
+/// outer ring runs counter-clockwise whereas a hole runs clockwise.
+///
+/// The ring is not explicitly closed: the last edge ends where the first one
+/// starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeRing {
+    edges: Vec<DirectedEdgeIndex>,
+    vertexes: Vec<VertexIndex>,
+}
+
+impl EdgeRing {
+    /// Returns the edges of the ring, in order.
+    ///
+    /// The origin of every edge is a cell of the outlined set.
+    #[must_use]
+    pub fn edges(&self) -> &[DirectedEdgeIndex] {
+        &self.edges
+    }
+
+    /// Returns the vertexes of the ring, in order.
+    ///
+    /// The `i`-th vertex is the starting point of the `i`-th edge.
+    #[must_use]
+    pub fn vertexes(&self) -> &[VertexIndex] {
+        &self.vertexes
+    }
+
+    /// Computes the boundary of the ring, in spherical coordinates.
+    ///
+    /// Distortion vertexes (introduced where an edge crosses an icosahedron
+    /// edge, on Class III resolutions) are included. As for the edges, the
+    /// ring is not explicitly closed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let rings = CellIndex::outline([index])?;
+    /// assert_eq!(rings[0].boundary().len(), 6);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn boundary(&self) -> Vec<LatLng> {
+        let mut coords = Vec::with_capacity(self.edges.len());
+        for edge in &self.edges {
+            let boundary = edge.boundary();
+            // The last vertex is the first one of the next edge.
+            coords.extend(boundary.iter().take(boundary.len() - 1));
+        }
+        coords
+    }
+
+    /// Returns true if the ring is the boundary of a hole.
+    ///
+    /// The orientation is computed from the topology alone, by counting the
+    /// convex (one cell of the set at the vertex) and concave (two cells)
+    /// turns along the ring.
+    #[must_use]
+    pub fn is_hole(&self) -> bool {
+        let count = self.edges.len();
+        let turns = (0..count).fold(0_i32, |acc, i| {
+            let current = self.edges[i].origin();
+            let next = self.edges[(i + 1) % count].origin();
+            if current == next { acc + 1 } else { acc - 1 }
+        });
+
+        turns < 0
+    }
+
+    /// Computes the length of the ring, in radians.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let rings = CellIndex::outline([index])?;
+    /// assert!(rings[0].length_rads() > 0.);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn length_rads(&self) -> f64 {
+        self.edges.iter().map(|edge| edge.length_rads()).sum()
+    }
+
+    /// Computes the length of the ring, in kilometers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let rings = CellIndex::outline([index])?;
+    /// assert!(rings[0].length_km() > 0.);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn length_km(&self) -> f64 {
+        self.edges.iter().map(|edge| edge.length_km()).sum()
+    }
+
+    /// Computes the length of the ring, in meters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let rings = CellIndex::outline([index])?;
+    /// assert!(rings[0].length_m() > 0.);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn length_m(&self) -> f64 {
+        self.edges.iter().map(|edge| edge.length_m()).sum()
+    }
+
+    /// Returns the edges of this ring that are shared with another ring.
+    ///
+    /// Two rings share an edge when they outline adjacent regions: the edge
+    /// then appears in both, with opposite directions. The returned edges are
+    /// the ones of `self`, in ring order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let neighbor = index.grid_ring::<Vec<_>>(1)[0];
+    /// let lhs = CellIndex::outline([index])?;
+    /// let rhs = CellIndex::outline([neighbor])?;
+    /// assert_eq!(lhs[0].shared_edges(&rhs[0]).len(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn shared_edges(&self, other: &Self) -> Vec<DirectedEdgeIndex> {
+        let reversed = other
+            .segments()
+            .map(|(from, to)| (to, from))
+            .collect::<Set<_>>();
+
+        self.segments()
+            .zip(self.edges.iter())
+            .filter_map(|(segment, &edge)| {
+                reversed.contains(&segment).then_some(edge)
+            })
+            .collect()
+    }
+
+    /// Iterates over the edges as pairs of vertexes.
+    fn segments(&self) -> impl Iterator<Item = (VertexIndex, VertexIndex)> {
+        let count = self.vertexes.len();
+        (0..count)
+            .map(move |i| (self.vertexes[i], self.vertexes[(i + 1) % count]))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Computes the outline(s) of a set of homogeneous cells.
+pub fn outline(
+    cells: impl IntoIterator<Item = CellIndex>,
+) -> Result<Vec<EdgeRing>, DissolutionError> {
+    InputOptions::new()
+        .edge_graph(cells)
+        .map(EdgeGraph::into_rings)
+}
+
+// -----------------------------------------------------------------------------
+
+/// A border of a cell, from one vertex to the next.
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    to: VertexIndex,
+    edge: DirectedEdgeIndex,
+}
+
+/// A graph of the borders of a cell set.
+#[derive(Debug, Default)]
+pub struct EdgeGraph {
+    segments: Map<VertexIndex, Vec<Segment>>,
+    /// Starting vertexes of the borders inserted since the last drain, if
+    /// tracked.
+    journal: Option<Vec<VertexIndex>>,
+}
+
+impl EdgeGraph {
+    /// Initializes a new, empty, `EdgeGraph` that keeps track of the inserted
+    /// borders.
+    pub fn with_journal() -> Self {
+        Self {
+            segments: Map::default(),
+            journal: Some(Vec::new()),
+        }
+    }
+
+    /// Returns the starting vertexes of the borders inserted since the last
+    /// call.
+    pub fn drain_journal(&mut self) -> Vec<VertexIndex> {
+        self.journal
+            .as_mut()
+            .map(core::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of vertexes where a border starts.
+    pub fn vertex_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Returns true if a border starts at the given vertex.
+    pub fn contains(&self, vertex: VertexIndex) -> bool {
+        self.segments.contains_key(&vertex)
+    }
+
+    /// Adds the outline of a cell, at the given resolution.
+    ///
+    /// `resolution` must not be finer than the resolution of the cell.
+    pub fn insert_cell(&mut self, cell: CellIndex, resolution: Resolution) {
+        if cell.resolution() == resolution {
+            self.insert_borders(cell);
+        } else {
+            self.insert_large_cell(cell, resolution);
+        }
+    }
+
+    /// Adds every border of a cell to the graph.
+    fn insert_borders(&mut self, cell: CellIndex) {
+        for (from, segment) in borders(cell) {
+            self.insert(from, segment);
+        }
+    }
+
+    /// Adds the outline of a cell coarser than `resolution`.
+    fn insert_large_cell(&mut self, cell: CellIndex, resolution: Resolution) {
+        let children = cell.children(resolution).collect::<Set<_>>();
+        let mut scratchpad = [0; 7];
+
+        for &child in &children {
+            // Only the children on the edge of the parent matter.
+            let count = neighbors(child, &mut scratchpad);
+            let is_boundary = scratchpad[0..count].iter().any(|&neighbor| {
+                !children.contains(&CellIndex::new_unchecked(neighbor))
+            });
+            if !is_boundary {
+                continue;
+            }
+
+            for (from, segment) in borders(child) {
+                if !children.contains(&segment.edge.destination()) {
+                    self.insert(from, segment);
+                }
+            }
+        }
+    }
+
+    /// Adds a border to the graph, canceling it if it's shared.
+    fn insert(&mut self, from: VertexIndex, segment: Segment) {
+        // If we've seen this border already, it was from the other side.
+        if let Some(segments) = self.segments.get_mut(&segment.to)
+            && let Some(pos) = segments.iter().position(|s| s.to == from)
+        {
+            segments.swap_remove(pos);
+            if segments.is_empty() {
+                self.segments.remove(&segment.to);
+            }
+            return;
+        }
+
+        self.segments
+            .entry(from)
+            // A vertex is shared by at most 3 cells.
+            .or_insert_with(|| Vec::with_capacity(3))
+            .push(segment);
+        if let Some(journal) = self.journal.as_mut() {
+            journal.push(from);
+        }
+    }
+
+    /// Removes and returns a border starting at the given vertex.
+    fn take(&mut self, from: VertexIndex) -> Option<Segment> {
+        let segments = self.segments.get_mut(&from)?;
+        let segment = segments.pop();
+        if segments.is_empty() {
+            self.segments.remove(&from);
+        }
+        segment
+    }
+
+    /// Removes and returns any ring from the graph.
+    pub fn pop_ring(&mut self) -> Option<EdgeRing> {
+        let start = *self.segments.keys().next()?;
+        Some(self.take_ring(start))
+    }
+
+    /// Removes and returns the ring going through the given vertex.
+    pub fn take_ring(&mut self, start: VertexIndex) -> EdgeRing {
+        let mut ring = EdgeRing {
+            edges: Vec::new(),
+            vertexes: Vec::new(),
+        };
+
+        let mut from = start;
+        while let Some(segment) = self.take(from) {
+            ring.vertexes.push(from);
+            ring.edges.push(segment.edge);
+            from = segment.to;
+        }
+        debug_assert_eq!(from, start, "unclosed ring");
+
+        ring
+    }
+
+    /// Returns the border starting at the given vertex, and the vertex where
+    /// it ends.
+    ///
+    /// Every vertex is shared by three cells, thus at most one border starts
+    /// at a given vertex.
+    pub fn border(
+        &self,
+        from: VertexIndex,
+    ) -> Option<(DirectedEdgeIndex, VertexIndex)> {
+        self.segments
+            .get(&from)
+            .and_then(|segments| segments.last())
+            .map(|segment| (segment.edge, segment.to))
+    }
+
+    /// Chains the borders into rings.
+    ///
+    /// The output is deterministic: each ring starts with its smallest edge,
+    /// and rings are sorted by their first edge.
+    pub fn into_rings(mut self) -> Vec<EdgeRing> {
+        let mut rings = Vec::new();
+        while let Some(mut ring) = self.pop_ring() {
+            let first = ring
+                .edges
+                .iter()
+                .enumerate()
+                .min_by_key(|&(_, edge)| edge)
+                .map_or(0, |(idx, _)| idx);
+            ring.edges.rotate_left(first);
+            ring.vertexes.rotate_left(first);
+            rings.push(ring);
+        }
+        rings.sort_unstable_by_key(|ring| ring.edges[0]);
+
+        rings
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Input settings shared by the dissolution front-ends.
+#[derive(Debug, Clone, Copy)]
+pub struct InputOptions {
+    pub mode: InputMode,
+    pub check_duplicate: bool,
+}
+
+impl InputOptions {
+    /// Homogeneous input, with duplicate detection.
+    pub const fn new() -> Self {
+        Self {
+            mode: InputMode::Homogeneous,
+            check_duplicate: true,
+        }
+    }
+
+    /// Disables duplicate detection.
+    pub const fn disable_duplicate_detection(mut self) -> Self {
+        self.check_duplicate = false;
+        self
+    }
+
+    /// Accepts heterogeneous input, down to the given resolution.
+    pub const fn enable_heterogeneous_support(
+        mut self,
+        resolution: Resolution,
+    ) -> Self {
+        self.mode = InputMode::Heterogeneous(resolution);
+        self
+    }
+
+    /// Returns a validator for a stream of input cells.
+    pub fn validator(self) -> InputValidator {
+        InputValidator {
+            mode: self.mode,
+            expected: None,
+            seen: self.check_duplicate.then(Set::default),
+            ancestors: Set::default(),
+        }
+    }
+
+    /// Builds the graph of the borders of the input cells.
+    pub fn edge_graph(
+        self,
+        cells: impl IntoIterator<Item = CellIndex>,
+    ) -> Result<EdgeGraph, DissolutionError> {
+        let mut validator = self.validator();
+        let mut graph = EdgeGraph::default();
+        for cell in cells {
+            let resolution = validator.check(cell)?;
+            graph.insert_cell(cell, resolution);
+        }
+
+        Ok(graph)
+    }
+
+    /// Returns the input cells, all converted to the same resolution.
+    ///
+    /// Coarse cells of an heterogeneous input are replaced by their children,
+    /// which may be a lot of cells.
+    #[cfg(feature = "geo")]
+    pub fn cells(
+        self,
+        cells: impl IntoIterator<Item = CellIndex>,
+    ) -> Result<Vec<CellIndex>, DissolutionError> {
+        let mut validator = self.validator();
+        let mut result = Vec::new();
+        for cell in cells {
+            let resolution = validator.check(cell)?;
+            result.extend(cell.children(resolution));
+        }
+
+        Ok(result)
+    }
+}
+
+/// How input cells are handled.
+#[derive(Debug, Clone, Copy)]
+pub enum InputMode {
+    /// An homogeneous set of cells.
+    Homogeneous,
+    /// An heterogeneous set of cells (e.g. compacted) with a max resolution.
+    Heterogeneous(Resolution),
+}
+
+/// Checks a stream of input cells against the input settings.
+#[derive(Debug)]
+pub struct InputValidator {
+    mode: InputMode,
+    /// Expected resolution, for homogeneous input.
+    expected: Option<Resolution>,
+    /// Cells seen so far, if duplicate detection is enabled.
+    seen: Option<Set<CellIndex>>,
+    /// Ancestors of the cells seen so far, for heterogeneous input.
+    ancestors: Set<CellIndex>,
+}
+
+impl InputValidator {
+    /// Checks a cell, and returns the resolution at which it must be outlined.
+    pub fn check(
+        &mut self,
+        cell: CellIndex,
+    ) -> Result<Resolution, DissolutionError> {
+        let resolution = cell.resolution();
+        let target = match self.mode {
+            InputMode::Homogeneous => *self.expected.get_or_insert(resolution),
+            InputMode::Heterogeneous(target) => target,
+        };
+        let is_valid = match self.mode {
+            InputMode::Homogeneous => resolution == target,
+            InputMode::Heterogeneous(_) => resolution <= target,
+        };
+        if !is_valid {
+            return Err(DissolutionError::UnsupportedResolution);
+        }
+
+        if let Some(seen) = self.seen.as_mut() {
+            if !seen.insert(cell) {
+                return Err(DissolutionError::DuplicateInput);
+            }
+            // Overlaps are detected without expanding the cells: a cell
+            // overlaps the previous ones if one of them is its ancestor, or
+            // if it's the ancestor of one of them.
+            if matches!(self.mode, InputMode::Heterogeneous(_)) {
+                let ancestors =
+                    iter::successors(resolution.pred(), |r| r.pred()).map(
+                        |resolution| cell.parent(resolution).expect("ancestor"),
+                    );
+                if self.ancestors.contains(&cell)
+                    || ancestors.clone().any(|parent| seen.contains(&parent))
+                {
+                    return Err(DissolutionError::DuplicateInput);
+                }
+                for parent in ancestors {
+                    // Coarser ancestors are already known past the first hit.
+                    if !self.ancestors.insert(parent) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(target)
+    }
+
+    /// Forgets the cells seen so far.
+    pub fn clear(&mut self) {
+        if let Some(seen) = self.seen.as_mut() {
+            seen.clear();
+        }
+        self.ancestors.clear();
+    }
+}
+
+/// Returns the borders of a cell, in counter-clockwise order.
+fn borders(cell: CellIndex) -> impl Iterator<Item = (VertexIndex, Segment)> {
+    let vertexes = cell.vertexes().collect::<Vec<_>>();
+    let template = bits::set_mode(u64::from(cell), IndexMode::DirectedEdge);
+    let count = cell.vertex_count();
+
+    (0..count).map(move |vertex| {
+        let i = usize::from(vertex);
+        // SAFETY: loop bound ensure valid vertex value.
+        let vertex = Vertex::new_unchecked(vertex);
+        let direction = vertex.to_direction(cell);
+        // SAFETY: a direction toward a vertex is a valid edge.
+        let edge = DirectedEdgeIndex::new_unchecked(bits::set_edge(
+            template,
+            Edge::new_unchecked(direction.into()),
+        ));
+        (
+            vertexes[i],
+            Segment {
+                to: vertexes[(i + 1) % vertexes.len()],
+                edge,
+            },
+        )
+    })
+}
@@ -0,0 +1,284 @@
+//! Nearest-neighbor search over point sets.
+//!
+//! Points are bucketed by the cell containing them, and queries explore the
+//! buckets ring by ring around the query point, until the remaining rings
+//! cannot contain a closer point. On sparse indexes, where the rings would
+//! visit more cells than there are buckets, the remaining buckets are scanned
+//! instead.
+
+use crate::{
+    CellIndex, LatLng, Resolution,
+    collections::{Map, Set},
+};
+use alloc::vec::Vec;
+
+/// Lower bound of the ratio between the length of any edge and the average
+/// hexagon edge length, at every resolution.
+///
+/// Edge lengths vary less than that, even around pentagons, but since this is
+/// only used to prune the search a loose bound is enough.
+const MIN_EDGE_RATIO: f64 = 0.5;
+
+/// An index of points, answering nearest-neighbor and within-radius queries.
+///
+/// The resolution should be chosen so that cells hold a few points each, and
+/// query radii span a few cells.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{LatLng, PointIndex, Resolution};
+///
+/// let index = PointIndex::from_points(Resolution::Seven, [
+///     (LatLng::new(48.864716, 2.349014)?, "Paris"),
+///     (LatLng::new(48.856613, 2.352222)?, "Hôtel de Ville"),
+///     (LatLng::new(45.764043, 4.835659)?, "Lyon"),
+/// ]);
+///
+/// let origin = LatLng::new(48.8566, 2.3522)?;
+/// let nearest = index.nearest(origin, 2);
+/// assert_eq!(*nearest[0].value, "Hôtel de Ville");
+/// assert_eq!(*nearest[1].value, "Paris");
+/// assert_eq!(index.within(origin, 10_000.).len(), 2);
+/// # Ok::<(), h3o::error::InvalidLatLng>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PointIndex<T> {
+    resolution: Resolution,
+    buckets: Map<CellIndex, Vec<(LatLng, T)>>,
+    len: usize,
+}
+
+impl<T> PointIndex<T> {
+    /// Initializes an empty index, bucketing points at the given resolution.
+    #[must_use]
+    pub fn new(resolution: Resolution) -> Self {
+        Self {
+            resolution,
+            buckets: Map::default(),
+            len: 0,
+        }
+    }
+
+    /// Builds an index from a set of points and their associated values.
+    ///
+    /// This is faster than inserting the points one by one.
+    #[must_use]
+    pub fn from_points(
+        resolution: Resolution,
+        points: impl IntoIterator<Item = (LatLng, T)>,
+    ) -> Self {
+        let mut points = points
+            .into_iter()
+            .map(|(point, value)| (point.to_cell(resolution), point, value))
+            .collect::<Vec<_>>();
+        points.sort_unstable_by_key(|&(cell, ..)| cell);
+
+        let mut index = Self::new(resolution);
+        index.len = points.len();
+        let mut points = points.into_iter().peekable();
+        while let Some((cell, point, value)) = points.next() {
+            let mut bucket = Vec::from([(point, value)]);
+            while let Some((_, point, value)) =
+                points.next_if(|&(next, ..)| next == cell)
+            {
+                bucket.push((point, value));
+            }
+            index.buckets.insert(cell, bucket);
+        }
+
+        index
+    }
+
+    /// Returns the resolution used to bucket the points.
+    #[must_use]
+    pub const fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// Returns the number of points in the index.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the index contains no point.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds a point to the index.
+    pub fn insert(&mut self, point: LatLng, value: T) {
+        let cell = point.to_cell(self.resolution);
+        self.buckets.entry(cell).or_default().push((point, value));
+        self.len += 1;
+    }
+
+    /// Removes a point from the index.
+    ///
+    /// Returns whether the point was found. If the point was inserted several
+    /// times with the same value, only one occurrence is removed.
+    pub fn remove(&mut self, point: LatLng, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let cell = point.to_cell(self.resolution);
+        let Some(bucket) = self.buckets.get_mut(&cell) else {
+            return false;
+        };
+        let Some(position) = bucket
+            .iter()
+            .position(|entry| entry.0 == point && entry.1 == *value)
+        else {
+            return false;
+        };
+
+        bucket.swap_remove(position);
+        if bucket.is_empty() {
+            self.buckets.remove(&cell);
+        }
+        self.len -= 1;
+        true
+    }
+
+    /// Returns an iterator over the points of the index, and their values.
+    ///
+    /// Points are grouped by cell, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (LatLng, &T)> {
+        self.buckets
+            .values()
+            .flatten()
+            .map(|(point, value)| (*point, value))
+    }
+
+    /// Returns the `k` points closest to `origin`, sorted by distance.
+    ///
+    /// Ties are broken arbitrarily.
+    #[must_use]
+    pub fn nearest(&self, origin: LatLng, k: usize) -> Vec<Neighbor<'_, T>> {
+        let mut result = Vec::new();
+        if k == 0 {
+            return result;
+        }
+
+        self.explore(origin, |neighbors, bound| {
+            result.extend(neighbors);
+            if result.len() > k {
+                result.select_nth_unstable_by(k - 1, Neighbor::cmp_distance);
+                result.truncate(k);
+            }
+            // Unexplored points cannot be closer than the current k-th one.
+            result.len() == k
+                && result.iter().all(|neighbor| neighbor.distance_m <= bound)
+        });
+
+        result.sort_unstable_by(Neighbor::cmp_distance);
+        result
+    }
+
+    /// Returns the points within `radius` meters of `origin`, sorted by
+    /// distance.
+    #[must_use]
+    pub fn within(&self, origin: LatLng, radius: f64) -> Vec<Neighbor<'_, T>> {
+        let mut result = Vec::new();
+
+        self.explore(origin, |neighbors, bound| {
+            result.extend(
+                neighbors.filter(|neighbor| neighbor.distance_m <= radius),
+            );
+            bound > radius
+        });
+
+        result.sort_unstable_by(Neighbor::cmp_distance);
+        result
+    }
+
+    /// Explores the buckets ring by ring around `origin`, until `visit`
+    /// returns true.
+    ///
+    /// `visit` is called with the points of each ring and a lower bound of
+    /// the distance of the unexplored points. Once the rings have visited
+    /// more cells than there are buckets, every remaining point is visited at
+    /// once, with an infinite bound.
+    fn explore<'a, F>(&'a self, origin: LatLng, mut visit: F)
+    where
+        F: FnMut(&mut dyn Iterator<Item = Neighbor<'a, T>>, f64) -> bool,
+    {
+        let center = origin.to_cell(self.resolution);
+        let min_edge = self.resolution.edge_length_m() * MIN_EDGE_RATIO;
+        let neighbor = |(point, value): &'a (LatLng, T)| Neighbor {
+            point: *point,
+            value,
+            distance_m: origin.distance_m(*point),
+        };
+        let mut explored = Set::default();
+        let (mut seen, mut visited) = (0, 0);
+
+        for k in 0.. {
+            // Scanning the remaining buckets is now cheaper than the rings.
+            if visited > self.buckets.len() {
+                let mut neighbors = self
+                    .buckets
+                    .iter()
+                    .filter(|(cell, _)| !explored.contains(*cell))
+                    .flat_map(|(_, bucket)| bucket)
+                    .map(neighbor);
+                visit(&mut neighbors, f64::INFINITY);
+                return;
+            }
+
+            let ring = center.grid_ring::<Vec<_>>(k);
+            // The whole grid has been explored.
+            if ring.is_empty() {
+                return;
+            }
+            visited += ring.len();
+
+            let mut neighbors = ring
+                .into_iter()
+                .filter_map(|cell| self.buckets.get_key_value(&cell))
+                .inspect(|&(&cell, bucket)| {
+                    seen += bucket.len();
+                    explored.insert(cell);
+                })
+                .flat_map(|(_, bucket)| bucket)
+                .map(neighbor);
+            // Reaching the unexplored points requires crossing `k` rings.
+            let bound = f64::from(k) * min_edge;
+            let is_done = visit(&mut neighbors, bound);
+            drop(neighbors);
+            if is_done || seen == self.len {
+                return;
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A point returned by a query on a [`PointIndex`].
+#[derive(Debug, PartialEq)]
+pub struct Neighbor<'a, T> {
+    /// The point.
+    pub point: LatLng,
+    /// The value associated to the point.
+    pub value: &'a T,
+    /// Distance to the query point, in meters.
+    pub distance_m: f64,
+}
+
+// Manual implementations, to avoid requiring `T: Clone`.
+impl<T> Clone for Neighbor<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Neighbor<'_, T> {}
+
+impl<T> Neighbor<'_, T> {
+    fn cmp_distance(&self, other: &Self) -> core::cmp::Ordering {
+        self.distance_m.total_cmp(&other.distance_m)
+    }
+}
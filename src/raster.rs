@@ -0,0 +1,498 @@
+//! Conversion between georeferenced lat/lng rasters and cells.
+//!
+//! Rasters are regular grids of values, in row-major order, georeferenced by
+//! an affine transform from pixel coordinates to longitude/latitude (in
+//! degrees), following the GDAL convention.
+
+use crate::{
+    CellIndex, LatLng, Resolution,
+    collections::{Map, Set},
+    error::InvalidRaster,
+    math::{abs, ceil, cos, mul_add},
+};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+/// Length of a degree of latitude, in meters.
+const DEGREE_LENGTH_M: f64 = 111_195.;
+/// Maximum number of samples, per axis, taken in each pixel for the
+/// area-weighted aggregation.
+const MAX_SUPERSAMPLING: u32 = 16;
+
+/// An affine transform from pixel coordinates to longitude/latitude.
+///
+/// The coordinates of the pixel `(col, row)` top-left corner are:
+///
+/// ```text
+/// lng = c[0] + col * c[1] + row * c[2]
+/// lat = c[3] + col * c[4] + row * c[5]
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoTransform {
+    coefficients: [f64; 6],
+}
+
+impl GeoTransform {
+    /// Initializes a transform from its coefficients, in GDAL order.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidRaster`] if a coefficient isn't finite or if the transform is
+    /// degenerate (i.e. not invertible).
+    pub fn new(coefficients: [f64; 6]) -> Result<Self, InvalidRaster> {
+        let [_, a, b, _, c, d] = coefficients;
+        if !coefficients.iter().all(|c| c.is_finite()) {
+            return Err(InvalidRaster::new("non-finite transform coefficient"));
+        }
+        if mul_add(a, d, -(b * c)) == 0. {
+            return Err(InvalidRaster::new("degenerate transform"));
+        }
+
+        Ok(Self { coefficients })
+    }
+
+    /// Initializes a north-up transform (i.e. without rotation), from the
+    /// coordinates of the top-left corner and the size of a pixel, in
+    /// degrees.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidRaster`] if a parameter isn't finite or a size is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::GeoTransform;
+    ///
+    /// // Global raster, with 0.1° pixels.
+    /// let transform = GeoTransform::north_up(-180., 90., 0.1, 0.1)?;
+    /// # Ok::<(), h3o::error::InvalidRaster>(())
+    /// ```
+    pub fn north_up(
+        west: f64,
+        north: f64,
+        pixel_width: f64,
+        pixel_height: f64,
+    ) -> Result<Self, InvalidRaster> {
+        Self::new([west, pixel_width, 0., north, 0., -pixel_height])
+    }
+
+    /// Returns the coefficients of the transform, in GDAL order.
+    #[must_use]
+    pub const fn coefficients(&self) -> [f64; 6] {
+        self.coefficients
+    }
+
+    /// Returns the coordinates of the given pixel coordinates, if valid.
+    ///
+    /// Integer coordinates are pixel corners, use `col + 0.5, row + 0.5` for
+    /// the pixel center.
+    #[must_use]
+    pub fn to_latlng(&self, col: f64, row: f64) -> Option<LatLng> {
+        let [x0, a, b, y0, c, d] = self.coefficients;
+        let lng = mul_add(row, b, mul_add(col, a, x0));
+        let lat = mul_add(row, d, mul_add(col, c, y0));
+
+        (abs(lat) <= 90.)
+            .then(|| LatLng::new(lat, lng).ok())
+            .flatten()
+    }
+
+    /// Rasterizes cell values onto a grid of `width` by `height` pixels.
+    ///
+    /// Each pixel gets the value of the cell containing its center, if any.
+    /// Cells can be of any resolution (e.g. a compacted set). If cells
+    /// overlap, the value of the finest one is used.
+    ///
+    /// Returns the pixel values, in row-major order.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidRaster`] if the number of pixels overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{GeoTransform, LatLng, Resolution};
+    ///
+    /// let cell = LatLng::new(48.8566, 2.3522)?.to_cell(Resolution::Five);
+    /// let transform = GeoTransform::north_up(2.3, 48.9, 0.01, 0.01)?;
+    /// let pixels = transform.rasterize(10, 10, [(cell, 42_u8)])?;
+    /// assert_eq!(pixels[55], Some(42));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn rasterize<T: Clone>(
+        &self,
+        width: usize,
+        height: usize,
+        cells: impl IntoIterator<Item = (CellIndex, T)>,
+    ) -> Result<Vec<Option<T>>, InvalidRaster> {
+        let size = width
+            .checked_mul(height)
+            .ok_or_else(|| InvalidRaster::new("raster size overflow"))?;
+        let mut values = Map::default();
+        let mut resolutions = 0_u16;
+        for (cell, value) in cells {
+            resolutions |= 1 << u8::from(cell.resolution());
+            values.insert(cell, value);
+        }
+        let mut pixels = vec![None; size];
+        let Some(finest) =
+            Resolution::range(Resolution::Zero, Resolution::Fifteen)
+                .rev()
+                .find(|&resolution| {
+                    resolutions & (1 << u8::from(resolution)) != 0
+                })
+        else {
+            return Ok(pixels);
+        };
+
+        for (index, pixel) in pixels.iter_mut().enumerate() {
+            let Some(center) = self.pixel_center(index, width) else {
+                continue;
+            };
+            let cell = center.to_cell(finest);
+            // Look for the finest cell containing the pixel center.
+            *pixel = Resolution::range(Resolution::Zero, finest)
+                .rev()
+                .filter(|&res| resolutions & (1 << u8::from(res)) != 0)
+                .find_map(|res| {
+                    let ancestor = cell.parent(res).expect("coarser");
+                    values.get(&ancestor)
+                })
+                .cloned();
+        }
+
+        Ok(pixels)
+    }
+
+    /// Returns the center of the pixel at the given row-major index.
+    #[expect(clippy::cast_precision_loss, reason = "raster dimensions")]
+    fn pixel_center(&self, index: usize, width: usize) -> Option<LatLng> {
+        let (row, col) = (index / width, index % width);
+        self.to_latlng(col as f64 + 0.5, row as f64 + 0.5)
+    }
+
+    /// Returns the pixel coordinates of the given coordinates.
+    fn pixel_coordinates(&self, ll: LatLng) -> (f64, f64) {
+        let [x0, a, b, y0, c, d] = self.coefficients;
+        let (dlng, dlat) = (ll.lng() - x0, ll.lat() - y0);
+        let det = mul_add(a, d, -(b * c));
+
+        (
+            mul_add(d, dlng, -(b * dlat)) / det,
+            mul_add(a, dlat, -(c * dlng)) / det,
+        )
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A georeferenced lat/lng raster.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{GeoTransform, Raster, Resolution};
+///
+/// // A 4x4 elevation model, with 0.01° pixels.
+/// let transform = GeoTransform::north_up(2.3, 48.9, 0.01, 0.01)?;
+/// let elevation = [
+///     35_i16, 36, 38, 41,
+///     34, 35, 37, 40,
+///     33, 34, 36, 39,
+///     32, 33, 35, 38,
+/// ];
+/// let raster = Raster::new(transform, 4, 4, &elevation)?;
+///
+/// let cells = raster.to_cells_mean(Resolution::Six);
+/// assert!(!cells.is_empty());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Raster<'a, T> {
+    transform: GeoTransform,
+    width: usize,
+    height: usize,
+    data: &'a [T],
+    nodata: Option<T>,
+}
+
+impl<'a, T: Copy> Raster<'a, T> {
+    /// Initializes a raster of `width` by `height` pixels, in row-major order.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidRaster`] if the data size doesn't match the dimensions.
+    pub fn new(
+        transform: GeoTransform,
+        width: usize,
+        height: usize,
+        data: &'a [T],
+    ) -> Result<Self, InvalidRaster> {
+        if width.checked_mul(height) != Some(data.len()) {
+            return Err(InvalidRaster::new("data size mismatch"));
+        }
+
+        Ok(Self {
+            transform,
+            width,
+            height,
+            data,
+            nodata: None,
+        })
+    }
+
+    /// Set the value marking pixels without data, which are ignored.
+    #[must_use]
+    pub const fn with_nodata(mut self, nodata: T) -> Self {
+        self.nodata = Some(nodata);
+        self
+    }
+
+    /// Returns the transform of the raster.
+    #[must_use]
+    pub const fn transform(&self) -> GeoTransform {
+        self.transform
+    }
+
+    /// Returns the width of the raster, in pixels.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the raster, in pixels.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Aggregates the pixels into cells, using the mean of the pixels whose
+    /// center is in each cell.
+    ///
+    /// Cells smaller than pixels may contain no pixel center: prefer
+    /// [`Self::to_cells_area_weighted`] in that case. NaN values are ignored.
+    ///
+    /// Returns the cell values, sorted by cell.
+    #[must_use]
+    pub fn to_cells_mean(&self, resolution: Resolution) -> Vec<(CellIndex, f64)>
+    where
+        T: Into<f64> + PartialEq,
+    {
+        let mut sums = Map::<CellIndex, (f64, f64)>::default();
+        for (cell, value) in self.pixels(resolution) {
+            let value = value.into();
+            if value.is_nan() {
+                continue;
+            }
+            let entry = sums.entry(cell).or_default();
+            entry.0 += value;
+            entry.1 += 1.;
+        }
+
+        sorted(
+            sums.into_iter()
+                .map(|(cell, (sum, count))| (cell, sum / count)),
+        )
+    }
+
+    /// Aggregates the pixels into cells, using the most frequent value of the
+    /// pixels whose center is in each cell.
+    ///
+    /// Ties are broken in favor of the lowest value.
+    ///
+    /// Returns the cell values, sorted by cell.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{GeoTransform, Raster, Resolution};
+    ///
+    /// // Land cover classes.
+    /// let transform = GeoTransform::north_up(2.3, 48.9, 0.001, 0.001)?;
+    /// let classes = [1_u8, 1, 2, 1, 3, 1, 2, 2, 1];
+    /// let raster = Raster::new(transform, 3, 3, &classes)?;
+    ///
+    /// let cells = raster.to_cells_mode(h3o::Resolution::Five);
+    /// assert_eq!(cells.len(), 1);
+    /// assert_eq!(cells[0].1, 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn to_cells_mode(&self, resolution: Resolution) -> Vec<(CellIndex, T)>
+    where
+        T: Ord,
+    {
+        let mut counts = Map::<CellIndex, BTreeMap<T, usize>>::default();
+        for (cell, value) in self.pixels(resolution) {
+            *counts.entry(cell).or_default().entry(value).or_default() += 1;
+        }
+
+        sorted(counts.into_iter().filter_map(|(cell, counts)| {
+            // `max_by_key` returns the last maximum: iterate backward.
+            let mode = counts
+                .into_iter()
+                .rev()
+                .max_by_key(|&(_, count)| count)
+                .map(|(value, _)| value)?;
+            Some((cell, mode))
+        }))
+    }
+
+    /// Aggregates the pixels into cells, weighting each pixel by the area it
+    /// covers in each cell.
+    ///
+    /// Pixels are supersampled so that even cells smaller than a pixel get a
+    /// value, and each sample is weighted by its area on the sphere (which
+    /// decreases with the latitude on a lat/lng grid). NaN values are
+    /// ignored.
+    ///
+    /// Supersampling is capped at 16×16 samples per pixel: beyond that, cells
+    /// missed by the samples get the value of the pixel containing their
+    /// center.
+    ///
+    /// Returns the cell values, sorted by cell.
+    #[must_use]
+    pub fn to_cells_area_weighted(
+        &self,
+        resolution: Resolution,
+    ) -> Vec<(CellIndex, f64)>
+    where
+        T: Into<f64> + PartialEq,
+    {
+        let samples = self.supersampling(resolution);
+        let step = 1. / f64::from(samples);
+        let mut sums = Map::<CellIndex, (f64, f64)>::default();
+        // Samples may miss cells when the supersampling is capped.
+        let mut centers = Vec::new();
+
+        for (index, &value) in self.data.iter().enumerate() {
+            if self.nodata == Some(value) {
+                continue;
+            }
+            let value = value.into();
+            if value.is_nan() {
+                continue;
+            }
+            #[expect(clippy::cast_precision_loss, reason = "raster dimensions")]
+            let (row, col) =
+                ((index / self.width) as f64, (index % self.width) as f64);
+            for i in 0..samples {
+                for j in 0..samples {
+                    let Some(point) = self.transform.to_latlng(
+                        mul_add(f64::from(i) + 0.5, step, col),
+                        mul_add(f64::from(j) + 0.5, step, row),
+                    ) else {
+                        continue;
+                    };
+                    let weight = cos(point.lat_radians());
+                    let entry =
+                        sums.entry(point.to_cell(resolution)).or_default();
+                    entry.0 += value * weight;
+                    entry.1 += weight;
+                }
+            }
+            if samples == MAX_SUPERSAMPLING {
+                centers.extend(
+                    self.pixel_cells(index, resolution)
+                        .into_iter()
+                        .map(|cell| (cell, value)),
+                );
+            }
+        }
+
+        let mut values = sums
+            .into_iter()
+            .filter(|&(_, (_, weight))| weight > 0.)
+            .map(|(cell, (sum, weight))| (cell, sum / weight))
+            .collect::<Map<_, _>>();
+        for (cell, value) in centers {
+            values.entry(cell).or_insert(value);
+        }
+
+        sorted(values.into_iter())
+    }
+
+    /// Returns the cells whose center is in the pixel at the given row-major
+    /// index.
+    ///
+    /// Cells are expected to be much smaller than the pixel, so that their
+    /// centers are reachable from the pixel center through neighbors.
+    fn pixel_cells(
+        &self,
+        index: usize,
+        resolution: Resolution,
+    ) -> Vec<CellIndex> {
+        let Some(center) = self.transform.pixel_center(index, self.width)
+        else {
+            return Vec::new();
+        };
+        #[expect(clippy::cast_precision_loss, reason = "raster dimensions")]
+        let (row, col) =
+            ((index / self.width) as f64, (index % self.width) as f64);
+        let contains = |cell: CellIndex| {
+            let (x, y) = self.transform.pixel_coordinates(LatLng::from(cell));
+            (col..col + 1.).contains(&x) && (row..row + 1.).contains(&y)
+        };
+
+        let start = center.to_cell(resolution);
+        let mut seen = Set::default();
+        seen.insert(start);
+        let mut cells = Vec::from([start]);
+        let mut i = 0;
+        while let Some(&cell) = cells.get(i) {
+            i += 1;
+            for neighbor in cell.grid_disk::<Vec<_>>(1) {
+                if seen.insert(neighbor) && contains(neighbor) {
+                    cells.push(neighbor);
+                }
+            }
+        }
+
+        cells
+    }
+
+    /// Returns the cell containing the center of each pixel with data, and
+    /// the pixel value.
+    fn pixels(
+        &self,
+        resolution: Resolution,
+    ) -> impl Iterator<Item = (CellIndex, T)> + '_
+    where
+        T: PartialEq,
+    {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(|&(_, value)| self.nodata != Some(*value))
+            .filter_map(move |(index, &value)| {
+                let center = self.transform.pixel_center(index, self.width)?;
+                Some((center.to_cell(resolution), value))
+            })
+    }
+
+    /// Returns the number of samples per axis to take in each pixel, so that
+    /// samples are smaller than half a cell edge.
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "clamped to a small positive value"
+    )]
+    fn supersampling(&self, resolution: Resolution) -> u32 {
+        let [_, a, b, _, c, d] = self.transform.coefficients;
+        let pixel_size =
+            (abs(a) + abs(b)).max(abs(c) + abs(d)) * DEGREE_LENGTH_M;
+        let samples = ceil(pixel_size / (resolution.edge_length_m() / 2.));
+
+        samples.clamp(1., f64::from(MAX_SUPERSAMPLING)) as u32
+    }
+}
+
+/// Collects and sorts cell values.
+fn sorted<T>(
+    values: impl Iterator<Item = (CellIndex, T)>,
+) -> Vec<(CellIndex, T)> {
+    let mut values = values.collect::<Vec<_>>();
+    values.sort_unstable_by_key(|(cell, _)| *cell);
+    values
+}
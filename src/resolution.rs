@@ -1,4 +1,6 @@
-use crate::{BaseCell, CellIndex, NUM_PENTAGONS, error, index::bits};
+use crate::{
+    BaseCell, CellIndex, EarthModel, NUM_PENTAGONS, error, index::bits,
+};
 use core::{ffi::c_int, fmt, iter::DoubleEndedIterator, str::FromStr};
 
 /// Cell resolution, from 0 to 15.
@@ -316,6 +318,119 @@ impl Resolution {
         }
     }
 
+    /// Returns the average hexagon area, in square kilometers, at this
+    /// resolution on the specified Earth model (excludes pentagons).
+    ///
+    /// The spherical model of H3 uses the authalic sphere of WGS84, thus the
+    /// average area is the same with every model (only the areas of the
+    /// individual cells differ).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{EarthModel, Resolution};
+    ///
+    /// let avg_area = Resolution::Three.area_km2_with(EarthModel::Wgs84);
+    /// assert_eq!(avg_area, Resolution::Three.area_km2());
+    /// ```
+    #[must_use]
+    pub const fn area_km2_with(self, model: EarthModel) -> f64 {
+        match model {
+            EarthModel::Sphere | EarthModel::Wgs84 => self.area_km2(),
+        }
+    }
+
+    /// Returns the average hexagon area, in square meters, at this resolution
+    /// on the specified Earth model (excludes pentagons).
+    ///
+    /// See [`Self::area_km2_with`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{EarthModel, Resolution};
+    ///
+    /// let avg_area = Resolution::Three.area_m2_with(EarthModel::Wgs84);
+    /// ```
+    #[must_use]
+    pub const fn area_m2_with(self, model: EarthModel) -> f64 {
+        match model {
+            EarthModel::Sphere | EarthModel::Wgs84 => self.area_m2(),
+        }
+    }
+
+    /// Returns the average hexagon edge length, in kilometers, at this
+    /// resolution on the specified Earth model (excludes pentagons).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{EarthModel, Resolution};
+    ///
+    /// let avg_edge_len =
+    ///     Resolution::Three.edge_length_km_with(EarthModel::Wgs84);
+    /// ```
+    #[must_use]
+    pub const fn edge_length_km_with(self, model: EarthModel) -> f64 {
+        if matches!(model, EarthModel::Sphere) {
+            return self.edge_length_km();
+        }
+        match self {
+            Self::Zero => 1281.2592123227769,
+            Self::One => 483.0580485320127,
+            Self::Two => 182.51341347231966,
+            Self::Three => 68.97939454322636,
+            Self::Four => 26.071824962348824,
+            Self::Five => 9.854115669256311,
+            Self::Six => 3.7245419931640895,
+            Self::Seven => 1.4064792869102287,
+            Self::Eight => 0.5314153419720292,
+            Self::Nine => 0.20078665097417364,
+            Self::Ten => 0.07586397310212886,
+            Self::Eleven => 0.028663969377032073,
+            Self::Twelve => 0.010830215013144033,
+            Self::Thirteen => 0.004092020741723079,
+            Self::Fourteen => 0.0015461035381448929,
+            Self::Fifteen => 0.0005841700962780508,
+        }
+    }
+
+    /// Returns the average hexagon edge length, in meters, at this
+    /// resolution on the specified Earth model (excludes pentagons).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{EarthModel, Resolution};
+    ///
+    /// let avg_edge_len =
+    ///     Resolution::Three.edge_length_m_with(EarthModel::Wgs84);
+    /// ```
+    #[must_use]
+    pub const fn edge_length_m_with(self, model: EarthModel) -> f64 {
+        if matches!(model, EarthModel::Sphere) {
+            return self.edge_length_m();
+        }
+        match self {
+            Self::Zero => 1281259.212322777,
+            Self::One => 483058.04853201273,
+            Self::Two => 182513.41347231966,
+            Self::Three => 68979.39454322636,
+            Self::Four => 26071.824962348823,
+            Self::Five => 9854.11566925631,
+            Self::Six => 3724.5419931640895,
+            Self::Seven => 1406.4792869102287,
+            Self::Eight => 531.4153419720292,
+            Self::Nine => 200.78665097417365,
+            Self::Ten => 75.86397310212887,
+            Self::Eleven => 28.663969377032075,
+            Self::Twelve => 10.830215013144034,
+            Self::Thirteen => 4.092020741723079,
+            Self::Fourteen => 1.5461035381448929,
+            Self::Fifteen => 0.5841700962780508,
+        }
+    }
+
     /// Returns the number of unique H3 indexes at the given resolution.
     ///
     /// # Example
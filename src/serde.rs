@@ -0,0 +1,393 @@
+//! Serde representations of the H3 index types.
+//!
+//! By default, [`CellIndex`], [`DirectedEdgeIndex`] and [`VertexIndex`] are
+//! serialized as an hexadecimal string (the same as their `Display`
+//! implementation) by human-readable formats (e.g. JSON), and as a 64-bit
+//! integer by the compact ones. When deserializing from a human-readable
+//! format, every representation below is accepted.
+//!
+//! The following modules can be used with `#[serde(with = "...")]` to force a
+//! specific representation:
+//! - [`hex`]: the hexadecimal string, e.g. `"8a1fb46622dffff"` (a `0x` prefix
+//!   is accepted on deserialization).
+//! - [`integer`]: the raw 64-bit integer, e.g. `622054503267303423`.
+//! - [`components`]: an object detailing the index, e.g.
+//!   `{"res": 2, "base_cell": 15, "digits": [0, 6]}`. Directed edges and
+//!   vertexes have an additional `edge` or `vertex` field, on top of the
+//!   components of their origin/owner cell.
+//!
+//! Indexes are always validated on deserialization.
+//!
+//! # Example
+//!
+//! ```
+//! use h3o::CellIndex;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Record {
+//!     cell: CellIndex,
+//!     #[serde(with = "h3o::serde::integer")]
+//!     raw: CellIndex,
+//! }
+//!
+//! let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+//! let json = serde_json::to_string(&Record { cell, raw: cell })?;
+//! assert_eq!(
+//!     json,
+//!     r#"{"cell":"8a1fb46622dffff","raw":622054503267303423}"#
+//! );
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::{
+    BaseCell, CellIndex, DEFAULT_CELL_INDEX, DirectedEdgeIndex, Direction,
+    Edge, IndexMode, Resolution, Vertex, VertexIndex, index::bits,
+};
+use core::{fmt, marker::PhantomData};
+use sealed::Components;
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, MapAccess, SeqAccess, Visitor, value::MapAccessDeserializer},
+};
+
+/// An H3 index type supported by the serde helpers.
+///
+/// This trait is sealed and cannot be implemented outside of `h3o`.
+pub trait Index: sealed::Sealed {}
+
+mod sealed {
+    use alloc::vec::Vec;
+    use serde::de;
+
+    /// The components of an index.
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Components {
+        pub(super) res: u8,
+        pub(super) base_cell: u8,
+        pub(super) digits: Vec<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub(super) edge: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub(super) vertex: Option<u8>,
+    }
+
+    pub trait Sealed:
+        Copy
+        + Into<u64>
+        + TryFrom<u64, Error: core::fmt::Display>
+        + core::fmt::LowerHex
+    {
+        /// Decomposes the index.
+        fn to_components(self) -> Components;
+
+        /// Rebuilds, and validates, an index from its components.
+        fn from_components<E: de::Error>(
+            components: Components,
+        ) -> Result<Self, E>;
+    }
+}
+
+impl Index for CellIndex {}
+impl Index for DirectedEdgeIndex {}
+impl Index for VertexIndex {}
+
+// -----------------------------------------------------------------------------
+
+/// Serializes indexes as hexadecimal strings.
+pub mod hex {
+    use super::{Index, IndexVisitor};
+    use serde::{Deserializer, Serializer};
+
+    /// Serializes an index as an hexadecimal string.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the serializer does.
+    pub fn serialize<T, S>(index: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Index,
+        S: Serializer,
+    {
+        serializer.collect_str(&format_args!("{index:x}"))
+    }
+
+    /// Deserializes an index from an hexadecimal string, with an optional
+    /// `0x` prefix.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the input isn't a string or isn't a valid index.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Index,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(IndexVisitor::new())
+    }
+}
+
+/// Serializes indexes as 64-bit integers.
+pub mod integer {
+    use super::{Index, IndexVisitor};
+    use serde::{Deserializer, Serializer};
+
+    /// Serializes an index as a 64-bit integer.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the serializer does.
+    pub fn serialize<T, S>(index: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Index,
+        S: Serializer,
+    {
+        serializer.serialize_u64((*index).into())
+    }
+
+    /// Deserializes an index from a 64-bit integer.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the input isn't an integer or isn't a valid index.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Index,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_u64(IndexVisitor::new())
+    }
+}
+
+/// Serializes indexes as objects detailing their components.
+pub mod components {
+    use super::{Components, Index};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serializes an index as a `{res, base_cell, digits}` object.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the serializer does.
+    pub fn serialize<T, S>(index: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Index,
+        S: Serializer,
+    {
+        index.to_components().serialize(serializer)
+    }
+
+    /// Deserializes an index from a `{res, base_cell, digits}` object.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the input isn't an object or isn't a valid index.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Index,
+        D: Deserializer<'de>,
+    {
+        Components::deserialize(deserializer).and_then(T::from_components)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+impl Components {
+    fn new(cell: CellIndex) -> Self {
+        let resolution = cell.resolution();
+        Self {
+            res: resolution.into(),
+            base_cell: cell.base_cell().into(),
+            digits: Resolution::range(Resolution::One, resolution)
+                .map(|res| {
+                    u8::from(cell.direction_at(res).expect("valid resolution"))
+                })
+                .collect(),
+            edge: None,
+            vertex: None,
+        }
+    }
+
+    /// Builds the bits of the cell, validating each component.
+    fn to_cell<E: de::Error>(&self) -> Result<u64, E> {
+        let resolution =
+            Resolution::try_from(self.res).map_err(de::Error::custom)?;
+        let base_cell =
+            BaseCell::try_from(self.base_cell).map_err(de::Error::custom)?;
+        if self.digits.len() != usize::from(resolution) {
+            return Err(de::Error::invalid_length(
+                self.digits.len(),
+                &"as many digits as the resolution",
+            ));
+        }
+
+        let mut bits = bits::set_resolution(DEFAULT_CELL_INDEX, resolution);
+        bits = h3o_bit::set_base_cell(bits, base_cell.into());
+        for (res, &digit) in
+            Resolution::range(Resolution::One, resolution).zip(&self.digits)
+        {
+            let direction =
+                Direction::try_from(digit).map_err(de::Error::custom)?;
+            bits = bits::set_direction(bits, direction.into(), res);
+        }
+
+        Ok(bits)
+    }
+}
+
+impl sealed::Sealed for CellIndex {
+    fn to_components(self) -> Components {
+        Components::new(self)
+    }
+
+    fn from_components<E: de::Error>(
+        components: Components,
+    ) -> Result<Self, E> {
+        if components.edge.is_some() || components.vertex.is_some() {
+            return Err(de::Error::custom("unexpected edge or vertex"));
+        }
+        let bits = components.to_cell()?;
+        Self::try_from(bits).map_err(de::Error::custom)
+    }
+}
+
+impl sealed::Sealed for DirectedEdgeIndex {
+    fn to_components(self) -> Components {
+        let mut components = Components::new(self.origin());
+        components.edge = Some(self.edge().into());
+        components
+    }
+
+    fn from_components<E: de::Error>(
+        components: Components,
+    ) -> Result<Self, E> {
+        if components.vertex.is_some() {
+            return Err(de::Error::custom("unexpected vertex"));
+        }
+        let edge = components
+            .edge
+            .ok_or_else(|| de::Error::missing_field("edge"))
+            .and_then(|value| {
+                Edge::try_from(value).map_err(de::Error::custom)
+            })?;
+        let bits = components.to_cell()?;
+        let bits = bits::set_mode(bits, IndexMode::DirectedEdge);
+        Self::try_from(bits::set_edge(bits, edge)).map_err(de::Error::custom)
+    }
+}
+
+impl sealed::Sealed for VertexIndex {
+    fn to_components(self) -> Components {
+        let mut components = Components::new(self.owner());
+        components.vertex = Some(self.vertex().into());
+        components
+    }
+
+    fn from_components<E: de::Error>(
+        components: Components,
+    ) -> Result<Self, E> {
+        if components.edge.is_some() {
+            return Err(de::Error::custom("unexpected edge"));
+        }
+        let vertex = components
+            .vertex
+            .ok_or_else(|| de::Error::missing_field("vertex"))
+            .and_then(|value| {
+                Vertex::try_from(value).map_err(de::Error::custom)
+            })?;
+        let bits = components.to_cell()?;
+        let bits = bits::set_mode(bits, IndexMode::Vertex);
+        Self::try_from(bits::set_vertex(bits, vertex))
+            .map_err(de::Error::custom)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Visitor accepting every representation of an index.
+struct IndexVisitor<T>(PhantomData<T>);
+
+impl<T> IndexVisitor<T> {
+    const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<'de, T: Index> Visitor<'de> for IndexVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an H3 index")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        T::try_from(value).map_err(E::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        let value = u64::try_from(value).map_err(|_| {
+            E::invalid_value(de::Unexpected::Signed(value), &self)
+        })?;
+        self.visit_u64(value)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        // Strictly hexadecimal (`from_str_radix` alone would accept a sign).
+        let digits = value.strip_prefix("0x").unwrap_or(value);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(E::invalid_value(de::Unexpected::Str(value), &self));
+        }
+        let value = u64::from_str_radix(digits, 16)
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))?;
+        T::try_from(value).map_err(E::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        map: A,
+    ) -> Result<Self::Value, A::Error> {
+        Components::deserialize(MapAccessDeserializer::new(map))
+            .and_then(T::from_components)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        Components::deserialize(de::value::SeqAccessDeserializer::new(seq))
+            .and_then(T::from_components)
+    }
+}
+
+macro_rules! impl_serde {
+    ($($index:ty),+) => {$(
+        impl Serialize for $index {
+            fn serialize<S: Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    hex::serialize(self, serializer)
+                } else {
+                    integer::serialize(self, serializer)
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $index {
+            fn deserialize<D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_any(IndexVisitor::new())
+                } else {
+                    integer::deserialize(deserializer)
+                }
+            }
+        }
+    )+};
+}
+
+impl_serde!(CellIndex, DirectedEdgeIndex, VertexIndex);
@@ -0,0 +1,677 @@
+//! Topology-preserving simplification of cell set outlines.
+//!
+//! Outlines are first split into arcs: maximal chains of borders separating
+//! the same two regions (or a region and the outside). Each arc is simplified
+//! once and shared by the rings on both sides, which guarantees that adjacent
+//! regions keep matching borders.
+//!
+//! Arcs are simplified independently, using the Douglas-Peucker algorithm, then
+//! points are restored until no simplified segments cross each other and no
+//! simplified segment moves its arc across a point of another one (which would
+//! e.g. push a small hole out of its shell).
+
+use crate::{
+    CellIndex, EdgeRing, LatLng, VertexIndex,
+    collections::Map,
+    coord::Vec3d,
+    error::{DissolutionError, InvalidParameter},
+    math::{abs, mul_add, sqrt},
+    outline::InputOptions,
+};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+/// Distance (in radians, ~6µm) under which a point is considered to be on a
+/// segment.
+///
+/// Vertexes of the grid are often aligned: a simplified segment may run
+/// through a point of another ring, with only rounding errors deciding on
+/// which side.
+const TOUCH_DISTANCE: f64 = 1e-12;
+
+/// Topology-preserving simplification of the outlines of cell sets.
+///
+/// Collapses the stair-steps of the cell boundaries, while guaranteeing that:
+/// - simplified rings never cross each other, nor themselves.
+/// - rings stay on the same side of each other (e.g. holes remain inside
+///   their shell).
+/// - regions simplified together still share their borders exactly.
+///
+/// The tolerance is expressed in radians (i.e. the distance in meters divided
+/// by the radius of the Earth): a point is removed if it's closer than the
+/// tolerance to the simplified outline.
+///
+/// Like [`EdgeRing`], rings are oriented (outer rings run counter-clockwise,
+/// holes clockwise) and not explicitly closed.
+#[derive(Debug, Clone, Copy)]
+pub struct Simplifier {
+    tolerance: f64,
+}
+
+impl Simplifier {
+    /// Initializes a new simplifier, with a tolerance in radians.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidParameter`] if the tolerance is negative or not finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::Simplifier;
+    ///
+    /// assert!(Simplifier::new(1e-6).is_ok());
+    /// assert!(Simplifier::new(f64::NAN).is_err());
+    /// ```
+    pub fn new(tolerance: f64) -> Result<Self, InvalidParameter> {
+        if tolerance.is_finite() && tolerance >= 0. {
+            Ok(Self { tolerance })
+        } else {
+            Err(InvalidParameter::new("invalid simplification tolerance"))
+        }
+    }
+
+    /// Simplifies the outline of a set of cells.
+    ///
+    /// # Errors
+    ///
+    /// All cell indexes must be unique and have the same resolution, otherwise
+    /// [`DissolutionError`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution, Simplifier};
+    ///
+    /// let index = CellIndex::try_from(0x089283470803ffff)?;
+    /// let cells = index.children(Resolution::Twelve);
+    /// // ~10m tolerance.
+    /// let simplifier = Simplifier::new(10. / 6_371_007.180_918_475)?;
+    /// let rings = simplifier.simplify(cells)?;
+    /// assert_eq!(rings.len(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn simplify(
+        &self,
+        cells: impl IntoIterator<Item = CellIndex>,
+    ) -> Result<Vec<Vec<LatLng>>, DissolutionError> {
+        let mut regions =
+            self.simplify_regions(cells.into_iter().map(|cell| (cell, ())))?;
+
+        Ok(regions.remove(&()).unwrap_or_default())
+    }
+
+    /// Simplifies the outlines of several regions together.
+    ///
+    /// Each cell is tagged with the region it belongs to: borders between two
+    /// regions are simplified the same way on both sides. Tagging every cell
+    /// with itself simplifies the boundaries of individual cells.
+    ///
+    /// # Errors
+    ///
+    /// All cell indexes must be unique and have the same resolution, otherwise
+    /// [`DissolutionError`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution, Simplifier};
+    ///
+    /// let index = CellIndex::try_from(0x089283470803ffff)?;
+    /// let cells = index.children(Resolution::Twelve).map(|cell| {
+    ///     let parent = cell.parent(Resolution::Eleven).expect("parent");
+    ///     (cell, parent)
+    /// });
+    /// let simplifier = Simplifier::new(10. / 6_371_007.180_918_475)?;
+    /// let regions = simplifier.simplify_regions(cells)?;
+    /// assert_eq!(regions.len(), 49);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn simplify_regions<K>(
+        &self,
+        cells: impl IntoIterator<Item = (CellIndex, K)>,
+    ) -> Result<BTreeMap<K, Vec<Vec<LatLng>>>, DissolutionError>
+    where
+        K: Ord,
+    {
+        let mut keys = BTreeMap::new();
+        let mut labels = Map::default();
+        let mut regions = Vec::<Vec<CellIndex>>::new();
+        let mut expected = None;
+
+        for (cell, key) in cells {
+            let resolution = cell.resolution();
+            if *expected.get_or_insert(resolution) != resolution {
+                return Err(DissolutionError::UnsupportedResolution);
+            }
+            let next = keys.len();
+            let region = *keys.entry(key).or_insert(next);
+            if region == regions.len() {
+                regions.push(Vec::new());
+            }
+            if labels.insert(cell, region).is_some() {
+                return Err(DissolutionError::DuplicateInput);
+            }
+            regions[region].push(cell);
+        }
+
+        let mut topology = Topology::default();
+        let mut rings = Vec::with_capacity(regions.len());
+        for cells in regions {
+            let graph = InputOptions::new()
+                .disable_duplicate_detection()
+                .edge_graph(cells)?;
+            rings.push(
+                graph
+                    .into_rings()
+                    .iter()
+                    .map(|ring| topology.insert_ring(ring, &labels))
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        topology.simplify(self.tolerance);
+        topology.repair(&rings);
+
+        Ok(keys
+            .into_iter()
+            .map(|(key, region)| {
+                let rings = rings[region]
+                    .iter()
+                    .map(|ring| topology.coordinates(ring))
+                    .collect();
+                (key, rings)
+            })
+            .collect())
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A ring, as a sequence of arcs (with a flag for reversed arcs).
+type RingArcs = Vec<(usize, bool)>;
+
+/// A chain of borders between two junctions.
+///
+/// Closed arcs (i.e. whole rings) repeat their first point at the end.
+#[derive(Debug)]
+struct Arc {
+    coords: Vec<LatLng>,
+    points: Vec<Vec3d>,
+    keep: Vec<bool>,
+    is_closed: bool,
+}
+
+impl Arc {
+    fn new(coords: Vec<LatLng>, is_closed: bool) -> Self {
+        let points = coords.iter().copied().map(Vec3d::from).collect();
+        let keep = vec![true; coords.len()];
+
+        Self {
+            coords,
+            points,
+            keep,
+            is_closed,
+        }
+    }
+
+    /// Simplifies the arc, using the Douglas-Peucker algorithm.
+    fn simplify(&mut self, tolerance: f64) {
+        let last = self.coords.len() - 1;
+        self.keep.fill(false);
+        self.keep[0] = true;
+        self.keep[last] = true;
+
+        // A closed arc needs extra anchors to remain a ring.
+        let anchors = if self.is_closed {
+            vec![0, last / 3, 2 * last / 3, last]
+        } else {
+            vec![0, last]
+        };
+        let mut stack = Vec::new();
+        for pair in anchors.windows(2) {
+            self.keep[pair[0]] = true;
+            stack.push((pair[0], pair[1]));
+        }
+
+        while let Some((first, last)) = stack.pop() {
+            if let Some((idx, distance)) = self.farthest(first, last)
+                && distance > tolerance
+            {
+                self.keep[idx] = true;
+                stack.push((first, idx));
+                stack.push((idx, last));
+            }
+        }
+    }
+
+    /// Restores the farthest point between two kept points, if any.
+    fn split(&mut self, first: usize, last: usize) -> bool {
+        self.farthest(first, last).is_some_and(|(idx, _)| {
+            let is_new = !self.keep[idx];
+            self.keep[idx] = true;
+            is_new
+        })
+    }
+
+    /// Finds the point farthest from the segment between two points.
+    fn farthest(&self, first: usize, last: usize) -> Option<(usize, f64)> {
+        let (start, end) = (self.points[first], self.points[last]);
+        (first + 1..last)
+            .map(|idx| (idx, distance(self.points[idx], start, end)))
+            .max_by(|lhs, rhs| lhs.1.total_cmp(&rhs.1))
+    }
+
+    /// Iterates over the indexes of the kept points.
+    fn kept(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.keep.len()).filter(|&idx| self.keep[idx])
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// The arcs of a set of rings, shared between the rings.
+#[derive(Debug, Default)]
+struct Topology {
+    arcs: Vec<Arc>,
+    /// Arcs, indexed by their first two vertexes in canonical order.
+    index: Map<(VertexIndex, VertexIndex), usize>,
+}
+
+impl Topology {
+    /// Splits a ring into arcs and registers them, if not already known.
+    fn insert_ring(
+        &mut self,
+        ring: &EdgeRing,
+        labels: &Map<CellIndex, usize>,
+    ) -> RingArcs {
+        let (edges, vertexes) = (ring.edges(), ring.vertexes());
+        let count = edges.len();
+        // Region on the other side of each edge, if any.
+        let sides = edges
+            .iter()
+            .map(|edge| labels.get(&edge.destination()).copied())
+            .collect::<Vec<_>>();
+        let junctions = (0..count)
+            .filter(|&idx| sides[(idx + count - 1) % count] != sides[idx])
+            .collect::<Vec<_>>();
+
+        if junctions.is_empty() {
+            // The canonical form starts at the smallest vertex, and goes
+            // toward its smallest neighbor.
+            let start = (0..count)
+                .min_by_key(|&idx| vertexes[idx])
+                .expect("non-empty ring");
+            let next = vertexes[(start + 1) % count];
+            let prev = vertexes[(start + count - 1) % count];
+            let is_reversed = prev < next;
+            let key = (vertexes[start], if is_reversed { prev } else { next });
+
+            let id = self.get_or_insert(key, || {
+                let mut coords = coordinates(ring, start, count);
+                coords.push(coords[0]);
+                if is_reversed {
+                    coords.reverse();
+                }
+                Arc::new(coords, true)
+            });
+            return vec![(id, is_reversed)];
+        }
+
+        let mut arcs = Vec::with_capacity(junctions.len());
+        for (i, &first) in junctions.iter().enumerate() {
+            let last = junctions[(i + 1) % junctions.len()];
+            let len = (last + count - first - 1) % count + 1;
+            let (from, to) = (vertexes[first], vertexes[last]);
+            // The canonical form starts at the smallest end.
+            let is_reversed = to < from;
+            let key = if is_reversed {
+                (to, vertexes[(first + len - 1) % count])
+            } else {
+                (from, vertexes[(first + 1) % count])
+            };
+
+            let id = self.get_or_insert(key, || {
+                let mut coords = coordinates(ring, first, len);
+                coords.push(LatLng::from(to));
+                if is_reversed {
+                    coords.reverse();
+                }
+                Arc::new(coords, false)
+            });
+            arcs.push((id, is_reversed));
+        }
+
+        arcs
+    }
+
+    fn get_or_insert(
+        &mut self,
+        key: (VertexIndex, VertexIndex),
+        arc: impl FnOnce() -> Arc,
+    ) -> usize {
+        if let Some(&id) = self.index.get(&key) {
+            return id;
+        }
+        let id = self.arcs.len();
+        self.arcs.push(arc());
+        self.index.insert(key, id);
+        id
+    }
+
+    /// Simplifies every arc, independently.
+    fn simplify(&mut self, tolerance: f64) {
+        for arc in &mut self.arcs {
+            arc.simplify(tolerance);
+        }
+    }
+
+    /// Restores points until the rings are valid, don't cross each other and
+    /// keep their relative positions.
+    fn repair(&mut self, rings: &[Vec<RingArcs>]) {
+        let mut segments = (0..self.arcs.len())
+            .map(|id| self.segments(id))
+            .collect::<Vec<_>>();
+
+        loop {
+            let mut is_split = vec![false; self.arcs.len()];
+
+            // Every ring needs at least 3 points.
+            for ring in rings.iter().flatten() {
+                let count = ring
+                    .iter()
+                    .map(|&(id, _)| segments[id].len())
+                    .sum::<usize>();
+                if count < 3 {
+                    let segment = ring
+                        .iter()
+                        .flat_map(|&(id, _)| &segments[id])
+                        .max_by_key(|segment| segment.last - segment.first)
+                        .expect("non-empty ring");
+                    is_split[segment.arc] |= self.arcs[segment.arc]
+                        .split(segment.first, segment.last);
+                }
+            }
+
+            let all = segments.iter().flatten().copied().collect::<Vec<_>>();
+            let conflicting = conflicts(&all)
+                .into_iter()
+                .flat_map(|(lhs, rhs)| [lhs, rhs])
+                .chain(enclosures(&all, &self.arcs))
+                .collect::<Vec<_>>();
+            for idx in conflicting {
+                let segment = &all[idx];
+                is_split[segment.arc] |=
+                    self.arcs[segment.arc].split(segment.first, segment.last);
+            }
+
+            // Nothing left to restore.
+            if !is_split.contains(&true) {
+                break;
+            }
+            for id in (0..self.arcs.len()).filter(|&id| is_split[id]) {
+                segments[id] = self.segments(id);
+            }
+        }
+    }
+
+    /// Returns the simplified segments of an arc.
+    fn segments(&self, id: usize) -> Vec<Segment> {
+        let arc = &self.arcs[id];
+        let kept = arc.kept().collect::<Vec<_>>();
+        kept.windows(2)
+            .map(|pair| Segment::new(id, pair[0], pair[1], &arc.points))
+            .collect()
+    }
+
+    /// Returns the simplified coordinates of a ring.
+    fn coordinates(&self, ring: &RingArcs) -> Vec<LatLng> {
+        let mut coords = Vec::new();
+        for &(id, is_reversed) in ring {
+            let arc = &self.arcs[id];
+            let mut kept =
+                arc.kept().map(|idx| arc.coords[idx]).collect::<Vec<_>>();
+            if is_reversed {
+                kept.reverse();
+            }
+            // The last point is the first one of the next arc.
+            kept.pop();
+            coords.extend(kept);
+        }
+        coords
+    }
+}
+
+/// Returns the coordinates of `len` edges of a ring, starting at `first`.
+///
+/// The first point is snapped on the topological vertex, so that junctions
+/// have the same coordinates in every arc.
+fn coordinates(ring: &EdgeRing, first: usize, len: usize) -> Vec<LatLng> {
+    let (edges, vertexes) = (ring.edges(), ring.vertexes());
+    let mut coords = Vec::with_capacity(len + 1);
+    for i in 0..len {
+        let boundary = edges[(first + i) % edges.len()].boundary();
+        coords.extend(boundary.iter().take(boundary.len() - 1));
+    }
+    coords[0] = LatLng::from(vertexes[first]);
+    coords
+}
+
+// -----------------------------------------------------------------------------
+
+/// A simplified segment, between two kept points of an arc.
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    arc: usize,
+    first: usize,
+    last: usize,
+    start: Vec3d,
+    end: Vec3d,
+    /// Bounding box, padded to account for the bulge of the great arc.
+    min: Vec3d,
+    max: Vec3d,
+}
+
+impl Segment {
+    fn new(arc: usize, first: usize, last: usize, points: &[Vec3d]) -> Self {
+        let (start, end) = (points[first], points[last]);
+        let pad = start.distance(&end) / 8. + f64::EPSILON;
+
+        Self {
+            arc,
+            first,
+            last,
+            start,
+            end,
+            min: Vec3d::new(
+                start.x.min(end.x) - pad,
+                start.y.min(end.y) - pad,
+                start.z.min(end.z) - pad,
+            ),
+            max: Vec3d::new(
+                start.x.max(end.x) + pad,
+                start.y.max(end.y) + pad,
+                start.z.max(end.z) + pad,
+            ),
+        }
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+            && self.min.z <= other.max.z
+            && other.min.z <= self.max.z
+    }
+
+    /// Returns true if both segments can't coexist in a valid outline.
+    fn conflicts_with(&self, other: &Self) -> bool {
+        let shared = [self.start, self.end]
+            .into_iter()
+            .filter(|point| *point == other.start || *point == other.end)
+            .count();
+
+        match shared {
+            // Consecutive segments.
+            1 => false,
+            // Overlapping segments.
+            2 => true,
+            _ => self.crosses(other),
+        }
+    }
+
+    /// Returns true if the point is on the great circle of the segment.
+    fn touches(&self, point: Vec3d) -> bool {
+        let normal = self.start.cross(&self.end);
+        abs(normal.dot(&point)) < TOUCH_DISTANCE * sqrt(normal.dot(&normal))
+    }
+
+    /// Returns true if the great arcs cross, or touch, each other.
+    fn crosses(&self, other: &Self) -> bool {
+        let normal = self.start.cross(&self.end);
+        let (lhs, rhs) = (normal.dot(&other.start), normal.dot(&other.end));
+        if lhs * rhs > 0. {
+            return false;
+        }
+        let normal = other.start.cross(&other.end);
+        let (lhs, rhs) = (normal.dot(&self.start), normal.dot(&self.end));
+        lhs * rhs <= 0.
+    }
+}
+
+/// Finds the pairs of conflicting segments, using a sweep along the X axis.
+fn conflicts(segments: &[Segment]) -> Vec<(usize, usize)> {
+    let mut order = (0..segments.len()).collect::<Vec<_>>();
+    order.sort_unstable_by(|&lhs, &rhs| {
+        segments[lhs].min.x.total_cmp(&segments[rhs].min.x)
+    });
+
+    let mut conflicts = Vec::new();
+    let mut active = Vec::<usize>::new();
+    for idx in order {
+        let segment = &segments[idx];
+        active.retain(|&other| segments[other].max.x >= segment.min.x);
+        for &other in &active {
+            if segment.overlaps(&segments[other])
+                && segment.conflicts_with(&segments[other])
+            {
+                conflicts.push((other, idx));
+            }
+        }
+        active.push(idx);
+    }
+
+    conflicts
+}
+
+/// Finds the segments that move their arc across, or onto, a kept point.
+///
+/// Such a point lies in the area between the simplified segment and the
+/// original points it replaces: it ends up on the other side of the arc even
+/// though no segments cross (e.g. a small hole left out of its shell).
+fn enclosures(segments: &[Segment], arcs: &[Arc]) -> Vec<usize> {
+    let mut points = arcs
+        .iter()
+        .enumerate()
+        .flat_map(|(id, arc)| {
+            arc.kept().map(move |idx| (arc.points[idx], id, idx))
+        })
+        .collect::<Vec<_>>();
+    points.sort_unstable_by(|lhs, rhs| lhs.0.x.total_cmp(&rhs.0.x));
+
+    (0..segments.len())
+        .filter(|&idx| {
+            let segment = &segments[idx];
+            // Nothing to move across without removed points.
+            if segment.last - segment.first < 2 {
+                return false;
+            }
+            let chain = &arcs[segment.arc].points[segment.first..=segment.last];
+            let (min, max) = chain.iter().fold(
+                (segment.min, segment.max),
+                |(min, max), point| {
+                    (
+                        Vec3d::new(
+                            min.x.min(point.x),
+                            min.y.min(point.y),
+                            min.z.min(point.z),
+                        ),
+                        Vec3d::new(
+                            max.x.max(point.x),
+                            max.y.max(point.y),
+                            max.z.max(point.z),
+                        ),
+                    )
+                },
+            );
+
+            let start = points.partition_point(|(point, _, _)| point.x < min.x);
+            points[start..]
+                .iter()
+                .take_while(|(point, _, _)| point.x <= max.x)
+                .any(|&(point, arc, idx)| {
+                    (arc != segment.arc
+                        || idx < segment.first
+                        || idx > segment.last)
+                        && (min.y..=max.y).contains(&point.y)
+                        && (min.z..=max.z).contains(&point.z)
+                        && point != segment.start
+                        && point != segment.end
+                        && (segment.touches(point) || encloses(chain, point))
+                })
+        })
+        .collect()
+}
+
+/// Returns true if the point is inside the polygon formed by a chain of
+/// points, closed by a straight line.
+///
+/// The test is performed in the plane tangent to the point, which is accurate
+/// enough for polygons this small.
+fn encloses(chain: &[Vec3d], point: Vec3d) -> bool {
+    // Any non-null vector orthogonal to the point will do: the basis doesn't
+    // need to be normalized to count crossings.
+    let axis = if abs(point.x) < abs(point.y).max(abs(point.z)) {
+        Vec3d::new(1., 0., 0.)
+    } else {
+        Vec3d::new(0., 1., 0.)
+    };
+    let u = point.cross(&axis);
+    let v = point.cross(&u);
+    let project = |p: &Vec3d| (u.dot(p), v.dot(p));
+
+    let mut is_inside = false;
+    let mut prev = project(&chain[chain.len() - 1]);
+    for current in chain.iter().map(project) {
+        if (prev.1 > 0.) != (current.1 > 0.) {
+            let x =
+                prev.0 - prev.1 * (current.0 - prev.0) / (current.1 - prev.1);
+            if x > 0. {
+                is_inside = !is_inside;
+            }
+        }
+        prev = current;
+    }
+
+    is_inside
+}
+
+/// Computes the distance between a point and a segment (as a chord, which is
+/// a good approximation of the great arc at this scale).
+fn distance(point: Vec3d, start: Vec3d, end: Vec3d) -> f64 {
+    let direction =
+        Vec3d::new(end.x - start.x, end.y - start.y, end.z - start.z);
+    let offset =
+        Vec3d::new(point.x - start.x, point.y - start.y, point.z - start.z);
+    let length = direction.dot(&direction);
+    let t = if length > 0. {
+        (offset.dot(&direction) / length).clamp(0., 1.)
+    } else {
+        0.
+    };
+    let projection = Vec3d::new(
+        mul_add(t, direction.x, start.x),
+        mul_add(t, direction.y, start.y),
+        mul_add(t, direction.z, start.z),
+    );
+
+    sqrt(point.distance(&projection))
+}
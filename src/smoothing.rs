@@ -0,0 +1,368 @@
+//! Kernel density estimation and smoothing over sparse cell sets.
+//!
+//! Unlike [`HexRaster::convolve`](crate::HexRaster::convolve), which works on
+//! a dense window of local `IJ` coordinates, these operators work on
+//! arbitrary collections of cells through [`CellIndex::grid_disk_distances`],
+//! and are thus safe to use across icosahedron faces and around pentagons.
+
+use crate::{
+    CellIndex, LatLng, Resolution,
+    collections::Map,
+    error::{InvalidParameter, ResolutionMismatch},
+    math::{exp, mul_add},
+};
+use alloc::vec::Vec;
+
+/// Weight profile of a smoothing kernel.
+///
+/// Bandwidths are expressed in the unit of the [`KernelDistance`] in use:
+/// grid distance (i.e. number of rings) or meters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum SmoothingKernel {
+    /// Same weight for every cell within the bandwidth.
+    Uniform {
+        /// Maximum distance of the weighted cells.
+        bandwidth: f64,
+    },
+    /// Weight decreasing linearly to zero at the bandwidth.
+    Triangular {
+        /// Distance at which the weight reaches zero.
+        bandwidth: f64,
+    },
+    /// Weight decreasing quadratically to zero at the bandwidth.
+    Epanechnikov {
+        /// Distance at which the weight reaches zero.
+        bandwidth: f64,
+    },
+    /// Gaussian weight.
+    Gaussian {
+        /// Standard deviation of the Gaussian.
+        sigma: f64,
+    },
+}
+
+impl SmoothingKernel {
+    /// Checks that the bandwidth is positive and finite.
+    fn validate(self) -> Result<(), InvalidParameter> {
+        let (Self::Uniform { bandwidth }
+        | Self::Triangular { bandwidth }
+        | Self::Epanechnikov { bandwidth }
+        | Self::Gaussian { sigma: bandwidth }) = self;
+        if bandwidth.is_finite() && bandwidth > 0. {
+            Ok(())
+        } else {
+            Err(InvalidParameter::new("invalid kernel bandwidth"))
+        }
+    }
+
+    /// Returns the (unnormalized) weight at the given distance.
+    ///
+    /// The weight is 1 at the center, for every valid kernel.
+    fn weight(self, distance: f64) -> f64 {
+        match self {
+            Self::Uniform { bandwidth } => {
+                if distance <= bandwidth {
+                    1.
+                } else {
+                    0.
+                }
+            }
+            Self::Triangular { bandwidth } => {
+                (1. - distance / bandwidth).max(0.)
+            }
+            Self::Epanechnikov { bandwidth } => {
+                let ratio = distance / bandwidth;
+                mul_add(ratio, -ratio, 1.).max(0.)
+            }
+            Self::Gaussian { sigma } => {
+                exp(-distance * distance / (2. * sigma * sigma))
+            }
+        }
+    }
+}
+
+/// Distance used to weight the neighbors of a cell.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum KernelDistance {
+    /// Grid distance, i.e. the number of rings between the cells.
+    #[default]
+    Grid,
+    /// Great circle distance between the cell centers, in meters.
+    Metric,
+}
+
+// -----------------------------------------------------------------------------
+
+/// A kernel smoother, spreading values over the neighboring cells.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{CellIndex, KernelSmootherBuilder, SmoothingKernel};
+///
+/// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+/// let smoother = KernelSmootherBuilder::new(
+///     SmoothingKernel::Gaussian { sigma: 1. },
+///     2,
+/// )
+/// .build()?;
+///
+/// let density = smoother.density([(cell, 100.)])?;
+/// assert_eq!(density.len(), 19);
+/// let total = density.iter().map(|(_, value)| value).sum::<f64>();
+/// assert!((total - 100.).abs() < 1e-9);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct KernelSmoother {
+    kernel: SmoothingKernel,
+    radius: u32,
+    distance: KernelDistance,
+    output_resolution: Option<Resolution>,
+}
+
+impl KernelSmoother {
+    /// Computes the kernel density of the input values.
+    ///
+    /// Each value is spread over the cells within the kernel radius, with
+    /// weights normalized over the cells actually reached, so that the total
+    /// mass is preserved (even around pentagons, which have less neighbors).
+    /// Values of duplicate cells are added up.
+    ///
+    /// Returns the density of each reached cell, sorted by cell. If an output
+    /// resolution is set, the densities of the cells are added up into their
+    /// ancestor at that resolution.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if the input cells don't share the same
+    /// resolution, or if it's coarser than the output resolution.
+    pub fn density(
+        &self,
+        values: impl IntoIterator<Item = (CellIndex, f64)>,
+    ) -> Result<Vec<(CellIndex, f64)>, ResolutionMismatch> {
+        let mut density = Map::<CellIndex, f64>::default();
+        let mut resolution = None;
+
+        for (cell, value) in values {
+            self.check_resolution(&mut resolution, cell)?;
+            let weights = self.weights(cell);
+            // Never zero, as the cell itself is always weighted.
+            let total = weights.iter().map(|(_, weight)| weight).sum::<f64>();
+            for (neighbor, weight) in weights {
+                *density.entry(neighbor).or_default() += value * weight / total;
+            }
+        }
+
+        Ok(self.output(density, resolution, |sum, value, _| sum + value))
+    }
+
+    /// Smooths the input values, by replacing the value of each cell with
+    /// the weighted average of the values of its neighbors (itself included).
+    ///
+    /// Only the input cells are considered: missing cells are neither used
+    /// nor produced, which makes the result independent of the number of
+    /// neighbors of a cell (e.g. around pentagons). Values of duplicate cells
+    /// are added up.
+    ///
+    /// Returns the smoothed value of each input cell, sorted by cell. If an
+    /// output resolution is set, the values of the cells are averaged into
+    /// their ancestor at that resolution.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolutionMismatch`] if the input cells don't share the same
+    /// resolution, or if it's coarser than the output resolution.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{CellIndex, KernelSmootherBuilder, SmoothingKernel};
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let values = cell.grid_disk::<Vec<_>>(1).into_iter()
+    ///     .map(|neighbor| (neighbor, if neighbor == cell { 7. } else { 0. }));
+    /// let smoother = KernelSmootherBuilder::new(
+    ///     SmoothingKernel::Uniform { bandwidth: 1. },
+    ///     1,
+    /// )
+    /// .build()?;
+    ///
+    /// let smoothed = smoother.smooth(values)?;
+    /// let center = smoothed.iter().find(|(c, _)| *c == cell).expect("center");
+    /// assert!((center.1 - 1.).abs() < 1e-9);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn smooth(
+        &self,
+        values: impl IntoIterator<Item = (CellIndex, f64)>,
+    ) -> Result<Vec<(CellIndex, f64)>, ResolutionMismatch> {
+        let mut input = Map::<CellIndex, f64>::default();
+        let mut resolution = None;
+        for (cell, value) in values {
+            self.check_resolution(&mut resolution, cell)?;
+            *input.entry(cell).or_default() += value;
+        }
+
+        let mut smoothed = Map::<CellIndex, f64>::default();
+        for &cell in input.keys() {
+            let (mut sum, mut total) = (0., 0.);
+            for (neighbor, weight) in self.weights(cell) {
+                if let Some(value) = input.get(&neighbor) {
+                    sum += value * weight;
+                    total += weight;
+                }
+            }
+            // Never zero, as the cell itself is always weighted.
+            smoothed.insert(cell, sum / total);
+        }
+
+        // Running average of the children values.
+        Ok(self.output(smoothed, resolution, |mean, value, count| {
+            #[expect(clippy::cast_precision_loss, reason = "small counts")]
+            let count = count as f64;
+            mean + (value - mean) / count
+        }))
+    }
+
+    /// Checks that every input cell has the same resolution, not coarser
+    /// than the output resolution.
+    fn check_resolution(
+        &self,
+        resolution: &mut Option<Resolution>,
+        cell: CellIndex,
+    ) -> Result<(), ResolutionMismatch> {
+        match *resolution {
+            Some(resolution) if resolution != cell.resolution() => {
+                Err(ResolutionMismatch)
+            }
+            Some(_) => Ok(()),
+            None => {
+                if self
+                    .output_resolution
+                    .is_some_and(|output| output > cell.resolution())
+                {
+                    return Err(ResolutionMismatch);
+                }
+                *resolution = Some(cell.resolution());
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the cells within the kernel radius and their weight.
+    fn weights(&self, cell: CellIndex) -> Vec<(CellIndex, f64)> {
+        let center = LatLng::from(cell);
+
+        cell.grid_disk_distances::<Vec<_>>(self.radius)
+            .into_iter()
+            .map(|(neighbor, distance)| {
+                let distance = match self.distance {
+                    KernelDistance::Grid => f64::from(distance),
+                    KernelDistance::Metric => {
+                        center.distance_m(LatLng::from(neighbor))
+                    }
+                };
+                (neighbor, self.kernel.weight(distance))
+            })
+            .filter(|&(_, weight)| weight > 0.)
+            .collect()
+    }
+
+    /// Aggregates the values at the output resolution, if any, and sorts
+    /// them.
+    ///
+    /// `merge` is called with the current aggregate, the new value and the
+    /// number of values merged so far (new value included).
+    fn output(
+        &self,
+        values: Map<CellIndex, f64>,
+        resolution: Option<Resolution>,
+        merge: impl Fn(f64, f64, usize) -> f64,
+    ) -> Vec<(CellIndex, f64)> {
+        let mut result = match (self.output_resolution, resolution) {
+            (Some(output), Some(input)) if output != input => {
+                let mut aggregated = Map::<CellIndex, (f64, usize)>::default();
+                for (cell, value) in values {
+                    let parent = cell.parent(output).expect("coarser");
+                    let entry = aggregated.entry(parent).or_insert((0., 0));
+                    entry.1 += 1;
+                    entry.0 = merge(entry.0, value, entry.1);
+                }
+                aggregated
+                    .into_iter()
+                    .map(|(cell, (value, _))| (cell, value))
+                    .collect::<Vec<_>>()
+            }
+            _ => values.into_iter().collect::<Vec<_>>(),
+        };
+        result.sort_unstable_by_key(|&(cell, _)| cell);
+
+        result
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A builder to configure a kernel smoother.
+#[derive(Debug, Clone, Copy)]
+pub struct KernelSmootherBuilder {
+    kernel: SmoothingKernel,
+    radius: u32,
+    distance: KernelDistance,
+    output_resolution: Option<Resolution>,
+}
+
+impl KernelSmootherBuilder {
+    /// Initializes a new kernel smoother builder.
+    ///
+    /// `radius` is the number of rings, around each cell, covered by the
+    /// kernel.
+    #[must_use]
+    pub const fn new(kernel: SmoothingKernel, radius: u32) -> Self {
+        Self {
+            kernel,
+            radius,
+            distance: KernelDistance::Grid,
+            output_resolution: None,
+        }
+    }
+
+    /// Set the distance used to weight the neighbors.
+    ///
+    /// Defaults to [`KernelDistance::Grid`].
+    #[must_use]
+    pub const fn distance(mut self, distance: KernelDistance) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    /// Set the resolution of the output cells.
+    ///
+    /// Must be coarser than, or equal to, the resolution of the input cells.
+    /// Defaults to the resolution of the input cells.
+    #[must_use]
+    pub const fn output_resolution(mut self, resolution: Resolution) -> Self {
+        self.output_resolution = Some(resolution);
+        self
+    }
+
+    /// Builds the kernel smoother.
+    ///
+    /// # Errors
+    ///
+    /// [`InvalidParameter`] if the kernel bandwidth (or sigma) isn't
+    /// positive and finite.
+    pub fn build(self) -> Result<KernelSmoother, InvalidParameter> {
+        self.kernel.validate()?;
+
+        Ok(KernelSmoother {
+            kernel: self.kernel,
+            radius: self.radius,
+            distance: self.distance,
+            output_resolution: self.output_resolution,
+        })
+    }
+}
@@ -0,0 +1,289 @@
+//! Graph-level topology of cell sets.
+//!
+//! Cells are seen as the nodes of a graph where edges connect neighboring
+//! cells (i.e. 1-connectivity). This is enough to split a set into connected
+//! regions and find the enclosed holes, without building any polygon.
+
+use crate::{
+    CellIndex,
+    collections::{Map, Set},
+    error::ResolutionMismatch,
+    grid::neighbors,
+};
+use alloc::{vec, vec::Vec};
+use core::mem;
+
+/// A connected region of a cell set.
+///
+/// Holes are computed against the component alone: a hole may thus contain
+/// cells (or whole components) of the input set, the same way the interior
+/// ring of a polygon may surround islands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellComponent {
+    cells: Vec<CellIndex>,
+    outer_boundary: Vec<CellIndex>,
+    inner_boundary: Vec<CellIndex>,
+    holes: Vec<Vec<CellIndex>>,
+}
+
+impl CellComponent {
+    /// Returns the cells of the component, sorted.
+    #[must_use]
+    pub fn cells(&self) -> &[CellIndex] {
+        &self.cells
+    }
+
+    /// Returns the cells of the component that touch the outside, sorted.
+    #[must_use]
+    pub fn outer_boundary(&self) -> &[CellIndex] {
+        &self.outer_boundary
+    }
+
+    /// Returns the cells of the component that touch a hole, sorted.
+    #[must_use]
+    pub fn inner_boundary(&self) -> &[CellIndex] {
+        &self.inner_boundary
+    }
+
+    /// Returns the cells of each hole enclosed by the component, sorted.
+    #[must_use]
+    pub fn holes(&self) -> &[Vec<CellIndex>] {
+        &self.holes
+    }
+
+    /// Returns true if the component doesn't enclose any hole.
+    #[must_use]
+    pub const fn is_simply_connected(&self) -> bool {
+        self.holes.is_empty()
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Splits a set of cells into its connected components.
+///
+/// Components are sorted by their smallest cell.
+pub fn connected_components(
+    cells: impl IntoIterator<Item = CellIndex>,
+) -> Result<Vec<CellComponent>, ResolutionMismatch> {
+    let mut cells = cells.into_iter().collect::<Vec<_>>();
+    if let Some(first) = cells.first() {
+        let resolution = first.resolution();
+        if cells.iter().any(|cell| cell.resolution() != resolution) {
+            return Err(ResolutionMismatch);
+        }
+    }
+    cells.sort_unstable();
+    cells.dedup();
+
+    let mut remaining = cells.iter().copied().collect::<Set<_>>();
+    let mut scratchpad = [0; 7];
+    let mut components = Vec::new();
+
+    // Iterating on sorted cells gives us sorted components for free.
+    for &seed in &cells {
+        if !remaining.remove(&seed) {
+            continue;
+        }
+        let mut component = vec![seed];
+        let mut stack = vec![seed];
+        while let Some(cell) = stack.pop() {
+            let count = neighbors(cell, &mut scratchpad);
+            for neighbor in &scratchpad[0..count] {
+                let neighbor = CellIndex::new_unchecked(*neighbor);
+                if remaining.remove(&neighbor) {
+                    component.push(neighbor);
+                    stack.push(neighbor);
+                }
+            }
+        }
+        components.push(analyze(component));
+    }
+
+    Ok(components)
+}
+
+/// Computes the boundaries and holes of a connected set of cells.
+fn analyze(mut cells: Vec<CellIndex>) -> CellComponent {
+    cells.sort_unstable();
+    let members = cells.iter().copied().collect::<Set<_>>();
+    let mut scratchpad = [0; 7];
+
+    // Find the cells just outside the component.
+    let mut frontier = Vec::new();
+    let mut seen = Set::default();
+    for &cell in &cells {
+        let count = neighbors(cell, &mut scratchpad);
+        for neighbor in &scratchpad[0..count] {
+            let neighbor = CellIndex::new_unchecked(*neighbor);
+            if !members.contains(&neighbor) && seen.insert(neighbor) {
+                frontier.push(neighbor);
+            }
+        }
+    }
+
+    let flood = Flood::new(&members, frontier);
+    let (outside, holes) = flood.classify();
+
+    let mut outer_boundary = Vec::new();
+    let mut inner_boundary = Vec::new();
+    for &cell in &cells {
+        let (mut is_outer, mut is_inner) = (false, false);
+        let count = neighbors(cell, &mut scratchpad);
+        for neighbor in &scratchpad[0..count] {
+            let neighbor = CellIndex::new_unchecked(*neighbor);
+            if members.contains(&neighbor) {
+                continue;
+            }
+            if outside.contains(&neighbor) {
+                is_outer = true;
+            } else {
+                is_inner = true;
+            }
+        }
+        if is_outer {
+            outer_boundary.push(cell);
+        }
+        if is_inner {
+            inner_boundary.push(cell);
+        }
+    }
+
+    CellComponent {
+        cells,
+        outer_boundary,
+        inner_boundary,
+        holes,
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Concurrent flood fills of the complement of a connected set of cells.
+///
+/// Every cell of the frontier (i.e. the cells touching the set from the
+/// outside) starts its own region. Regions are grown one layer at a time, in a
+/// round-robin fashion, and merged when they meet.
+///
+/// An enclosed region (a hole) eventually stops growing whereas the outside
+/// keeps going: the traversal stops as soon as only one region is still
+/// growing, thus exploring the outside only as far as the deepest hole.
+struct Flood<'a> {
+    /// The cells of the set, acting as walls.
+    walls: &'a Set<CellIndex>,
+    /// Region of every visited cell.
+    owner: Map<CellIndex, usize>,
+    /// Union-find parent of each region.
+    parent: Vec<usize>,
+    /// Cells waiting to be expanded, for every region.
+    queues: Vec<Vec<CellIndex>>,
+    /// Visited cells, for every region.
+    cells: Vec<Vec<CellIndex>>,
+}
+
+impl<'a> Flood<'a> {
+    fn new(walls: &'a Set<CellIndex>, frontier: Vec<CellIndex>) -> Self {
+        let owner = frontier
+            .iter()
+            .enumerate()
+            .map(|(region, &cell)| (cell, region))
+            .collect();
+        let parent = (0..frontier.len()).collect();
+        let queues = frontier.iter().map(|&cell| vec![cell]).collect();
+        let cells = frontier.into_iter().map(|cell| vec![cell]).collect();
+
+        Self {
+            walls,
+            owner,
+            parent,
+            queues,
+            cells,
+        }
+    }
+
+    /// Returns the cells of the outside and the cells of each hole.
+    fn classify(mut self) -> (Set<CellIndex>, Vec<Vec<CellIndex>>) {
+        let mut scratchpad = [0; 7];
+        let mut active = self.active();
+
+        while active.len() > 1 {
+            for region in active {
+                // Skip regions merged during this round.
+                if self.find(region) != region {
+                    continue;
+                }
+                let queue = mem::take(&mut self.queues[region]);
+                let mut next = Vec::with_capacity(queue.len());
+                for cell in queue {
+                    let count = neighbors(cell, &mut scratchpad);
+                    for neighbor in &scratchpad[0..count] {
+                        let neighbor = CellIndex::new_unchecked(*neighbor);
+                        if self.walls.contains(&neighbor) {
+                            continue;
+                        }
+                        if let Some(&other) = self.owner.get(&neighbor) {
+                            let other = self.find(other);
+                            if other != region {
+                                self.parent[other] = region;
+                                next.append(&mut self.queues[other]);
+                                let mut cells =
+                                    mem::take(&mut self.cells[other]);
+                                self.cells[region].append(&mut cells);
+                            }
+                        } else {
+                            self.owner.insert(neighbor, region);
+                            self.cells[region].push(neighbor);
+                            next.push(neighbor);
+                        }
+                    }
+                }
+                self.queues[region] = next;
+            }
+            active = self.active();
+        }
+
+        // The region still growing is the outside. If every region stopped,
+        // we're on a sphere after all: the largest one is the outside.
+        let outside = active.first().copied().or_else(|| {
+            self.roots().max_by_key(|&region| self.cells[region].len())
+        });
+        let mut holes = Vec::new();
+        let mut outside_cells = Set::default();
+        for region in 0..self.parent.len() {
+            if self.parent[region] != region {
+                continue;
+            }
+            let mut cells = mem::take(&mut self.cells[region]);
+            if Some(region) == outside {
+                outside_cells.extend(cells);
+            } else {
+                cells.sort_unstable();
+                holes.push(cells);
+            }
+        }
+        holes.sort_unstable();
+
+        (outside_cells, holes)
+    }
+
+    /// Returns the regions that are still growing.
+    fn active(&self) -> Vec<usize> {
+        self.roots()
+            .filter(|&region| !self.queues[region].is_empty())
+            .collect()
+    }
+
+    /// Iterates over the regions (i.e. the roots of the union-find).
+    fn roots(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.parent.len()).filter(|&region| self.parent[region] == region)
+    }
+
+    /// Finds the root of a region, with path halving.
+    fn find(&mut self, mut region: usize) -> usize {
+        while self.parent[region] != region {
+            self.parent[region] = self.parent[self.parent[region]];
+            region = self.parent[region];
+        }
+        region
+    }
+}
@@ -0,0 +1,395 @@
+//! Binning of trajectories into cell visits.
+//!
+//! Timestamps are plain integers, in any unit (e.g. milliseconds since the
+//! UNIX epoch), as long as they don't decrease along the trajectory.
+
+use crate::{CellIndex, LatLng, Resolution, error::UnorderedTimestamp};
+use alloc::vec::Vec;
+use core::num::NonZeroU64;
+
+/// Default maximum number of cells inferred between two consecutive points.
+const DEFAULT_MAX_GAP: u32 = 1_000;
+
+/// A trajectory, as a run-length encoded sequence of cell visits.
+///
+/// Consecutive points in the same cell are merged into a single visit, and
+/// transitions between cells are assumed to happen halfway between the cells
+/// centers: the exit time of a visit is thus the entry time of the next one.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{LatLng, Resolution, TrajectoryBuilder};
+///
+/// let mut trajectory = TrajectoryBuilder::new(Resolution::Nine).build();
+/// trajectory.push_batch([
+///     (LatLng::new(48.8566, 2.3522)?, 0),
+///     (LatLng::new(48.8567, 2.3523)?, 30),
+///     (LatLng::new(48.8600, 2.3600)?, 90),
+/// ])?;
+///
+/// let visits = trajectory.visits();
+/// assert_eq!(visits[0].entry, 0);
+/// assert_eq!(visits.last().map(|visit| visit.exit), Some(90));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Trajectory {
+    resolution: Resolution,
+    fill_gaps: bool,
+    max_gap: u32,
+    visits: Vec<CellVisit>,
+}
+
+impl Trajectory {
+    /// Adds a timestamped point at the end of the trajectory.
+    ///
+    /// # Errors
+    ///
+    /// [`UnorderedTimestamp`] if the timestamp is lower than the previous one.
+    pub fn push(
+        &mut self,
+        point: LatLng,
+        time: u64,
+    ) -> Result<(), UnorderedTimestamp> {
+        let cell = point.to_cell(self.resolution);
+        let Some(last) = self.visits.last_mut() else {
+            self.visits.push(CellVisit::new(cell, time, time, 1));
+            return Ok(());
+        };
+        if time < last.exit {
+            return Err(UnorderedTimestamp);
+        }
+        if last.cell == cell {
+            last.exit = time;
+            last.point_count += 1;
+            return Ok(());
+        }
+
+        let path = if self.fill_gaps {
+            path(last.cell, cell, self.max_gap)
+        } else {
+            Vec::new()
+        };
+        // Crossing between the j-th and (j+1)-th cells of the path.
+        let (start, steps) = (last.exit, path.len() as u64 + 1);
+        let crossing = |j: u64| interpolate(start, time, 2 * j + 1, 2 * steps);
+
+        last.exit = crossing(0);
+        for (j, &cell) in (1..).zip(&path) {
+            let visit = CellVisit::new(cell, crossing(j - 1), crossing(j), 0);
+            self.visits.push(visit);
+        }
+        self.visits
+            .push(CellVisit::new(cell, crossing(steps - 1), time, 1));
+
+        Ok(())
+    }
+
+    /// Adds a batch of timestamped points at the end of the trajectory.
+    ///
+    /// # Errors
+    ///
+    /// [`UnorderedTimestamp`] if a timestamp is lower than the previous one.
+    /// Points before the unordered one are kept.
+    pub fn push_batch(
+        &mut self,
+        points: impl IntoIterator<Item = (LatLng, u64)>,
+    ) -> Result<(), UnorderedTimestamp> {
+        for (point, time) in points {
+            self.push(point, time)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the resolution of the visited cells.
+    #[must_use]
+    pub const fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// Returns the visits of the trajectory, in chronological order.
+    #[must_use]
+    pub fn visits(&self) -> &[CellVisit] {
+        &self.visits
+    }
+
+    /// Consumes the trajectory, returning its visits.
+    #[must_use]
+    pub fn into_visits(self) -> Vec<CellVisit> {
+        self.visits
+    }
+
+    /// Returns the total time spent in each visited cell, sorted by cell.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{LatLng, Resolution, TrajectoryBuilder};
+    ///
+    /// let mut trajectory = TrajectoryBuilder::new(Resolution::Nine).build();
+    /// trajectory.push_batch([
+    ///     (LatLng::new(48.8566, 2.3522)?, 0),
+    ///     (LatLng::new(48.8600, 2.3600)?, 60),
+    ///     (LatLng::new(48.8566, 2.3522)?, 120),
+    /// ])?;
+    ///
+    /// let dwell_times = trajectory.dwell_times();
+    /// let total = dwell_times.iter().map(|(_, time)| time).sum::<u64>();
+    /// assert_eq!(total, 120);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn dwell_times(&self) -> Vec<(CellIndex, u64)> {
+        let mut dwell_times = self
+            .visits
+            .iter()
+            .map(|visit| (visit.cell, visit.dwell()))
+            .collect::<Vec<_>>();
+        dwell_times.sort_unstable_by_key(|&(cell, _)| cell);
+        dwell_times.dedup_by(|(cell, time), (prev_cell, prev_time)| {
+            let is_same = cell == prev_cell;
+            if is_same {
+                *prev_time += *time;
+            }
+            is_same
+        });
+
+        dwell_times
+    }
+
+    /// Returns the (cell, time bucket) pairs covered by the trajectory.
+    ///
+    /// Time buckets are the timestamps divided by `window`, and each visit
+    /// covers the buckets overlapping its `[entry, exit)` interval. Pairs are
+    /// sorted by time bucket then cell, without duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use h3o::{LatLng, Resolution, TrajectoryBuilder};
+    /// use std::num::NonZeroU64;
+    ///
+    /// let mut trajectory = TrajectoryBuilder::new(Resolution::Nine).build();
+    /// trajectory.push_batch([
+    ///     (LatLng::new(48.8566, 2.3522)?, 0),
+    ///     (LatLng::new(48.8567, 2.3523)?, 150),
+    /// ])?;
+    ///
+    /// let window = NonZeroU64::new(60).expect("non-zero");
+    /// let keys = trajectory.time_buckets(window);
+    /// assert_eq!(keys.iter().map(|(_, bucket)| *bucket).collect::<Vec<_>>(), [0, 1, 2]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn time_buckets(&self, window: NonZeroU64) -> Vec<(CellIndex, u64)> {
+        let mut keys = self
+            .visits
+            .iter()
+            .flat_map(|visit| {
+                let first = visit.entry / window;
+                let last =
+                    visit.exit.saturating_sub(1).max(visit.entry) / window;
+                (first..=last).map(|bucket| (visit.cell, bucket))
+            })
+            .collect::<Vec<_>>();
+        keys.sort_unstable_by_key(|&(cell, bucket)| (bucket, cell));
+        keys.dedup();
+
+        keys
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A visit of a cell, along a trajectory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellVisit {
+    /// The visited cell.
+    pub cell: CellIndex,
+    /// When the trajectory entered the cell.
+    pub entry: u64,
+    /// When the trajectory left the cell.
+    pub exit: u64,
+    /// Number of points observed in the cell during the visit.
+    ///
+    /// Zero for cells inferred while filling a gap between two points.
+    pub point_count: usize,
+}
+
+impl CellVisit {
+    const fn new(
+        cell: CellIndex,
+        entry: u64,
+        exit: u64,
+        point_count: usize,
+    ) -> Self {
+        Self {
+            cell,
+            entry,
+            exit,
+            point_count,
+        }
+    }
+
+    /// Returns the time spent in the cell.
+    #[must_use]
+    pub const fn dwell(&self) -> u64 {
+        self.exit - self.entry
+    }
+
+    /// Returns true if the cell was inferred while filling a gap between two
+    /// points, rather than observed.
+    #[must_use]
+    pub const fn is_interpolated(&self) -> bool {
+        self.point_count == 0
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A builder to configure a trajectory.
+#[derive(Debug, Clone, Copy)]
+pub struct TrajectoryBuilder {
+    resolution: Resolution,
+    fill_gaps: bool,
+    max_gap: u32,
+}
+
+impl TrajectoryBuilder {
+    /// Initializes a new trajectory builder with default settings.
+    #[must_use]
+    pub const fn new(resolution: Resolution) -> Self {
+        Self {
+            resolution,
+            fill_gaps: true,
+            max_gap: DEFAULT_MAX_GAP,
+        }
+    }
+
+    /// Disable gap filling.
+    ///
+    /// By default, the cells crossed between two consecutive points lying in
+    /// non-neighboring cells are inferred and added to the trajectory.
+    #[must_use]
+    pub const fn disable_gap_filling(mut self) -> Self {
+        self.fill_gaps = false;
+        self
+    }
+
+    /// Set the maximum number of cells inferred between two consecutive
+    /// points.
+    ///
+    /// Gaps wider than this are left unfilled, as if gap filling was disabled
+    /// for this pair of points. Defaults to 1000 cells.
+    #[must_use]
+    pub const fn max_gap(mut self, max_gap: u32) -> Self {
+        self.max_gap = max_gap;
+        self
+    }
+
+    /// Builds the trajectory.
+    #[must_use]
+    pub const fn build(self) -> Trajectory {
+        Trajectory {
+            resolution: self.resolution,
+            fill_gaps: self.fill_gaps,
+            max_gap: self.max_gap,
+            visits: Vec::new(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// Returns the cells strictly between `from` and `to`, forming a path of
+/// neighboring cells.
+///
+/// The path is empty if it would contain more than `max_gap` cells, or if no
+/// contiguous path can be found.
+#[expect(
+    clippy::redundant_pub_crate,
+    reason = "internal helper, shared with the map-matcher"
+)]
+pub(crate) fn path(
+    from: CellIndex,
+    to: CellIndex,
+    max_gap: u32,
+) -> Vec<CellIndex> {
+    if from.is_neighbor_with(to).unwrap_or(false) {
+        return Vec::new();
+    }
+    // Skip the computation upfront when the distance is known.
+    let gap = from
+        .grid_distance(to)
+        .ok()
+        .and_then(|distance| u32::try_from(distance - 1).ok());
+    if gap.is_some_and(|gap| gap > max_gap) {
+        return Vec::new();
+    }
+
+    let Some(mut path) = from
+        .grid_path_cells(to)
+        .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+        .ok()
+        .or_else(|| greedy_path(from, to, max_gap))
+    else {
+        return Vec::new();
+    };
+    // Strip the endpoints.
+    path.pop();
+    path.remove(0);
+
+    path
+}
+
+/// Walks from `from` toward `to`, always moving to the neighbor closest to the
+/// destination.
+///
+/// Unlike `grid_path_cells`, this works across pentagons and icosahedron faces
+/// distortions.
+///
+/// Returns `None` if the walk gets stuck or needs more than `max_gap` cells
+/// between the endpoints.
+fn greedy_path(
+    from: CellIndex,
+    to: CellIndex,
+    max_gap: u32,
+) -> Option<Vec<CellIndex>> {
+    let max_len = usize::try_from(max_gap)
+        .unwrap_or(usize::MAX)
+        .saturating_add(2);
+    let target = LatLng::from(to);
+    let mut path = Vec::from([from]);
+    let mut current = from;
+    let mut distance = LatLng::from(from).distance_rads(target);
+
+    while current != to {
+        let (next, next_distance) = current
+            .grid_disk::<Vec<_>>(1)
+            .into_iter()
+            .map(|cell| (cell, LatLng::from(cell).distance_rads(target)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .filter(|&(_, next_distance)| next_distance < distance)?;
+        path.push(next);
+        if path.len() > max_len {
+            return None;
+        }
+        (current, distance) = (next, next_distance);
+    }
+
+    Some(path)
+}
+
+/// Returns `start + (end - start) * numerator / denominator`, without
+/// overflow.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "result is between start and end"
+)]
+fn interpolate(start: u64, end: u64, numerator: u64, denominator: u64) -> u64 {
+    let delta = u128::from(end - start) * u128::from(numerator)
+        / u128::from(denominator);
+    start + delta as u64
+}
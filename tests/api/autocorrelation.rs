@@ -0,0 +1,269 @@
+use super::rng::SplitMix64;
+use h3o::{
+    CellIndex, LatLng, Resolution, SpatialAutocorrelation, SpatialWeights,
+    error::AutocorrelationError,
+};
+
+/// Deterministic pseudo-random values.
+fn random_values(cells: &[CellIndex]) -> Vec<(CellIndex, f64)> {
+    let mut rng = SplitMix64::new();
+    cells.iter().map(|&cell| (cell, rng.next_f64())).collect()
+}
+
+/// Brute-force statistics, with immediate neighbors weights.
+struct Reference {
+    morans_i: f64,
+    local: Vec<f64>,
+    gi_star: Vec<f64>,
+}
+
+impl Reference {
+    fn new(values: &[(CellIndex, f64)]) -> Self {
+        let n = values.len() as f64;
+        let w = |a: CellIndex, b: CellIndex| {
+            if a.is_neighbor_with(b).expect("same resolution") {
+                1.
+            } else {
+                0.
+            }
+        };
+        let mean = values.iter().map(|(_, x)| x).sum::<f64>() / n;
+        let m2 = values.iter().map(|(_, x)| (x - mean).powi(2)).sum::<f64>();
+
+        let (mut s0, mut cross) = (0., 0.);
+        let mut local = Vec::new();
+        let mut gi_star = Vec::new();
+        let std_dev = (m2 / n).sqrt();
+        for &(a, xa) in values {
+            let mut lag = 0.;
+            let (mut sum, mut weights) = (xa, 1.);
+            for &(b, xb) in values {
+                let weight = w(a, b);
+                s0 += weight;
+                cross += weight * (xa - mean) * (xb - mean);
+                lag += weight * (xb - mean);
+                sum += weight * xb;
+                weights += weight;
+            }
+            local.push((xa - mean) / (m2 / n) * lag);
+            let denominator =
+                std_dev * ((n * weights - weights * weights) / (n - 1.)).sqrt();
+            gi_star.push((sum - mean * weights) / denominator);
+        }
+
+        Self {
+            morans_i: n / s0 * cross / m2,
+            local,
+            gi_star,
+        }
+    }
+}
+
+fn assert_close(lhs: f64, rhs: f64) {
+    assert!((lhs - rhs).abs() < 1e-9, "{lhs} vs {rhs}");
+}
+
+fn check(values: &[(CellIndex, f64)]) {
+    let mut values = values.to_vec();
+    values.sort_unstable_by_key(|&(cell, _)| cell);
+    let stats = SpatialAutocorrelation::new(
+        values.iter().copied(),
+        SpatialWeights::Rings(1),
+    )
+    .expect("stats");
+    let expected = Reference::new(&values);
+
+    assert_close(stats.morans_i().expect("moran").i, expected.morans_i);
+    let local = stats.local_morans_i();
+    let gi_star = stats.getis_ord_gi_star();
+    for (i, &(cell, _)) in values.iter().enumerate() {
+        assert_eq!(local[i].0, cell);
+        assert_close(local[i].1, expected.local[i]);
+        assert_eq!(gi_star[i].0, cell);
+        assert_close(gi_star[i].1, expected.gi_star[i]);
+    }
+}
+
+#[test]
+fn brute_force() {
+    let center = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    let cells = center.grid_disk::<Vec<_>>(4);
+    check(&random_values(&cells));
+}
+
+#[test]
+fn around_pentagon() {
+    let pentagon = CellIndex::try_from(0x8009fffffffffff)
+        .expect("pentagon")
+        .center_child(Resolution::Five)
+        .expect("center child");
+    let cells = pentagon.grid_disk::<Vec<_>>(3);
+    let values = random_values(&cells);
+
+    let stats = SpatialAutocorrelation::new(
+        values.iter().copied(),
+        SpatialWeights::Rings(1),
+    )
+    .expect("stats");
+    let count = stats
+        .neighbor_counts()
+        .find_map(|(cell, count)| (cell == pentagon).then_some(count));
+    assert_eq!(count, Some(5));
+
+    check(&values);
+}
+
+#[test]
+fn clustered_values() {
+    let center = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    let values = center
+        .grid_disk_distances::<Vec<_>>(6)
+        .into_iter()
+        .map(|(cell, distance)| (cell, if distance <= 2 { 10. } else { 1. }))
+        .collect::<Vec<_>>();
+    let stats = SpatialAutocorrelation::new(
+        values.iter().copied(),
+        SpatialWeights::Rings(1),
+    )
+    .expect("stats");
+
+    let moran = stats.morans_i().expect("moran");
+    assert!(moran.i > 0.5, "{moran:?}");
+    assert!(moran.z_score > 3., "{moran:?}");
+    assert_close(moran.expected, -1. / (values.len() as f64 - 1.));
+
+    let hotspot = stats
+        .getis_ord_gi_star()
+        .into_iter()
+        .find_map(|(cell, z)| (cell == center).then_some(z))
+        .expect("center");
+    assert!(hotspot > 3., "{hotspot}");
+}
+
+#[test]
+fn random_values_are_not_significant() {
+    let center = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    let cells = center.grid_disk::<Vec<_>>(10);
+    let stats = SpatialAutocorrelation::new(
+        random_values(&cells),
+        SpatialWeights::Rings(1),
+    )
+    .expect("stats");
+
+    let moran = stats.morans_i().expect("moran");
+    assert!(moran.z_score.abs() < 3., "{moran:?}");
+}
+
+#[test]
+fn distance_band() {
+    let center = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    let values = random_values(&center.grid_disk::<Vec<_>>(4));
+    // Between the first (~1.73 edge) and second (~3 edges) rings.
+    let band = Resolution::Ten.edge_length_m() * 2.3;
+
+    let rings = SpatialAutocorrelation::new(
+        values.iter().copied(),
+        SpatialWeights::Rings(1),
+    )
+    .expect("rings");
+    let distance = SpatialAutocorrelation::new(
+        values.iter().copied(),
+        SpatialWeights::DistanceBand(band),
+    )
+    .expect("distance");
+
+    assert_eq!(
+        rings.neighbor_counts().collect::<Vec<_>>(),
+        distance.neighbor_counts().collect::<Vec<_>>()
+    );
+    assert_eq!(rings.morans_i(), distance.morans_i());
+
+    let wider = SpatialAutocorrelation::new(
+        values.iter().copied(),
+        SpatialWeights::DistanceBand(band * 2.),
+    )
+    .expect("wider");
+    let count = wider
+        .neighbor_counts()
+        .find_map(|(cell, count)| (cell == center).then_some(count));
+    assert!(count > Some(6));
+}
+
+#[test]
+fn sparse_distance_band() {
+    // Wide bands on fine cells would cover billions of cells.
+    let paris = LatLng::new(48.854_501, 2.370_951)
+        .expect("paris")
+        .to_cell(Resolution::Fifteen);
+    let neighbor = paris.grid_disk::<Vec<_>>(1)[1];
+    let sydney = LatLng::new(-33.867_487, 151.206_990)
+        .expect("sydney")
+        .to_cell(Resolution::Fifteen);
+    let values = [(paris, 1.), (neighbor, 2.), (sydney, 3.)];
+
+    let local = SpatialAutocorrelation::new(
+        values,
+        SpatialWeights::DistanceBand(1000.),
+    )
+    .expect("local");
+    let mut counts = local.neighbor_counts().collect::<Vec<_>>();
+    counts.sort_unstable();
+    let mut expected = vec![(paris, 1), (neighbor, 1), (sydney, 0)];
+    expected.sort_unstable();
+    assert_eq!(counts, expected);
+
+    let global =
+        SpatialAutocorrelation::new(values, SpatialWeights::DistanceBand(2e7))
+            .expect("global");
+    assert!(global.neighbor_counts().all(|(_, count)| count == 2));
+}
+
+#[test]
+fn degenerate() {
+    let center = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    let constant = center.grid_disk::<Vec<_>>(2).into_iter().map(|c| (c, 1.));
+    let stats = SpatialAutocorrelation::new(constant, SpatialWeights::Rings(1))
+        .expect("stats");
+    assert_eq!(stats.len(), 19);
+    assert_eq!(stats.morans_i(), None);
+    assert!(stats.local_morans_i().is_empty());
+    assert!(stats.getis_ord_gi_star().is_empty());
+
+    let empty = SpatialAutocorrelation::new([], SpatialWeights::Rings(1))
+        .expect("empty");
+    assert!(empty.is_empty());
+    assert_eq!(empty.morans_i(), None);
+
+    let parent = center.parent(Resolution::Nine).expect("parent");
+    assert_eq!(
+        SpatialAutocorrelation::new(
+            [(center, 1.), (parent, 2.)],
+            SpatialWeights::Rings(1)
+        )
+        .err(),
+        Some(AutocorrelationError::ResolutionMismatch)
+    );
+}
+
+#[test]
+fn invalid_distance_band() {
+    let cell = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    for distance in [-1., f64::NAN, f64::INFINITY, 1e9] {
+        assert_eq!(
+            SpatialAutocorrelation::new(
+                [(cell, 1.)],
+                SpatialWeights::DistanceBand(distance)
+            )
+            .err(),
+            Some(AutocorrelationError::InvalidDistanceBand),
+            "{distance}"
+        );
+    }
+    assert!(
+        SpatialAutocorrelation::new(
+            [(cell, 1.)],
+            SpatialWeights::DistanceBand(0.)
+        )
+        .is_ok()
+    );
+}
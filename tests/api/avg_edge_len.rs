@@ -1,4 +1,4 @@
-use h3o::Resolution;
+use h3o::{EarthModel, Resolution};
 use std::{
     fs::File,
     io::{BufRead, BufReader},
@@ -39,3 +39,32 @@ fn golden_file() {
         );
     }
 }
+
+#[test]
+fn golden_file_wgs84() {
+    // Load the file generated by tools/average_edge_length wgs84
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("dataset/avgEdgeLenWgs84.txt");
+
+    let file = File::open(path).expect("open test dataset");
+    let reader = BufReader::new(file);
+    let resolutions = Resolution::range(Resolution::Zero, Resolution::Fifteen);
+
+    for (resolution, line) in resolutions.zip(reader.lines()) {
+        let line = line.expect("test input");
+        let parts = line.split(',').collect::<Vec<&str>>();
+        let avg_km = parts[0].parse::<f64>().expect("edge len in kilometers");
+        let avg_m = parts[1].parse::<f64>().expect("edge len in meters");
+
+        assert_eq!(
+            resolution.edge_length_km_with(EarthModel::Wgs84),
+            avg_km,
+            "avg edge len km at {resolution}"
+        );
+        assert_eq!(
+            resolution.edge_length_m_with(EarthModel::Wgs84),
+            avg_m,
+            "avg edge len m at {resolution}"
+        );
+    }
+}
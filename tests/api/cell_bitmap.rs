@@ -0,0 +1,185 @@
+use h3o::{
+    CellBitmap, CellIndex, Resolution,
+    error::{DecodingError, ResolutionMismatch},
+};
+
+fn cell() -> CellIndex {
+    CellIndex::try_from(0x8a1fb46622dffff).expect("cell")
+}
+
+fn bitmap(cells: impl IntoIterator<Item = CellIndex>) -> CellBitmap {
+    CellBitmap::from_cells(Resolution::Ten, cells).expect("bitmap")
+}
+
+#[test]
+fn insert_remove() {
+    let mut bitmap = CellBitmap::new(Resolution::Ten);
+    assert!(bitmap.is_empty());
+
+    assert_eq!(bitmap.insert(cell()), Ok(true));
+    assert_eq!(bitmap.insert(cell()), Ok(false));
+    assert!(bitmap.contains(cell()));
+    assert_eq!(bitmap.len(), 1);
+
+    let parent = cell().parent(Resolution::Nine).expect("parent");
+    assert_eq!(bitmap.insert(parent), Err(ResolutionMismatch));
+    assert!(!bitmap.contains(parent));
+
+    assert!(bitmap.remove(cell()));
+    assert!(!bitmap.remove(cell()));
+    assert!(bitmap.is_empty());
+    assert_eq!(bitmap, CellBitmap::new(Resolution::Ten));
+}
+
+#[test]
+fn ordered_iteration() {
+    let mut cells = cell().grid_disk::<Vec<_>>(10);
+    let result = bitmap(cells.iter().copied()).iter().collect::<Vec<_>>();
+    cells.sort_unstable();
+
+    assert_eq!(result, cells);
+}
+
+#[test]
+fn dense() {
+    // Big enough to span several chunks, with bitmap containers.
+    let base_cell = cell().parent(Resolution::Zero).expect("base cell");
+    let pentagon = CellIndex::try_from(0x8009fffffffffff).expect("pentagon");
+    let bitmap = CellBitmap::from_cells(Resolution::Six, [pentagon, base_cell])
+        .expect("bitmap");
+    let mut cells = base_cell
+        .children(Resolution::Six)
+        .chain(pentagon.children(Resolution::Six))
+        .collect::<Vec<_>>();
+    cells.sort_unstable();
+
+    assert_eq!(bitmap.len(), cells.len() as u64);
+    assert!(bitmap.iter().eq(cells.iter().copied()));
+}
+
+#[test]
+fn set_operations() {
+    let disk = bitmap(cell().grid_disk::<Vec<_>>(100));
+    let ring = bitmap(cell().grid_ring::<Vec<_>>(50));
+    let other = bitmap(
+        cell()
+            .grid_disk::<Vec<_>>(500)
+            .into_iter()
+            .filter(|cell| u64::from(*cell) % 3 == 0),
+    );
+
+    assert_eq!(disk.union(&ring), Ok(disk.clone()));
+    assert_eq!(disk.intersection(&ring), Ok(ring.clone()));
+
+    let union = disk.union(&other).expect("union");
+    let intersection = disk.intersection(&other).expect("intersection");
+    assert_eq!(union.len() + intersection.len(), disk.len() + other.len());
+    assert!(intersection.iter().all(|cell| disk.contains(cell)));
+    assert!(intersection.iter().all(|cell| other.contains(cell)));
+    assert!(
+        disk.iter()
+            .chain(other.iter())
+            .all(|cell| union.contains(cell))
+    );
+
+    let empty = disk.intersection(&CellBitmap::new(Resolution::Ten));
+    assert_eq!(empty.map(|bitmap| bitmap.is_empty()), Ok(true));
+    let coarse = CellBitmap::new(Resolution::Nine);
+    assert_eq!(disk.union(&coarse), Err(ResolutionMismatch));
+    assert_eq!(disk.intersection(&coarse), Err(ResolutionMismatch));
+}
+
+#[test]
+fn rank_select() {
+    let base_cell = cell().parent(Resolution::Five).expect("parent");
+    let bitmap = bitmap(
+        cell()
+            .grid_disk::<Vec<_>>(20)
+            .into_iter()
+            .chain(base_cell.children(Resolution::Ten).step_by(5)),
+    );
+
+    for (i, cell) in bitmap.iter().enumerate() {
+        let i = i as u64;
+        assert_eq!(bitmap.rank(cell), Ok(i + 1));
+        assert_eq!(bitmap.select(i), Some(cell));
+    }
+    assert_eq!(bitmap.select(bitmap.len()), None);
+
+    let first = CellIndex::first(Resolution::Ten);
+    assert_eq!(bitmap.rank(first), Ok(0));
+    let last = CellIndex::last(Resolution::Ten);
+    assert_eq!(bitmap.rank(last), Ok(bitmap.len()));
+    assert_eq!(bitmap.rank(base_cell), Err(ResolutionMismatch));
+}
+
+#[test]
+fn from_cells() {
+    let parent = cell().parent(Resolution::Seven).expect("parent");
+    let bitmap = bitmap([parent, cell()]);
+
+    assert_eq!(bitmap.len(), 343);
+    assert!(bitmap.iter().eq(parent.children(Resolution::Ten)));
+
+    let child = cell().center_child(Resolution::Eleven).expect("child");
+    let result = CellBitmap::from_cells(Resolution::Ten, [child]);
+    assert_eq!(result, Err(ResolutionMismatch));
+}
+
+#[test]
+fn serialization() {
+    let pentagon = CellIndex::try_from(0x8009fffffffffff).expect("pentagon");
+    for bitmap in [
+        CellBitmap::new(Resolution::Ten),
+        bitmap([cell()]),
+        bitmap(cell().grid_disk::<Vec<_>>(50)),
+        CellBitmap::from_cells(Resolution::Six, [pentagon]).expect("bitmap"),
+    ] {
+        let bytes = bitmap.to_bytes();
+        assert_eq!(CellBitmap::from_bytes(&bytes), Ok(bitmap));
+    }
+
+    let whole =
+        CellBitmap::from_cells(Resolution::Zero, CellIndex::base_cells())
+            .expect("bitmap");
+    assert_eq!(CellBitmap::from_bytes(&whole.to_bytes()), Ok(whole));
+}
+
+#[test]
+fn invalid_bytes() {
+    let bytes = bitmap(cell().grid_disk::<Vec<_>>(2)).to_bytes();
+
+    let result = CellBitmap::from_bytes(b"H3CS\x01\x0a\x00");
+    assert_eq!(result, Err(DecodingError::InvalidHeader));
+    let result = CellBitmap::from_bytes(b"H3CB\x02\x0a\x00");
+    assert_eq!(result, Err(DecodingError::UnsupportedVersion(2)));
+    let result = CellBitmap::from_bytes(b"H3CB\x01\x10\x00");
+    assert_eq!(result, Err(DecodingError::InvalidHeader));
+
+    for len in 0..bytes.len() {
+        let result = CellBitmap::from_bytes(&bytes[..len]);
+        assert!(result.is_err(), "truncated input ({len} bytes)");
+    }
+    let mut extra = bytes;
+    extra.push(0);
+    assert_eq!(
+        CellBitmap::from_bytes(&extra),
+        Err(DecodingError::TrailingBytes)
+    );
+
+    for input in [
+        // Unsorted values.
+        &b"H3CB\x01\x00\x01\x00\x01\x02\x00\x01\x00"[..],
+        // Duplicate values.
+        b"H3CB\x01\x00\x01\x00\x01\x01\x00\x01\x00",
+        // Ordinal out of range.
+        b"H3CB\x01\x00\x01\x00\x00\x7a\x00",
+        // Duplicate chunk.
+        b"H3CB\x01\x0f\x02\x00\x00\x00\x00\x00\x00\x00\x00",
+        // Chunk key overflowing the ordinals (`1 << 48`).
+        b"H3CB\x01\x00\x01\x80\x80\x80\x80\x80\x80\x40\x00\x00\x00",
+    ] {
+        let result = CellBitmap::from_bytes(input);
+        assert_eq!(result, Err(DecodingError::InvalidEntry), "{input:?}");
+    }
+}
@@ -0,0 +1,136 @@
+use h3o::{CellIndex, Resolution, error};
+use std::collections::BTreeSet;
+
+fn center() -> CellIndex {
+    CellIndex::try_from(0x8a1fb46622dffff).expect("center")
+}
+
+fn sorted(cells: impl IntoIterator<Item = CellIndex>) -> Vec<CellIndex> {
+    cells
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+#[test]
+fn disk() {
+    let cells = center().grid_disk::<Vec<_>>(2);
+    let components =
+        CellIndex::connected_components(cells.clone()).expect("components");
+
+    assert_eq!(components.len(), 1);
+    let component = &components[0];
+    assert_eq!(component.cells(), sorted(cells));
+    assert_eq!(
+        component.outer_boundary(),
+        sorted(center().grid_ring::<Vec<_>>(2))
+    );
+    assert!(component.inner_boundary().is_empty());
+    assert!(component.is_simply_connected());
+}
+
+#[test]
+fn ring_with_island() {
+    let ring = center().grid_ring::<Vec<_>>(2);
+    let cells = ring.iter().copied().chain(std::iter::once(center()));
+    let components =
+        CellIndex::connected_components(cells).expect("components");
+
+    assert_eq!(components.len(), 2);
+    let (ring_component, island) = if components[0].cells().len() == 1 {
+        (&components[1], &components[0])
+    } else {
+        (&components[0], &components[1])
+    };
+
+    assert_eq!(island.cells(), [center()]);
+    assert!(island.is_simply_connected());
+
+    assert_eq!(ring_component.cells(), sorted(ring.clone()));
+    assert_eq!(ring_component.outer_boundary(), sorted(ring.clone()));
+    assert_eq!(ring_component.inner_boundary(), sorted(ring));
+    // The hole contains the island.
+    assert_eq!(
+        ring_component.holes(),
+        [sorted(center().grid_disk::<Vec<_>>(1))]
+    );
+}
+
+#[test]
+fn thick_ring() {
+    let inner = center().grid_disk::<BTreeSet<_>>(2);
+    let cells = center()
+        .grid_disk::<Vec<_>>(5)
+        .into_iter()
+        .filter(|cell| !inner.contains(cell));
+    let components =
+        CellIndex::connected_components(cells).expect("components");
+
+    assert_eq!(components.len(), 1);
+    let component = &components[0];
+    assert_eq!(component.holes(), [sorted(inner)]);
+    assert_eq!(
+        component.outer_boundary(),
+        sorted(center().grid_ring::<Vec<_>>(5))
+    );
+    assert_eq!(
+        component.inner_boundary(),
+        sorted(center().grid_ring::<Vec<_>>(3))
+    );
+}
+
+#[test]
+fn around_pentagon() {
+    let pentagon = Resolution::Five.pentagons().next().expect("pentagon");
+    let ring = pentagon.grid_ring::<Vec<_>>(2);
+    let components = CellIndex::connected_components(ring).expect("components");
+
+    assert_eq!(components.len(), 1);
+    assert_eq!(
+        components[0].holes(),
+        [sorted(pentagon.grid_disk::<Vec<_>>(1))]
+    );
+}
+
+#[test]
+fn is_simply_connected() {
+    assert_eq!(
+        CellIndex::is_simply_connected(center().grid_disk::<Vec<_>>(3)),
+        Ok(true)
+    );
+    assert_eq!(
+        CellIndex::is_simply_connected(center().grid_ring::<Vec<_>>(1)),
+        Ok(false)
+    );
+    // Two disjoint cells.
+    assert_eq!(
+        CellIndex::is_simply_connected([
+            center(),
+            center().grid_ring::<Vec<_>>(2)[0]
+        ]),
+        Ok(false)
+    );
+    assert_eq!(CellIndex::is_simply_connected([]), Ok(false));
+}
+
+#[test]
+fn resolution_mismatch() {
+    let child = center().center_child(Resolution::Twelve).expect("child");
+
+    assert_eq!(
+        CellIndex::connected_components([center(), child]),
+        Err(error::ResolutionMismatch)
+    );
+}
+
+#[test]
+fn duplicates() {
+    let components =
+        CellIndex::connected_components([center(), center(), center()])
+            .expect("components");
+
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0].cells(), [center()]);
+    assert_eq!(components[0].outer_boundary(), [center()]);
+}
@@ -1,4 +1,7 @@
-use h3o::{CellIndex, Resolution, error};
+use h3o::{
+    BaseCell, CellIndex, CellIndexBuilder, Direction, LatLng, Resolution, error,
+};
+use std::collections::BTreeSet;
 
 #[test]
 fn is_neighbor_with() {
@@ -41,6 +44,291 @@ fn try_from_str() {
     assert!(result.is_err(), "invalid string");
 }
 
+#[test]
+fn parse_formats() {
+    let expected = CellIndex::try_from(0x8a1fb46622dffff).expect("index");
+
+    for input in [
+        "8a1fb46622dffff",
+        "08a1fb46622dffff",
+        "8A1FB46622DFFFF",
+        "0x8a1fb46622dffff",
+        "0X8A1fb46622dffff",
+        "622054503267303423",
+        "0622054503267303423",
+        "a3yt6c8pr",
+        "A3YT6C8PR",
+    ] {
+        assert_eq!(CellIndex::parse(input), Ok(expected), "{input}");
+    }
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(CellIndex::parse(""), Err(error::ParsingError::Empty));
+    assert_eq!(
+        CellIndex::parse("0x"),
+        Err(error::ParsingError::InvalidLength(2))
+    );
+    assert_eq!(
+        CellIndex::parse("8a1fb46622dfffg"),
+        Err(error::ParsingError::InvalidCharacter {
+            position: 14,
+            character: 'g'
+        })
+    );
+    assert_eq!(
+        CellIndex::parse("0x8a1fb4662€dfff"),
+        Err(error::ParsingError::InvalidCharacter {
+            position: 11,
+            character: '€'
+        })
+    );
+    assert_eq!(
+        CellIndex::parse("0x18a1fb46622dffff0"),
+        Err(error::ParsingError::Overflow)
+    );
+    assert_eq!(
+        CellIndex::parse("99999999999999999999"),
+        Err(error::ParsingError::Overflow)
+    );
+    assert!(matches!(
+        CellIndex::parse("0x8a1fb46622dfff"),
+        Err(error::ParsingError::InvalidIndex(_))
+    ));
+    assert!(matches!(
+        CellIndex::parse("622054503267303422"),
+        Err(error::ParsingError::InvalidIndex(_))
+    ));
+
+    // Short codes.
+    assert_eq!(
+        CellIndex::parse("a3yt6c8p"),
+        Err(error::ParsingError::InvalidLength(8))
+    );
+    assert_eq!(
+        CellIndex::parse("a3yt6cupr"),
+        Err(error::ParsingError::InvalidCharacter {
+            position: 6,
+            character: 'u'
+        })
+    );
+    assert_eq!(
+        CellIndex::parse("g3yt6c8pr"),
+        Err(error::ParsingError::InvalidCharacter {
+            position: 0,
+            character: 'g'
+        })
+    );
+    // Non-zero padding bits.
+    assert_eq!(
+        CellIndex::parse("a3yt6c8ps"),
+        Err(error::ParsingError::InvalidCharacter {
+            position: 8,
+            character: 's'
+        })
+    );
+    // Base cell 122.
+    assert!(matches!(
+        CellIndex::parse("0yg"),
+        Err(error::ParsingError::InvalidIndex(_))
+    ));
+}
+
+#[test]
+fn short_code() {
+    let expected = CellIndex::try_from(0x8a1fb46622dffff).expect("index");
+    assert_eq!(expected.short_code().as_str(), "a3yt6c8pr");
+
+    let mut lengths = Vec::new();
+    for resolution in Resolution::range(Resolution::Zero, Resolution::Fifteen) {
+        let points = (-8..=8).flat_map(|lat| {
+            (-17..=17).map(move |lng| {
+                LatLng::new(f64::from(lat) * 11., f64::from(lng) * 10.6)
+                    .expect("point")
+            })
+        });
+        let cells = points
+            .map(|point| point.to_cell(resolution))
+            .chain(resolution.pentagons())
+            .chain(
+                CellIndex::base_cells()
+                    .filter_map(|cell| cell.center_child(resolution)),
+            );
+        for cell in cells {
+            let code = cell.short_code();
+            assert_eq!(CellIndex::parse(code.as_str()), Ok(cell), "{code}");
+            assert_eq!(code.to_string(), code.as_str());
+            lengths.push(code.as_str().len());
+        }
+    }
+    assert_eq!(lengths.iter().min(), Some(&3));
+    assert_eq!(lengths.iter().max(), Some(&12));
+}
+
+#[test]
+fn path() {
+    let expected = CellIndex::try_from(0x8a1fb46622dffff).expect("index");
+    assert_eq!(expected.to_path_string(), "15/6643142133");
+
+    for resolution in Resolution::range(Resolution::Zero, Resolution::Fifteen) {
+        let points = (-8..=8).flat_map(|lat| {
+            (-17..=17).map(move |lng| {
+                LatLng::new(f64::from(lat) * 11., f64::from(lng) * 10.6)
+                    .expect("point")
+            })
+        });
+        let cells = points
+            .map(|point| point.to_cell(resolution))
+            .chain(resolution.pentagons());
+        for cell in cells {
+            let path = cell.to_path_string();
+            assert_eq!(CellIndex::parse(&path), Ok(cell), "{path}");
+
+            let directions = cell
+                .directions()
+                .map(|(resolution, direction)| {
+                    assert_eq!(cell.direction_at(resolution), Some(direction));
+                    direction
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(directions.len(), usize::from(resolution));
+            assert_eq!(
+                CellIndex::from_directions(cell.base_cell(), &directions),
+                Ok(cell)
+            );
+        }
+    }
+}
+
+#[test]
+fn path_errors() {
+    // Pentagon deleted subsequence.
+    let pentagon = BaseCell::try_from(4).expect("base cell");
+    assert!(pentagon.is_pentagon());
+    assert!(
+        CellIndex::from_directions(
+            pentagon,
+            &[Direction::Center, Direction::K]
+        )
+        .is_err()
+    );
+    assert!(matches!(
+        CellIndex::parse("4/01"),
+        Err(error::ParsingError::InvalidIndex(_))
+    ));
+    assert!(CellIndex::parse("4/02").is_ok());
+    assert!(CellIndex::parse("5/01").is_ok());
+
+    // Too many directions.
+    assert!(CellIndex::from_directions(pentagon, &[Direction::I; 16]).is_err());
+    assert_eq!(
+        CellIndex::parse("5/0123456012345601"),
+        Err(error::ParsingError::InvalidLength(16))
+    );
+
+    // Invalid base cell.
+    assert!(matches!(
+        CellIndex::parse("122/"),
+        Err(error::ParsingError::InvalidIndex(_))
+    ));
+    assert_eq!(
+        CellIndex::parse("1000/"),
+        Err(error::ParsingError::InvalidCharacter {
+            position: 3,
+            character: '0'
+        })
+    );
+    assert_eq!(
+        CellIndex::parse("/12"),
+        Err(error::ParsingError::InvalidCharacter {
+            position: 0,
+            character: '/'
+        })
+    );
+
+    // Invalid directions.
+    assert_eq!(
+        CellIndex::parse("20/1237"),
+        Err(error::ParsingError::InvalidCharacter {
+            position: 6,
+            character: '7'
+        })
+    );
+    assert_eq!(
+        CellIndex::parse("20/12/3"),
+        Err(error::ParsingError::InvalidCharacter {
+            position: 5,
+            character: '/'
+        })
+    );
+}
+
+#[test]
+fn builder() {
+    for cell in Resolution::range(Resolution::Zero, Resolution::Fifteen)
+        .flat_map(|resolution| {
+            let center = LatLng::new(48.85, 2.35).expect("point");
+            [center.to_cell(resolution)]
+                .into_iter()
+                .chain(resolution.pentagons())
+        })
+    {
+        let result = CellIndexBuilder::new(cell.base_cell())
+            .directions(cell.directions().map(|(_, direction)| direction))
+            .build();
+        assert_eq!(result, Ok(cell));
+    }
+
+    // Missing directions default to the center child.
+    let base_cell = BaseCell::try_from(20).expect("base cell");
+    let cell = CellIndexBuilder::new(base_cell)
+        .resolution(Resolution::Four)
+        .direction(Direction::J)
+        .build()
+        .expect("cell");
+    assert_eq!(cell.to_path_string(), "20/2000");
+    assert_eq!(
+        CellIndexBuilder::new(base_cell)
+            .resolution(Resolution::Two)
+            .build()
+            .ok(),
+        CellIndex::base_cells()
+            .find(|cell| cell.base_cell() == base_cell)
+            .and_then(|cell| cell.center_child(Resolution::Two))
+    );
+
+    // More directions than the resolution.
+    assert!(
+        CellIndexBuilder::new(base_cell)
+            .resolution(Resolution::One)
+            .directions([Direction::J, Direction::K])
+            .build()
+            .is_err()
+    );
+    // Too many directions.
+    assert!(
+        CellIndexBuilder::new(base_cell)
+            .directions([Direction::J; 16])
+            .build()
+            .is_err()
+    );
+    // Pentagon deleted subsequence.
+    let pentagon = BaseCell::try_from(4).expect("base cell");
+    assert!(
+        CellIndexBuilder::new(pentagon)
+            .directions([Direction::Center, Direction::Center, Direction::K])
+            .build()
+            .is_err()
+    );
+    assert!(
+        CellIndexBuilder::new(pentagon)
+            .directions([Direction::Center, Direction::J, Direction::K])
+            .build()
+            .is_ok()
+    );
+}
+
 // Resolutions are displayed as numerical value.
 #[test]
 fn display() {
@@ -100,6 +388,233 @@ fn child_position_roundtrip() {
     assert_eq!(cell, Some(child));
 }
 
+#[test]
+fn ordinal() {
+    let index = CellIndex::try_from(0x8a1fb46622dffff).expect("index");
+    assert_eq!(index.ordinal(), 4_423_355_056);
+
+    for resolution in Resolution::range(Resolution::Zero, Resolution::Fifteen) {
+        assert_eq!(CellIndex::first(resolution).ordinal(), 0);
+        assert_eq!(
+            CellIndex::last(resolution).ordinal(),
+            resolution.cell_count() - 1
+        );
+    }
+}
+
+#[test]
+fn ordinal_follows_ordering() {
+    let mut cell = CellIndex::first(Resolution::Two);
+    for ordinal in 0..Resolution::Two.cell_count() {
+        assert_eq!(cell.ordinal(), ordinal, "{cell}");
+        assert_eq!(
+            CellIndex::from_ordinal(Resolution::Two, ordinal),
+            Some(cell)
+        );
+        cell = cell.succ().unwrap_or(cell);
+    }
+}
+
+#[test]
+fn from_ordinal() {
+    for resolution in Resolution::range(Resolution::Zero, Resolution::Fifteen) {
+        let count = resolution.cell_count();
+        assert_eq!(
+            CellIndex::from_ordinal(resolution, 0),
+            Some(CellIndex::first(resolution))
+        );
+        assert_eq!(
+            CellIndex::from_ordinal(resolution, count - 1),
+            Some(CellIndex::last(resolution))
+        );
+        assert_eq!(CellIndex::from_ordinal(resolution, count), None);
+    }
+    assert_eq!(CellIndex::from_ordinal(Resolution::Zero, u64::MAX), None);
+
+    // Around a pentagon.
+    let pentagon = CellIndex::try_from(0x8f0800000000000).expect("pentagon");
+    for cell in pentagon.grid_disk::<Vec<_>>(3) {
+        assert_eq!(
+            CellIndex::from_ordinal(Resolution::Fifteen, cell.ordinal()),
+            Some(cell)
+        );
+    }
+}
+
+#[test]
+fn key_range() {
+    let index = CellIndex::try_from(0x8a1fb46622dffff).expect("index");
+    let range = index.key_range(Resolution::Thirteen).expect("range");
+    let children = index.children(Resolution::Thirteen).collect::<Vec<_>>();
+    assert_eq!(*range.start(), u64::from(children[0]));
+    assert_eq!(*range.end(), u64::from(children[children.len() - 1]));
+    assert_eq!(index.key_range(Resolution::Ten), Some(range_of(index)));
+    assert_eq!(index.key_range(Resolution::Nine), None);
+
+    // Pentagons skip the deleted subsequence, not the last child.
+    let pentagon = CellIndex::try_from(0x8009fffffffffff).expect("pentagon");
+    let range = pentagon.key_range(Resolution::Two).expect("range");
+    let children = pentagon.children(Resolution::Two).collect::<Vec<_>>();
+    assert_eq!(*range.start(), u64::from(children[0]));
+    assert_eq!(*range.end(), u64::from(children[children.len() - 1]));
+}
+
+#[test]
+fn key_ranges() {
+    let index = CellIndex::try_from(0x8a1fb46622dffff).expect("index");
+    let pentagon = CellIndex::try_from(0x8a0800000007fff).expect("pentagon");
+    let mut cells = index.grid_disk::<Vec<_>>(5);
+    cells.extend(pentagon.grid_disk::<Vec<_>>(5));
+    CellIndex::compact(&mut cells).expect("compact");
+    let expected =
+        CellIndex::uncompact(cells.iter().copied(), Resolution::Twelve)
+            .collect::<BTreeSet<_>>();
+
+    let ranges =
+        CellIndex::key_ranges(Resolution::Twelve, cells.iter().copied())
+            .expect("ranges");
+    // Ranges cover exactly the expected cells.
+    let mut count = 0;
+    for range in &ranges {
+        let mut cell = CellIndex::try_from(*range.start()).expect("start");
+        loop {
+            assert!(expected.contains(&cell), "{cell}");
+            count += 1;
+            if u64::from(cell) == *range.end() {
+                break;
+            }
+            cell = cell.succ().expect("next cell");
+        }
+    }
+    assert_eq!(count, expected.len());
+    // Ranges are sorted and minimal.
+    for pair in ranges.windows(2) {
+        let end = CellIndex::try_from(*pair[0].end()).expect("end");
+        assert!(pair[0].end() < pair[1].start());
+        assert_ne!(end.succ().map(u64::from), Some(*pair[1].start()));
+    }
+
+    // Overlapping and duplicate input is merged.
+    let parent = index.parent(Resolution::Eight).expect("parent");
+    let result =
+        CellIndex::key_ranges(Resolution::Eleven, [index, parent, index]);
+    assert_eq!(
+        result,
+        Ok(vec![range_of_children(parent, Resolution::Eleven)])
+    );
+
+    // Consecutive siblings form a single range.
+    let result = CellIndex::key_ranges(
+        Resolution::Ten,
+        parent.children(Resolution::Nine),
+    );
+    assert_eq!(result, Ok(vec![range_of_children(parent, Resolution::Ten)]));
+    // Even across base cells.
+    let result =
+        CellIndex::key_ranges(Resolution::One, CellIndex::base_cells());
+    assert_eq!(result.map(|ranges| ranges.len()), Ok(1));
+
+    let child = index.center_child(Resolution::Eleven).expect("child");
+    let result = CellIndex::key_ranges(Resolution::Ten, [child]);
+    assert_eq!(result, Err(error::ResolutionMismatch));
+}
+
+#[test]
+fn locality_key_is_unique() {
+    for resolution in [Resolution::Zero, Resolution::One, Resolution::Two] {
+        let cells = CellIndex::base_cells()
+            .flat_map(|cell| cell.children(resolution))
+            .collect::<Vec<_>>();
+        let keys = cells
+            .iter()
+            .map(|cell| cell.locality_key())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(keys.len(), cells.len(), "{resolution}");
+    }
+
+    // Finest resolution, around a pentagon and across faces.
+    let pentagon = CellIndex::try_from(0x8f0800000000000).expect("pentagon");
+    let cells = pentagon.grid_disk::<Vec<_>>(20);
+    let keys = cells
+        .iter()
+        .map(|cell| cell.locality_key())
+        .collect::<BTreeSet<_>>();
+    assert_eq!(keys.len(), cells.len());
+}
+
+#[test]
+fn locality_ordering() {
+    // Sum of the grid distances between consecutive cells.
+    let jumps = |cells: &[CellIndex]| {
+        cells
+            .windows(2)
+            .map(|pair| pair[0].grid_distance(pair[1]).expect("distance"))
+            .sum::<i32>()
+    };
+    // Disk overlapping several base cells.
+    let index = CellIndex::try_from(0x85283473fffffff).expect("index");
+    let mut cells = index.grid_disk::<Vec<_>>(30);
+
+    cells.sort_unstable();
+    let native = jumps(&cells);
+    cells.sort_by(CellIndex::cmp_locality);
+    let locality = jumps(&cells);
+    assert!(locality * 2 < native, "{locality} vs {native}");
+
+    // Total order, even across resolutions.
+    let parent = index.parent(Resolution::Four).expect("parent");
+    assert_eq!(index.cmp_locality(&index), std::cmp::Ordering::Equal);
+    assert_ne!(parent.cmp_locality(&index), std::cmp::Ordering::Equal);
+}
+
+#[test]
+fn locality_ranges() {
+    let index = CellIndex::try_from(0x8a1fb46622dffff).expect("index");
+    let mut cells = index.grid_disk::<Vec<_>>(5);
+    CellIndex::compact(&mut cells).expect("compact");
+    let keys = CellIndex::uncompact(cells.iter().copied(), Resolution::Twelve)
+        .map(CellIndex::locality_key)
+        .collect::<BTreeSet<_>>();
+
+    let ranges =
+        CellIndex::locality_ranges(Resolution::Twelve, cells.iter().copied())
+            .expect("ranges");
+    let count = ranges
+        .iter()
+        .map(|range| range.end() - range.start() + 1)
+        .sum::<u64>();
+    assert_eq!(count, keys.len() as u64);
+    assert!(
+        keys.iter()
+            .all(|key| { ranges.iter().any(|range| range.contains(key)) })
+    );
+    for pair in ranges.windows(2) {
+        assert!(pair[0].end() + 1 < *pair[1].start());
+    }
+
+    let child = index.center_child(Resolution::Eleven).expect("child");
+    let result = CellIndex::locality_ranges(Resolution::Ten, [child]);
+    assert_eq!(result, Err(error::ResolutionMismatch));
+
+    let base = CellIndex::base_cells().next().expect("base cell");
+    let result = CellIndex::locality_ranges(Resolution::Fifteen, [base]);
+    assert_eq!(result, Err(error::ResolutionMismatch));
+}
+
+fn range_of(cell: CellIndex) -> std::ops::RangeInclusive<u64> {
+    u64::from(cell)..=u64::from(cell)
+}
+
+fn range_of_children(
+    cell: CellIndex,
+    resolution: Resolution,
+) -> std::ops::RangeInclusive<u64> {
+    let mut children = cell.children(resolution);
+    let first = children.next().expect("first child");
+    let last = children.last().unwrap_or(first);
+    u64::from(first)..=u64::from(last)
+}
+
 #[test]
 fn max_grid_ring_size() {
     assert_eq!(h3o::max_grid_ring_size(2), 12);
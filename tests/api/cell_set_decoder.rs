@@ -0,0 +1,157 @@
+use h3o::{
+    CellIndex, Resolution,
+    error::{CompactionError, DecodingError},
+};
+
+fn round_trip(mut cells: Vec<CellIndex>) -> Vec<u8> {
+    let bytes = CellIndex::encode_set(cells.iter().copied()).expect("encode");
+    let decoded = CellIndex::decode_set(&bytes)
+        .expect("header")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("decode");
+    cells.sort_unstable();
+    assert_eq!(decoded, cells);
+    bytes
+}
+
+#[test]
+fn disk() {
+    let index = CellIndex::try_from(0x8a1fb46622dffff).expect("index");
+    let cells = index.grid_disk::<Vec<_>>(20);
+    let count = cells.len();
+
+    let bytes = round_trip(cells);
+    assert!(bytes.len() < count / 2, "{} bytes", bytes.len());
+}
+
+#[test]
+fn pentagon() {
+    let pentagon = CellIndex::try_from(0x8a0800000007fff).expect("pentagon");
+    round_trip(pentagon.grid_disk::<Vec<_>>(10));
+
+    let base_cell = pentagon.parent(Resolution::Zero).expect("base cell");
+    round_trip(base_cell.children(Resolution::Five).collect());
+}
+
+#[test]
+fn whole_resolution() {
+    let cells = CellIndex::base_cells()
+        .flat_map(|cell| cell.children(Resolution::Two))
+        .collect::<Vec<_>>();
+    let bytes = round_trip(cells);
+    // Header and one byte per base cell.
+    assert_eq!(bytes.len(), 4 + 1 + 1 + 1 + 122);
+}
+
+#[test]
+fn extreme_resolutions() {
+    round_trip(CellIndex::base_cells().step_by(3).collect());
+
+    let index = CellIndex::try_from(0x8f734e64992d6d8).expect("index");
+    round_trip(index.grid_disk::<Vec<_>>(5));
+    round_trip(vec![index]);
+}
+
+#[test]
+fn sparse() {
+    let cells = CellIndex::base_cells()
+        .flat_map(|cell| cell.children(Resolution::Three).step_by(97))
+        .collect::<Vec<_>>();
+    round_trip(cells);
+}
+
+#[test]
+fn empty() {
+    round_trip(Vec::new());
+}
+
+#[test]
+fn compacted() {
+    let index = CellIndex::try_from(0x8a1fb46622dffff).expect("index");
+    let mut cells = index.grid_disk::<Vec<_>>(20);
+    let bytes = CellIndex::encode_set(cells.iter().copied()).expect("encode");
+
+    let decoder = CellIndex::decode_set(&bytes).expect("header");
+    assert_eq!(decoder.resolution(), Resolution::Ten);
+    let result = decoder
+        .compacted()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("decode");
+    CellIndex::compact(&mut cells).expect("compact");
+    assert_eq!(result, cells);
+}
+
+#[test]
+fn invalid_input() {
+    let index = CellIndex::try_from(0x8a1fb46622dffff).expect("index");
+    let parent = index.parent(Resolution::Nine).expect("parent");
+
+    assert_eq!(
+        CellIndex::encode_set([index, index]),
+        Err(CompactionError::DuplicateInput)
+    );
+    assert_eq!(
+        CellIndex::encode_set([index, parent]),
+        Err(CompactionError::HeterogeneousResolution)
+    );
+}
+
+#[test]
+fn invalid_header() {
+    let bytes = CellIndex::encode_set([
+        CellIndex::try_from(0x8a1fb46622dffff).expect("index")
+    ])
+    .expect("encode");
+
+    let result = CellIndex::decode_set(b"H3XS\x01\x0a\x00");
+    assert_eq!(result.err(), Some(DecodingError::InvalidHeader));
+
+    let result = CellIndex::decode_set(b"H3CS\x02\x0a\x00");
+    assert_eq!(result.err(), Some(DecodingError::UnsupportedVersion(2)));
+
+    let result = CellIndex::decode_set(b"H3CS\x01\x10\x00");
+    assert_eq!(result.err(), Some(DecodingError::InvalidHeader));
+
+    for len in 0..7 {
+        let result = CellIndex::decode_set(&bytes[..len]);
+        assert!(result.is_err(), "truncated header ({len} bytes)");
+    }
+}
+
+#[test]
+fn invalid_body() {
+    let decode = |bytes: &[u8]| {
+        CellIndex::decode_set(bytes)
+            .expect("header")
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let index = CellIndex::try_from(0x8a1fb46622dffff).expect("index");
+    let bytes =
+        CellIndex::encode_set(index.grid_disk::<Vec<_>>(2)).expect("encode");
+
+    // Missing entry.
+    let result = decode(&bytes[..bytes.len() - 1]);
+    assert_eq!(result, Err(DecodingError::UnexpectedEnd));
+
+    // Extra byte.
+    let mut extra = bytes.clone();
+    extra.push(0);
+    assert_eq!(decode(&extra), Err(DecodingError::TrailingBytes));
+
+    // Ordinal out of range.
+    let result = decode(b"H3CS\x01\x00\x01\xff\x0f");
+    assert_eq!(result, Err(DecodingError::InvalidEntry));
+
+    // Resolution delta too big.
+    let result = decode(b"H3CS\x01\x00\x01\x01");
+    assert_eq!(result, Err(DecodingError::InvalidEntry));
+
+    // Ordinal not aligned on a parent: 2nd child as a res 0 cell.
+    let result = decode(b"H3CS\x01\x01\x01\x11");
+    assert_eq!(result, Err(DecodingError::InvalidEntry));
+
+    // Varint overflow.
+    let result =
+        decode(b"H3CS\x01\x01\x01\xff\xff\xff\xff\xff\xff\xff\xff\xff\x7f");
+    assert_eq!(result, Err(DecodingError::InvalidEntry));
+}
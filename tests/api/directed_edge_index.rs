@@ -1,4 +1,4 @@
-use h3o::DirectedEdgeIndex;
+use h3o::{CellIndex, DirectedEdgeIndex, DirectedEdgeIndexBuilder, Edge};
 
 #[test]
 fn try_from_str() {
@@ -37,3 +37,24 @@ fn display() {
             .to_owned();
     assert_eq!(result, expected, "binary");
 }
+
+#[test]
+fn builder() {
+    let origin = CellIndex::try_from(0x8a194e699ab7fff).expect("cell");
+    for edge in origin.edges() {
+        let result = DirectedEdgeIndexBuilder::new(origin, edge.edge()).build();
+        assert_eq!(result, Ok(edge));
+    }
+
+    // Pentagons don't have an edge along the K axis.
+    let pentagon = CellIndex::try_from(0x8009fffffffffff).expect("pentagon");
+    let builder =
+        DirectedEdgeIndexBuilder::new(origin, Edge::try_from(1).expect("edge"))
+            .origin(pentagon);
+    assert!(builder.build().is_err());
+    let edge = Edge::try_from(2).expect("edge");
+    assert_eq!(
+        builder.edge(edge).build().map(|index| index.origin()),
+        Ok(pentagon)
+    );
+}
@@ -0,0 +1,235 @@
+use h3o::{
+    CellIndex, DissolverBuilder, LatLng, Resolution, error::DissolutionError,
+};
+use std::cell::Cell;
+
+fn center() -> CellIndex {
+    CellIndex::try_from(0x8a1fb46622dffff).expect("center")
+}
+
+/// Two islands, one of them with a hole, a few parents apart.
+fn cells() -> Vec<CellIndex> {
+    let far = center()
+        .grid_ring::<Vec<_>>(12)
+        .into_iter()
+        .next()
+        .expect("far");
+    let mut cells = center().grid_ring::<Vec<_>>(2);
+    cells.extend(center().grid_ring::<Vec<_>>(3));
+    cells.extend(far.grid_disk::<Vec<_>>(2));
+    cells.sort_unstable();
+    cells
+}
+
+/// Normalizes rings so they can be compared, regardless of their starting
+/// point and order.
+fn normalize(
+    rings: impl IntoIterator<Item = Vec<LatLng>>,
+) -> Vec<Vec<(u64, u64)>> {
+    let mut rings = rings
+        .into_iter()
+        .map(|ring| {
+            let mut ring = ring
+                .into_iter()
+                .map(|ll| {
+                    (ll.lat_radians().to_bits(), ll.lng_radians().to_bits())
+                })
+                .collect::<Vec<_>>();
+            let first = ring
+                .iter()
+                .enumerate()
+                .min_by_key(|&(_, coord)| coord)
+                .map_or(0, |(idx, _)| idx);
+            ring.rotate_left(first);
+            ring
+        })
+        .collect::<Vec<_>>();
+    rings.sort_unstable();
+    rings
+}
+
+#[test]
+fn dissolve() {
+    let dissolver = DissolverBuilder::new().build();
+    let result = dissolver.dissolve(cells()).expect("rings");
+    let expected = CellIndex::outline(cells())
+        .expect("outline")
+        .into_iter()
+        .map(|ring| ring.boundary());
+
+    assert_eq!(normalize(result), normalize(expected));
+}
+
+#[test]
+fn distortions() {
+    let cell = CellIndex::base_cells()
+        .flat_map(|cell| cell.children(Resolution::One))
+        .find(|cell| cell.boundary().len() > 6)
+        .expect("cell with distortions");
+    let dissolver = DissolverBuilder::new().build();
+    let rings = dissolver
+        .dissolve([cell])
+        .expect("rings")
+        .collect::<Vec<_>>();
+
+    assert_eq!(rings.len(), 1);
+    assert_eq!(rings[0].len(), cell.boundary().len());
+}
+
+#[test]
+fn sorted() {
+    let dissolver = DissolverBuilder::new().build();
+    let expected = dissolver.dissolve(cells()).expect("rings");
+
+    for resolution in [Resolution::Zero, Resolution::Six, Resolution::Ten] {
+        let result = dissolver
+            .dissolve_sorted(cells(), resolution)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("rings");
+        assert_eq!(
+            normalize(result),
+            normalize(dissolver.dissolve(cells()).expect("rings")),
+            "resolution {resolution}"
+        );
+    }
+    assert_eq!(expected.count(), 3);
+}
+
+#[test]
+fn sorted_is_lazy() {
+    let cells = cells();
+    let consumed = Cell::new(0);
+    let input = cells.iter().copied().inspect(|_| {
+        consumed.set(consumed.get() + 1);
+    });
+
+    let dissolver = DissolverBuilder::new().build();
+    let mut rings = dissolver.dissolve_sorted(input, Resolution::Eight);
+    assert!(rings.next().is_some());
+    assert!(consumed.get() < cells.len(), "ring emitted early");
+    assert_eq!(rings.count(), 2);
+}
+
+#[test]
+fn sorted_heterogeneous() {
+    let parent = center().parent(Resolution::Eight).expect("parent");
+    let mut cells = parent
+        .children(Resolution::Nine)
+        .skip(1)
+        .collect::<Vec<_>>();
+    let first = parent.children(Resolution::Nine).next().expect("child");
+    cells.extend(first.children(Resolution::Ten));
+    cells.sort_unstable();
+
+    let dissolver = DissolverBuilder::new()
+        .enable_heterogeneous_support(Resolution::Ten)
+        .build();
+    let result = dissolver
+        .dissolve_sorted(cells.iter().copied(), Resolution::Nine)
+        .collect::<Result<Vec<_>, _>>()
+        .expect("rings");
+    let expected = dissolver.dissolve(cells).expect("rings");
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(normalize(result), normalize(expected));
+}
+
+#[test]
+fn sorted_errors() {
+    let dissolver = DissolverBuilder::new().build();
+
+    let mut cells = cells();
+    cells.reverse();
+    let result = dissolver
+        .dissolve_sorted(cells, Resolution::Eight)
+        .collect::<Result<Vec<_>, _>>();
+    assert_eq!(result, Err(DissolutionError::UnsortedInput));
+
+    let result = dissolver
+        .dissolve_sorted([center(), center()], Resolution::Eight)
+        .collect::<Result<Vec<_>, _>>();
+    assert_eq!(result, Err(DissolutionError::DuplicateInput));
+
+    let parent = center().parent(Resolution::Nine).expect("parent");
+    let result = dissolver
+        .dissolve_sorted([parent], Resolution::Ten)
+        .collect::<Result<Vec<_>, _>>();
+    assert_eq!(result, Err(DissolutionError::UnsupportedResolution));
+
+    let mut rings =
+        dissolver.dissolve_sorted([parent, center()], Resolution::Eight);
+    assert_eq!(
+        rings.next(),
+        Some(Err(DissolutionError::UnsupportedResolution))
+    );
+    assert_eq!(rings.next(), None);
+}
+
+#[test]
+fn errors() {
+    let dissolver = DissolverBuilder::new().build();
+    let result = dissolver
+        .dissolve([center(), center()])
+        .map(Iterator::count);
+    assert_eq!(result, Err(DissolutionError::DuplicateInput));
+
+    let dissolver = DissolverBuilder::new()
+        .disable_duplicate_detection()
+        .enable_heterogeneous_support(Resolution::Nine)
+        .build();
+    let result = dissolver.dissolve([center()]).map(Iterator::count);
+    assert_eq!(result, Err(DissolutionError::UnsupportedResolution));
+
+    // Overlaps are detected in both orders.
+    let parent = center().parent(Resolution::Seven).expect("parent");
+    let dissolver = DissolverBuilder::new()
+        .enable_heterogeneous_support(Resolution::Ten)
+        .build();
+    for cells in [[parent, center()], [center(), parent]] {
+        let result = dissolver.dissolve(cells).map(Iterator::count);
+        assert_eq!(result, Err(DissolutionError::DuplicateInput));
+    }
+}
+
+#[test]
+fn sorted_sparse() {
+    // Lots of holes and islands, spread over many parents.
+    let parent = center().parent(Resolution::Six).expect("parent");
+    let cells = parent
+        .children(Resolution::Ten)
+        .enumerate()
+        .filter_map(|(idx, cell)| {
+            (idx % 7 != 0 && idx % 11 != 0).then_some(cell)
+        })
+        .collect::<Vec<_>>();
+
+    let dissolver = DissolverBuilder::new().build();
+    let expected = dissolver.dissolve(cells.iter().copied()).expect("rings");
+    let result = dissolver
+        .dissolve_sorted(cells, Resolution::Eight)
+        .collect::<Result<Vec<_>, _>>()
+        .expect("rings");
+
+    assert_eq!(normalize(result), normalize(expected));
+}
+
+#[test]
+fn sorted_long_rings() {
+    // An annulus: two long rings, bordering every batch.
+    let mut cells = center().grid_ring::<Vec<_>>(15);
+    cells.extend(center().grid_ring::<Vec<_>>(16));
+    cells.sort_unstable();
+
+    let dissolver = DissolverBuilder::new().build();
+    let expected = dissolver.dissolve(cells.iter().copied()).expect("rings");
+    let expected = normalize(expected);
+    assert_eq!(expected.len(), 2);
+
+    for resolution in [Resolution::Eight, Resolution::Nine, Resolution::Ten] {
+        let result = dissolver
+            .dissolve_sorted(cells.iter().copied(), resolution)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("rings");
+        assert_eq!(normalize(result), expected, "resolution {resolution}");
+    }
+}
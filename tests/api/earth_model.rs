@@ -0,0 +1,121 @@
+use h3o::{CellIndex, DirectedEdgeIndex, EarthModel, LatLng, Resolution};
+
+#[test]
+fn default_is_sphere() {
+    assert_eq!(EarthModel::default(), EarthModel::Sphere);
+}
+
+#[test]
+fn sphere_is_unchanged() {
+    let src = LatLng::new(48.864716, 2.349014).expect("src");
+    let dst = LatLng::new(31.224361, 121.469170).expect("dst");
+    assert_eq!(
+        src.distance_m_with(dst, EarthModel::Sphere),
+        src.distance_m(dst)
+    );
+    assert_eq!(
+        src.distance_km_with(dst, EarthModel::Sphere),
+        src.distance_km(dst)
+    );
+
+    let cell = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    assert_eq!(cell.area_m2_with(EarthModel::Sphere), cell.area_m2());
+    assert_eq!(cell.area_km2_with(EarthModel::Sphere), cell.area_km2());
+
+    let edge = DirectedEdgeIndex::try_from(0x13a194e699ab7fff).expect("edge");
+    assert_eq!(edge.length_m_with(EarthModel::Sphere), edge.length_m());
+    assert_eq!(edge.length_km_with(EarthModel::Sphere), edge.length_km());
+
+    for resolution in Resolution::range(Resolution::Zero, Resolution::Fifteen) {
+        assert_eq!(
+            resolution.edge_length_m_with(EarthModel::Sphere),
+            resolution.edge_length_m()
+        );
+        assert_eq!(
+            resolution.area_m2_with(EarthModel::Sphere),
+            resolution.area_m2()
+        );
+    }
+}
+
+#[test]
+fn geodesic_inverse() {
+    // Reference values from GeographicLib.
+    let jfk = LatLng::new(40.64, -73.78).expect("jfk");
+    let dst = LatLng::new(32.621100463725796, 49.052487092959836).expect("dst");
+    let distance = jfk.distance_m_with(dst, EarthModel::Wgs84);
+    let azimuth = jfk.azimuth_with(dst, EarthModel::Wgs84);
+    assert!((distance - 10e6).abs() < 1e-6, "{distance}");
+    assert!((azimuth - 45.).abs() < 1e-9, "{azimuth}");
+
+    // Quarter meridian.
+    let equator = LatLng::new(0., 0.).expect("equator");
+    let pole = LatLng::new(90., 0.).expect("pole");
+    let distance = equator.distance_m_with(pole, EarthModel::Wgs84);
+    assert!((distance - 10_001_965.729_312).abs() < 1e-3, "{distance}");
+    assert_eq!(equator.azimuth_with(pole, EarthModel::Wgs84), 0.);
+}
+
+#[test]
+fn nearly_antipodal() {
+    let src = LatLng::new(0., 0.).expect("src");
+    let dst = LatLng::new(0.5, 179.7).expect("dst");
+    let ellipsoid = src.distance_m_with(dst, EarthModel::Wgs84);
+    let sphere = src.distance_m(dst);
+
+    assert!(ellipsoid.is_finite());
+    assert!((ellipsoid / sphere - 1.).abs() < 0.005, "{ellipsoid}");
+    assert_eq!(src.distance_m_with(src, EarthModel::Wgs84), 0.);
+}
+
+#[test]
+fn azimuth() {
+    let src = LatLng::new(10., 10.).expect("src");
+    for (lat, lng, expected) in [
+        (20., 10., 0.),
+        (10., 11., 90.),
+        (0., 10., 180.),
+        (10., 9., 270.),
+    ] {
+        let dst = LatLng::new(lat, lng).expect("dst");
+        for model in [EarthModel::Sphere, EarthModel::Wgs84] {
+            let azimuth = src.azimuth_with(dst, model);
+            assert!((0. ..360.).contains(&azimuth));
+            assert!((azimuth - expected).abs() < 0.1, "{azimuth} {model:?}");
+        }
+    }
+}
+
+#[test]
+fn cell_area() {
+    // The authalic sphere preserves the total area.
+    let total = CellIndex::base_cells()
+        .map(|cell| cell.area_m2_with(EarthModel::Wgs84))
+        .sum::<f64>();
+    let expected = CellIndex::base_cells().map(CellIndex::area_m2).sum::<f64>();
+    assert!((total / expected - 1.).abs() < 1e-12, "{total}");
+
+    // But, compared to the sphere, cells are larger near the poles and smaller
+    // near the equator.
+    let polar = LatLng::new(89., 0.)
+        .expect("polar")
+        .to_cell(Resolution::Five);
+    let equatorial = LatLng::new(0., 0.).expect("eq").to_cell(Resolution::Five);
+    assert!(polar.area_m2_with(EarthModel::Wgs84) > polar.area_m2());
+    assert!(equatorial.area_m2_with(EarthModel::Wgs84) < equatorial.area_m2());
+    for cell in [polar, equatorial] {
+        let ratio = cell.area_m2_with(EarthModel::Wgs84) / cell.area_m2();
+        assert!((ratio - 1.).abs() < 0.01, "{cell}: {ratio}");
+    }
+}
+
+#[test]
+fn edge_length() {
+    let cell = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    for edge in cell.edges() {
+        let ellipsoid = edge.length_m_with(EarthModel::Wgs84);
+        let ratio = ellipsoid / edge.length_m();
+        assert!((ratio - 1.).abs() < 0.005, "{edge}: {ratio}");
+        assert_eq!(edge.length_km_with(EarthModel::Wgs84), ellipsoid / 1000.);
+    }
+}
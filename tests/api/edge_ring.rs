@@ -0,0 +1,136 @@
+use h3o::{CellIndex, LatLng, Resolution, error::DissolutionError};
+use std::collections::BTreeSet;
+
+fn center() -> CellIndex {
+    CellIndex::try_from(0x8a1fb46622dffff).expect("center")
+}
+
+#[test]
+fn single_cell() {
+    let rings = CellIndex::outline([center()]).expect("outline");
+
+    assert_eq!(rings.len(), 1);
+    assert!(!rings[0].is_hole());
+    assert_eq!(
+        rings[0].edges().iter().copied().collect::<BTreeSet<_>>(),
+        center().edges().collect::<BTreeSet<_>>()
+    );
+    assert_eq!(
+        rings[0].vertexes().iter().copied().collect::<BTreeSet<_>>(),
+        center().vertexes().collect::<BTreeSet<_>>()
+    );
+}
+
+#[test]
+fn edges_are_chained() {
+    let cells = center().grid_disk::<Vec<_>>(2);
+    let rings = CellIndex::outline(cells).expect("outline");
+    let ring = &rings[0];
+
+    assert_eq!(ring.edges().len(), 30);
+    for (edge, &vertex) in ring.edges().iter().zip(ring.vertexes()) {
+        assert_eq!(edge.boundary()[0], LatLng::from(vertex));
+    }
+}
+
+#[test]
+fn perimeter() {
+    let cells = center().grid_disk::<Vec<_>>(1);
+    let rings = CellIndex::outline(cells).expect("outline");
+    let expected = rings[0]
+        .edges()
+        .iter()
+        .map(|edge| edge.length_m())
+        .sum::<f64>();
+
+    assert_eq!(rings.len(), 1);
+    assert_eq!(rings[0].edges().len(), 18);
+    assert_eq!(rings[0].length_m(), expected);
+    float_eq::assert_float_eq!(
+        rings[0].length_km() * 1000.,
+        rings[0].length_m(),
+        r2nd <= 1e-12
+    );
+}
+
+#[test]
+fn hole() {
+    let cells = center().grid_ring::<Vec<_>>(1);
+    let rings = CellIndex::outline(cells).expect("outline");
+
+    assert_eq!(rings.len(), 2);
+    let hole = rings.iter().find(|ring| ring.is_hole()).expect("hole");
+    let outer = rings.iter().find(|ring| !ring.is_hole()).expect("outer");
+    assert_eq!(hole.edges().len(), 6);
+    assert_eq!(outer.edges().len(), 18);
+    // The hole is the center cell, seen from its neighbors.
+    assert_eq!(
+        hole.vertexes().iter().copied().collect::<BTreeSet<_>>(),
+        center().vertexes().collect::<BTreeSet<_>>()
+    );
+}
+
+#[test]
+fn shared_edges() {
+    let region = center().grid_disk::<Vec<_>>(1);
+    let neighbors = center().grid_ring::<Vec<_>>(2);
+    let inner = CellIndex::outline(region).expect("inner");
+    let outer = CellIndex::outline(neighbors).expect("outer");
+    let hole = outer.iter().find(|ring| ring.is_hole()).expect("hole");
+
+    let shared = inner[0].shared_edges(hole);
+    assert_eq!(shared.len(), 18);
+    assert!(shared.iter().all(|edge| !hole.edges().contains(edge)));
+    assert_eq!(
+        hole.shared_edges(&inner[0])
+            .into_iter()
+            .map(|edge| edge.destination())
+            .collect::<BTreeSet<_>>(),
+        inner[0]
+            .edges()
+            .iter()
+            .map(|edge| edge.origin())
+            .collect::<BTreeSet<_>>()
+    );
+
+    // Disjoint regions share nothing.
+    let far = CellIndex::outline(center().grid_ring::<Vec<_>>(4)).expect("far");
+    assert!(
+        far.iter()
+            .all(|ring| inner[0].shared_edges(ring).is_empty())
+    );
+}
+
+#[test]
+fn pentagon() {
+    let pentagon = Resolution::Five.pentagons().next().expect("pentagon");
+    let rings = CellIndex::outline([pentagon]).expect("outline");
+    assert_eq!(rings.len(), 1);
+    assert_eq!(rings[0].edges().len(), 5);
+    assert!(!rings[0].is_hole());
+
+    let ring = pentagon.grid_ring::<Vec<_>>(1);
+    let rings = CellIndex::outline(ring).expect("outline");
+    assert_eq!(rings.len(), 2);
+    let hole = rings.iter().find(|ring| ring.is_hole()).expect("hole");
+    assert_eq!(hole.edges().len(), 5);
+}
+
+#[test]
+fn heterogeneous() {
+    let parent = center().parent(Resolution::Zero).expect("parent");
+    let result = CellIndex::outline([parent, center()]);
+    assert_eq!(result, Err(DissolutionError::UnsupportedResolution));
+}
+
+#[test]
+fn duplicate() {
+    let result = CellIndex::outline([center(), center()]);
+    assert_eq!(result, Err(DissolutionError::DuplicateInput));
+}
+
+#[test]
+fn empty() {
+    let rings = CellIndex::outline(std::iter::empty()).expect("outline");
+    assert!(rings.is_empty());
+}
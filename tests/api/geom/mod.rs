@@ -1,4 +1,5 @@
 mod plotter;
+mod polygon_index;
 mod solvent;
 mod tiler;
 mod to_geo;
@@ -0,0 +1,159 @@
+use super::utils::load_polygon;
+use geo::{BoundingRect as _, Coord, Intersects as _, Polygon, polygon};
+use h3o::{
+    LatLng, Resolution,
+    geom::{OverlapCandidate, PolygonIndexBuilder},
+};
+
+/// Regular grid of points over the bounding box of a polygon (in degrees).
+fn points(polygon: &Polygon, count: usize) -> Vec<LatLng> {
+    let bbox = polygon.bounding_rect().expect("bbox");
+    let (width, height) = (bbox.width(), bbox.height());
+    (0..count)
+        .flat_map(|y| (0..count).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let lng = bbox.min().x + width * (x as f64 + 0.5) / count as f64;
+            let lat = bbox.min().y + height * (y as f64 + 0.5) / count as f64;
+            LatLng::new(lat, lng).expect("point")
+        })
+        .collect()
+}
+
+fn contains(polygon: &Polygon, point: LatLng) -> bool {
+    let mut polygon = polygon.clone();
+    geo::ToRadians::to_radians_in_place(&mut polygon);
+    polygon.intersects(&Coord {
+        x: point.lng_radians(),
+        y: point.lat_radians(),
+    })
+}
+
+#[test]
+fn containing() {
+    let paris = load_polygon("Paris");
+    let mut index = PolygonIndexBuilder::new(Resolution::Nine).build();
+    let id = index.add(paris.clone()).expect("add");
+    assert_eq!(id, 0);
+    assert_eq!(index.len(), 1);
+
+    for point in points(&paris, 50) {
+        let expected = contains(&paris, point).then_some(id);
+        let result = index.containing(point).collect::<Vec<_>>();
+        assert_eq!(result, Vec::from_iter(expected), "{point}");
+    }
+}
+
+#[test]
+fn overlapping_polygons() {
+    let paris = load_polygon("Paris");
+    let square = polygon![
+        (x: 2.3, y: 48.8), (x: 2.5, y: 48.8), (x: 2.5, y: 48.9), (x: 2.3, y: 48.9),
+    ];
+    let mut index = PolygonIndexBuilder::new(Resolution::Eight).build();
+    let ids = index
+        .add_batch([paris.clone(), square.clone()])
+        .expect("add batch");
+    assert_eq!(ids, 0..2);
+
+    let points = points(&paris, 40);
+    let result = index
+        .join_points(points.iter().copied())
+        .collect::<Vec<_>>();
+    let expected = points
+        .iter()
+        .enumerate()
+        .flat_map(|(rank, &point)| {
+            [&paris, &square]
+                .into_iter()
+                .enumerate()
+                .filter(move |(_, polygon)| contains(polygon, point))
+                .map(move |(id, _)| (rank, id))
+        })
+        .collect::<Vec<_>>();
+    let mut sorted = result.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, expected);
+    // Both polygons are matched for some points.
+    assert!(result.windows(2).any(|pair| pair[0].0 == pair[1].0));
+}
+
+#[test]
+fn transmeridian() {
+    let polygon = load_polygon("Transmeridian");
+    let mut index = PolygonIndexBuilder::new(Resolution::Six).build();
+    index.add(polygon).expect("add");
+
+    let east = LatLng::new(0.1, 179.9).expect("east");
+    let west = LatLng::new(0.1, -179.9).expect("west");
+    let far = LatLng::new(0.1, 0.).expect("far");
+    assert_eq!(index.containing(east).count(), 1);
+    assert_eq!(index.containing(west).count(), 1);
+    assert_eq!(index.containing(far).count(), 0);
+}
+
+#[test]
+fn overlap_candidates() {
+    let paris = load_polygon("Paris");
+    let inside = polygon![
+        (x: 2.34, y: 48.85), (x: 2.36, y: 48.85), (x: 2.36, y: 48.86), (x: 2.34, y: 48.86),
+    ];
+    let away = polygon![
+        (x: 10., y: 10.), (x: 10.1, y: 10.), (x: 10.1, y: 10.1), (x: 10., y: 10.1),
+    ];
+    let mut lhs = PolygonIndexBuilder::new(Resolution::Seven).build();
+    lhs.add_batch([paris, away.clone()]).expect("add");
+    let mut rhs = PolygonIndexBuilder::new(Resolution::Ten).build();
+    rhs.add_batch([inside, away]).expect("add");
+
+    let result = lhs.overlap_candidates(&rhs);
+    assert_eq!(
+        result,
+        vec![
+            OverlapCandidate {
+                left: 0,
+                right: 0,
+                is_certain: true,
+            },
+            OverlapCandidate {
+                left: 1,
+                right: 1,
+                is_certain: true,
+            },
+        ]
+    );
+
+    // Symmetric.
+    let reversed = rhs.overlap_candidates(&lhs);
+    assert_eq!(reversed.len(), result.len());
+    assert!(reversed.iter().zip(&result).all(|(a, b)| {
+        a.left == b.right && a.right == b.left && a.is_certain == b.is_certain
+    }));
+
+    let empty = PolygonIndexBuilder::new(Resolution::Seven).build();
+    assert!(empty.is_empty());
+    assert!(lhs.overlap_candidates(&empty).is_empty());
+}
+
+#[test]
+fn touching_polygons() {
+    // Adjacent squares: boundary cells are shared, but no interior ones.
+    let west = polygon![
+        (x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.),
+    ];
+    let east = polygon![
+        (x: 1., y: 0.), (x: 2., y: 0.), (x: 2., y: 1.), (x: 1., y: 1.),
+    ];
+    let mut lhs = PolygonIndexBuilder::new(Resolution::Six).build();
+    lhs.add(west).expect("add");
+    let mut rhs = PolygonIndexBuilder::new(Resolution::Six).build();
+    rhs.add(east).expect("add");
+
+    assert_eq!(
+        lhs.overlap_candidates(&rhs),
+        vec![OverlapCandidate {
+            left: 0,
+            right: 0,
+            is_certain: false,
+        }]
+    );
+}
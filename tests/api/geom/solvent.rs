@@ -4,6 +4,7 @@ use approx::{assert_relative_eq, relative_eq};
 use geo::{Area, BooleanOps, LineString, MultiPolygon, Polygon, polygon};
 use h3o::{
     CellIndex, Resolution,
+    error::DissolutionError,
     geom::{SolventBuilder, TilerBuilder},
 };
 
@@ -734,6 +735,82 @@ fn non_deterministic_output() {
     }
 }
 
+#[test]
+fn outline() {
+    let index = CellIndex::try_from(0x89283470803ffff).expect("index");
+    let center = index.center_child(Resolution::Ten).expect("center");
+    let cells = index
+        .children(Resolution::Ten)
+        .filter(|&cell| cell != center)
+        .collect::<Vec<_>>();
+
+    let solvent = SolventBuilder::new().build();
+    let geom = solvent.dissolve(cells.iter().copied()).expect("geometry");
+    let rings = solvent.outline(cells.iter().copied()).expect("outline");
+
+    assert_eq!(rings.len(), 2);
+    let (holes, exteriors) =
+        rings.iter().partition::<Vec<_>, _>(|ring| ring.is_hole());
+    assert_eq!(exteriors[0].edges().len() + 1, geom.0[0].exterior().0.len());
+    assert_eq!(holes[0].edges().len() + 1, geom.0[0].interiors()[0].0.len());
+
+    let solvent = SolventBuilder::new()
+        .enable_heterogeneous_support(Resolution::Ten)
+        .build();
+    let mut compacted = cells.clone();
+    CellIndex::compact(&mut compacted).expect("compacted");
+    let result = solvent.outline(compacted).expect("heterogeneous");
+    assert_eq!(result, rings);
+
+    let result = solvent.outline([index, center]);
+    assert_eq!(result, Err(DissolutionError::DuplicateInput));
+}
+
+#[test]
+fn simplification() {
+    let index = CellIndex::try_from(0x89283470803ffff).expect("index");
+    let center = index.center_child(Resolution::Ten).expect("center");
+    let cells = index
+        .children(Resolution::Thirteen)
+        .filter(|&cell| cell.parent(Resolution::Ten).expect("parent") != center)
+        .collect::<Vec<_>>();
+    // ~5m, in radians.
+    let tolerance = 5. / 6_371_007.180_918_475;
+
+    let expected = SolventBuilder::new()
+        .build()
+        .dissolve(cells.iter().copied())
+        .expect("geometry");
+    let result = SolventBuilder::new()
+        .enable_simplification(tolerance)
+        .expect("tolerance")
+        .build()
+        .dissolve(cells.iter().copied())
+        .expect("simplified");
+
+    assert_eq!(result.0.len(), 1);
+    assert_eq!(result.0[0].interiors().len(), 1);
+    assert!(
+        result.0[0].exterior().0.len() < expected.0[0].exterior().0.len() / 4
+    );
+    assert_relative_eq!(
+        result.unsigned_area(),
+        expected.unsigned_area(),
+        epsilon = expected.unsigned_area() * 1e-2
+    );
+
+    let mut compacted = cells.clone();
+    CellIndex::compact(&mut compacted).expect("compacted");
+    let heterogeneous = SolventBuilder::new()
+        .enable_heterogeneous_support(Resolution::Thirteen)
+        .enable_simplification(tolerance)
+        .expect("tolerance")
+        .build()
+        .dissolve(compacted)
+        .expect("heterogeneous");
+    assert_eq!(heterogeneous, result);
+}
+
 macro_rules! grid_disk {
     ($name:ident, $base_cell:literal, $resolution:literal) => {
         #[test]
@@ -798,6 +875,77 @@ grid_disk!(grid_disk_pentagon_res13, 0x8031fffffffffff, 13);
 grid_disk!(grid_disk_pentagon_res14, 0x8031fffffffffff, 14);
 grid_disk!(grid_disk_pentagon_res15, 0x8031fffffffffff, 15);
 
+#[test]
+fn metrics() {
+    let index = CellIndex::try_from(0x8a1fb46622dffff).expect("index");
+    let solvent = SolventBuilder::new().build();
+
+    // A single cell.
+    let result = solvent.metrics([index]).expect("single");
+    assert_eq!(result.len(), 1);
+    let expected = index.metrics();
+    assert_relative_eq!(result[0].area_m2(), expected.area_m2());
+    assert_relative_eq!(
+        result[0].perimeter_m(),
+        expected.perimeter_m(),
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        result[0].min_edge_length_m(),
+        expected.min_edge_length_m(),
+        epsilon = 1e-9
+    );
+    assert!(result[0].centroid().distance_m(expected.centroid()) < 1e-6);
+
+    // A ring around a hole, and an isolated cell.
+    let ring = index.grid_ring::<Vec<_>>(1);
+    let island = index.grid_ring::<Vec<_>>(3)[0];
+    let cells = ring.iter().copied().chain([island]).collect::<Vec<_>>();
+    let result = solvent.metrics(cells.iter().copied()).expect("regions");
+    assert_eq!(result.len(), 2);
+    let (donut, islet) = if ring.iter().min() < Some(&island) {
+        (result[0], result[1])
+    } else {
+        (result[1], result[0])
+    };
+
+    let rings = CellIndex::outline(ring.iter().copied()).expect("outline");
+    let length = rings.iter().map(|ring| ring.length_m()).sum::<f64>();
+    let area = ring.iter().map(|cell| cell.area_m2()).sum::<f64>();
+    assert_relative_eq!(donut.perimeter_m(), length, epsilon = 1e-6);
+    assert_relative_eq!(donut.area_m2(), area, epsilon = 1e-6);
+    assert!(donut.centroid().distance_m(index.into()) < 1.);
+    assert!(donut.compactness() < expected.compactness());
+    assert_relative_eq!(islet.area_m2(), island.area_m2());
+
+    // Heterogeneous input.
+    let children = index.children(Resolution::Twelve).collect::<Vec<_>>();
+    let homogeneous = solvent.metrics(children).expect("homogeneous");
+    let heterogeneous = SolventBuilder::new()
+        .enable_heterogeneous_support(Resolution::Twelve)
+        .build()
+        .metrics([index])
+        .expect("heterogeneous");
+    assert_eq!(homogeneous, heterogeneous);
+
+    assert_eq!(
+        solvent.metrics([index, index]),
+        Err(DissolutionError::DuplicateInput)
+    );
+    assert_eq!(
+        solvent.metrics([index, island.parent(Resolution::Nine).expect("p")]),
+        Err(DissolutionError::UnsupportedResolution)
+    );
+    let child = index.center_child(Resolution::Twelve).expect("child");
+    assert_eq!(
+        SolventBuilder::new()
+            .enable_heterogeneous_support(Resolution::Twelve)
+            .build()
+            .metrics([child, index]),
+        Err(DissolutionError::DuplicateInput)
+    );
+}
+
 // -----------------------------------------------------------------------------
 
 /// Returns true if two LineString are equivalent.
@@ -867,3 +1015,13 @@ fn assert_hetero_equal_homo(
     let intersection_area = result.union(expected).unsigned_area();
     assert_eq!(intersection_area / union_area, 1., "geom mismatch")
 }
+
+#[test]
+fn invalid_simplification_tolerance() {
+    assert!(SolventBuilder::new().enable_simplification(-1.).is_err());
+    assert!(
+        SolventBuilder::new()
+            .enable_simplification(f64::NAN)
+            .is_err()
+    );
+}
@@ -2,6 +2,7 @@ use super::utils::load_polygon;
 use geo::{LineString, MultiPolygon, Polygon, Rect, coord, polygon};
 use h3o::{
     CellIndex, LatLng, Resolution,
+    error::ResolutionMismatch,
     geom::{ContainmentMode, TilerBuilder},
 };
 use std::{collections::BTreeSet, f64::consts::PI};
@@ -247,6 +248,48 @@ fn annotated_coverage_with_contains() {
     assert_eq!(overlap, 0);
 }
 
+#[test]
+fn bitmap() {
+    let mut tiler = TilerBuilder::new(Resolution::Eight)
+        .containment_mode(ContainmentMode::IntersectsBoundary)
+        .build();
+    tiler
+        .add(load_polygon("Paris"))
+        .expect("failed to add polygon");
+    let expected = tiler.clone().into_coverage().collect::<BTreeSet<_>>();
+    let result = tiler.into_bitmap();
+
+    assert_eq!(result.len(), expected.len() as u64);
+    assert!(result.iter().eq(expected.iter().copied()));
+}
+
+#[test]
+fn key_ranges() {
+    let mut tiler = TilerBuilder::new(Resolution::Eight)
+        .containment_mode(ContainmentMode::IntersectsBoundary)
+        .build();
+    tiler
+        .add(load_polygon("Paris"))
+        .expect("failed to add polygon");
+    let expected = tiler.clone().into_coverage().collect::<BTreeSet<_>>();
+    let ranges = tiler
+        .clone()
+        .into_key_ranges(Resolution::Eight)
+        .expect("ranges");
+
+    assert!(ranges.len() < expected.len());
+    assert!(expected.iter().all(|cell| {
+        let key = u64::from(*cell);
+        ranges.iter().any(|range| range.contains(&key))
+    }));
+
+    // Ranges don't depend on the target resolution.
+    let result = tiler.clone().into_key_ranges(Resolution::Ten);
+    assert_eq!(result.map(|ranges| ranges.len()), Ok(ranges.len()));
+    let result = tiler.into_key_ranges(Resolution::Seven);
+    assert_eq!(result, Err(ResolutionMismatch));
+}
+
 // -----------------------------------------------------------------------------
 
 macro_rules! world_test {
@@ -0,0 +1,248 @@
+use h3o::{CellIndex, CoordIJ, HexKernel, HexRaster};
+use std::collections::{BTreeMap, BTreeSet};
+
+fn anchor() -> CellIndex {
+    CellIndex::try_from(0x8a1fb46622dffff).expect("anchor")
+}
+
+fn to_set(raster: &HexRaster<()>) -> BTreeSet<CellIndex> {
+    raster
+        .cells()
+        .map(|res| res.expect("cell").0)
+        .collect::<BTreeSet<_>>()
+}
+
+#[test]
+fn roundtrip() {
+    let cells = anchor()
+        .grid_disk::<Vec<_>>(3)
+        .into_iter()
+        .map(|cell| (cell, u64::from(cell)))
+        .collect::<BTreeMap<_, _>>();
+    let raster =
+        HexRaster::from_cells(anchor(), cells.clone()).expect("raster");
+
+    assert_eq!(raster.count(), cells.len());
+    assert_eq!(raster.width(), 7);
+    assert_eq!(raster.height(), 7);
+
+    let result = raster
+        .into_cells()
+        .collect::<Result<BTreeMap<_, _>, _>>()
+        .expect("cells");
+    assert_eq!(result, cells);
+}
+
+#[test]
+fn empty() {
+    let raster = HexRaster::<u8>::from_cells(anchor(), std::iter::empty())
+        .expect("raster");
+
+    assert_eq!(raster.count(), 0);
+    assert_eq!(raster.into_cells().count(), 0);
+}
+
+#[test]
+fn resolution_mismatch() {
+    let cell = anchor()
+        .center_child(h3o::Resolution::Eleven)
+        .expect("child");
+    let result = HexRaster::from_cells(anchor(), [(cell, 1)]);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn insert_grows() {
+    let mut raster =
+        HexRaster::new(anchor(), CoordIJ::new(0, 0), 1, 1).expect("raster");
+    let origin = raster.coord_of(anchor()).expect("coord");
+    let far = CoordIJ::new(origin.i + 3, origin.j - 2);
+
+    assert_eq!(raster.insert(origin, 1), Ok(None));
+    assert_eq!(raster.insert(far, 2), Ok(None));
+    assert_eq!(raster.insert(far, 3), Ok(Some(2)));
+
+    assert_eq!(raster.get(origin), Some(&1));
+    assert_eq!(raster.get(far), Some(&3));
+    assert_eq!(raster.count(), 2);
+    assert_eq!(raster.remove(far), Some(3));
+    assert_eq!(raster.count(), 1);
+}
+
+#[test]
+fn neighbors_match_grid_disk() {
+    let cells = anchor().grid_disk::<Vec<_>>(2);
+    let raster =
+        HexRaster::from_cells(anchor(), cells.iter().map(|&c| (c, ())))
+            .expect("raster");
+    let origin = raster.coord_of(anchor()).expect("coord");
+
+    let result = raster
+        .neighbors(origin)
+        .map(|(coord, ())| raster.cell_at(coord).expect("cell"))
+        .collect::<BTreeSet<_>>();
+    let expected = anchor()
+        .grid_disk::<BTreeSet<_>>(1)
+        .into_iter()
+        .filter(|&cell| cell != anchor())
+        .collect::<BTreeSet<_>>();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn dilate() {
+    let cells = [anchor(), anchor().grid_ring::<Vec<_>>(3)[0]];
+    let raster =
+        HexRaster::from_cells(anchor(), cells.iter().map(|&c| (c, ())))
+            .expect("raster");
+
+    let result = to_set(&raster.dilate().expect("dilate"));
+    let expected = cells
+        .iter()
+        .flat_map(|cell| cell.grid_disk::<Vec<_>>(1))
+        .collect::<BTreeSet<_>>();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn dilate_max() {
+    let raster =
+        HexRaster::from_cells(anchor(), [(anchor(), 1)]).expect("raster");
+    let origin = raster.coord_of(anchor()).expect("coord");
+    let mut raster = raster.dilate().expect("dilate");
+    raster.insert(origin, 0).expect("insert");
+
+    let result = raster.dilate().expect("dilate");
+    assert_eq!(result.get(origin), Some(&1));
+}
+
+#[test]
+fn erode() {
+    let cells = anchor().grid_disk::<Vec<_>>(2);
+    let raster =
+        HexRaster::from_cells(anchor(), cells.iter().map(|&c| (c, ())))
+            .expect("raster");
+
+    let result = to_set(&raster.erode());
+    let expected = anchor().grid_disk::<BTreeSet<_>>(1);
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn label_components() {
+    let ring = anchor().grid_ring::<Vec<_>>(2);
+    let cells = std::iter::once(anchor())
+        .chain(ring.iter().copied())
+        .collect::<Vec<_>>();
+    let raster =
+        HexRaster::from_cells(anchor(), cells.iter().map(|&c| (c, ())))
+            .expect("raster");
+
+    let (labels, count) = raster.label_components();
+    assert_eq!(count, 2);
+
+    let origin = raster.coord_of(anchor()).expect("coord");
+    let center_label = labels.get(origin).copied();
+    for cell in ring {
+        let coord = raster.coord_of(cell).expect("coord");
+        assert_ne!(labels.get(coord).copied(), center_label);
+    }
+}
+
+#[test]
+fn flood_fill() {
+    // A ring of walls, flooding from the center only fills the inside.
+    let ring = anchor().grid_ring::<Vec<_>>(2);
+    let mut raster =
+        HexRaster::from_cells(anchor(), ring.iter().map(|&cell| (cell, false)))
+            .expect("raster");
+    let origin = raster.coord_of(anchor()).expect("coord");
+
+    let count = raster.flood_fill(origin, &true);
+
+    assert_eq!(count, 7);
+    let filled = raster
+        .iter()
+        .filter_map(|(coord, &value)| value.then_some(coord))
+        .map(|coord| raster.cell_at(coord).expect("cell"))
+        .collect::<BTreeSet<_>>();
+    assert_eq!(filled, anchor().grid_disk::<BTreeSet<_>>(1));
+}
+
+#[test]
+fn convolve() {
+    let cells = anchor().grid_disk_distances::<Vec<_>>(3);
+    let raster = HexRaster::from_cells(
+        anchor(),
+        cells
+            .iter()
+            .map(|&(cell, distance)| (cell, f64::from(distance))),
+    )
+    .expect("raster");
+    let kernel =
+        HexKernel::radial(
+            1,
+            |distance| {
+                if distance == 0 { 0.5 } else { 0.5 / 6. }
+            },
+        )
+        .expect("kernel");
+
+    let result = raster.convolve(&kernel);
+    let origin = raster.coord_of(anchor()).expect("coord");
+
+    // Center is 0, and its 6 neighbors are at distance 1.
+    float_eq::assert_float_eq!(
+        result.get(origin).copied().expect("value"),
+        0.5,
+        abs <= 1e-12
+    );
+    assert_eq!(result.count(), raster.count());
+}
+
+#[test]
+fn kernel() {
+    let kernel = HexKernel::radial(2, |_| 1.).expect("kernel").normalized();
+
+    assert_eq!(kernel.radius(), 2);
+    assert_eq!(kernel.iter().count(), 19);
+    float_eq::assert_float_eq!(
+        kernel.iter().map(|(_, weight)| weight).sum::<f64>(),
+        1.,
+        abs <= 1e-12
+    );
+    assert!(kernel.weight(CoordIJ::new(2, -2)).is_none());
+    assert!(kernel.weight(CoordIJ::new(2, 2)).is_some());
+}
+
+#[test]
+fn kernel_weight_overflow() {
+    let kernel = HexKernel::radial(2, |_| 1.).expect("kernel");
+
+    assert!(kernel.weight(CoordIJ::new(i32::MIN, 0)).is_none());
+    assert!(kernel.weight(CoordIJ::new(i32::MAX, -1)).is_none());
+}
+
+#[test]
+fn kernel_too_large() {
+    assert!(HexKernel::from_fn(u32::MAX, |_| 1.).is_err());
+    assert!(HexKernel::radial(HexKernel::MAX_RADIUS + 1, |_| 1.).is_err());
+    assert!(HexKernel::radial(HexKernel::MAX_RADIUS, |_| 1.).is_ok());
+}
+
+#[test]
+fn extent_overflow() {
+    let max = CoordIJ::new(i32::MAX, 0);
+
+    assert!(HexRaster::<u8>::new(anchor(), max, 2, 1).is_err());
+    assert!(HexRaster::<u8>::new(anchor(), max, 1, usize::MAX).is_err());
+    assert!(HexRaster::<u8>::new(anchor(), max, usize::MAX, 2).is_err());
+
+    let mut raster = HexRaster::new(anchor(), max, 1, 1).expect("raster");
+    assert_eq!(raster.insert(max, 1), Ok(None));
+    assert!(raster.dilate().is_err());
+}
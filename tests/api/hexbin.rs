@@ -0,0 +1,210 @@
+use super::rng::SplitMix64;
+use h3o::{CellStats, HexBin, LatLng, Resolution, error::ResolutionMismatch};
+
+/// Deterministic pseudo-random points and values around Paris.
+fn points(count: usize) -> Vec<(LatLng, f64)> {
+    let mut rng = SplitMix64::new();
+    (0..count)
+        .map(|_| {
+            let lat = 48.8566 + rng.next_signed() * 0.5;
+            let lng = 2.3522 + rng.next_signed() * 0.5;
+            let value = rng.next_signed() * 100.;
+            (LatLng::new(lat, lng).expect("point"), value)
+        })
+        .collect()
+}
+
+fn assert_close(lhs: Option<f64>, rhs: Option<f64>) {
+    let (lhs, rhs) = (lhs.expect("lhs"), rhs.expect("rhs"));
+    assert!(
+        (lhs - rhs).abs() <= 1e-9 * (1. + rhs.abs()),
+        "{lhs} vs {rhs}"
+    );
+}
+
+fn assert_same_stats(lhs: &CellStats, rhs: &CellStats) {
+    assert_eq!(lhs.count(), rhs.count());
+    assert_eq!(lhs.min(), rhs.min());
+    assert_eq!(lhs.max(), rhs.max());
+    assert_close(Some(lhs.sum()), Some(rhs.sum()));
+    assert_close(lhs.mean(), rhs.mean());
+    assert_close(lhs.variance(), rhs.variance());
+    for q in [0., 0.1, 0.5, 0.9, 1.] {
+        assert_eq!(lhs.quantile(q), rhs.quantile(q));
+    }
+}
+
+#[test]
+fn statistics() {
+    let point = LatLng::new(48.8566, 2.3522).expect("point");
+    let values = [4., -2., 7.5, 0., 10., 3.];
+    let bins = HexBin::from_points(
+        Resolution::Nine,
+        values.iter().map(|&value| (point, value)),
+    );
+    assert_eq!(bins.len(), 1);
+
+    let stats = bins.get(point.to_cell(Resolution::Nine)).expect("stats");
+    let mean = values.iter().sum::<f64>() / 6.;
+    let variance = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / 6.;
+    assert_eq!(stats.count(), 6);
+    assert_close(Some(stats.sum()), Some(22.5));
+    assert_eq!(stats.min(), Some(-2.));
+    assert_eq!(stats.max(), Some(10.));
+    assert_close(stats.mean(), Some(mean));
+    assert_close(stats.variance(), Some(variance));
+    assert_close(stats.std_dev(), Some(variance.sqrt()));
+}
+
+#[test]
+fn empty() {
+    let bins = HexBin::new(Resolution::Nine);
+    assert!(bins.is_empty());
+
+    let cell = LatLng::new(48.8566, 2.3522)
+        .expect("point")
+        .to_cell(Resolution::Nine);
+    assert!(bins.get(cell).is_none());
+
+    let stats = bins.stats(cell).expect("stats");
+    assert_eq!(stats.count(), 0);
+    assert_eq!(stats.mean(), None);
+    assert_eq!(stats.variance(), None);
+    assert_eq!(stats.quantile(0.5), None);
+}
+
+#[test]
+fn nan_values_are_ignored() {
+    let point = LatLng::new(48.8566, 2.3522).expect("point");
+    let bins = HexBin::from_points(
+        Resolution::Nine,
+        [(point, 1.), (point, f64::NAN), (point, 3.)],
+    );
+
+    let stats = bins.get(point.to_cell(Resolution::Nine)).expect("stats");
+    assert_eq!(stats.count(), 2);
+    assert_eq!(stats.mean(), Some(2.));
+}
+
+#[test]
+fn infinite_values_are_ignored() {
+    let point = LatLng::new(48.8566, 2.3522).expect("point");
+    let bins = HexBin::from_points(
+        Resolution::Nine,
+        [
+            (point, 1.),
+            (point, f64::INFINITY),
+            (point, f64::NEG_INFINITY),
+        ],
+    );
+
+    let stats = bins.get(point.to_cell(Resolution::Nine)).expect("stats");
+    assert_eq!(stats.count(), 1);
+    assert_eq!(stats.mean(), Some(1.));
+    assert_eq!(stats.variance(), Some(0.));
+    assert_eq!(stats.quantile(0.5), Some(1.));
+}
+
+#[test]
+fn quantiles() {
+    let point = LatLng::new(48.8566, 2.3522).expect("point");
+    let mut values = (-500..=1500)
+        .map(|value| f64::from(value) * 0.37)
+        .collect::<Vec<_>>();
+    let bins = HexBin::from_points(
+        Resolution::Nine,
+        values.iter().map(|&value| (point, value)),
+    );
+    let stats = bins.get(point.to_cell(Resolution::Nine)).expect("stats");
+    values.sort_by(f64::total_cmp);
+
+    for q in [0., 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 1.] {
+        let rank = (q * (values.len() - 1) as f64) as usize;
+        let expected = values[rank];
+        let estimate = stats.quantile(q).expect("quantile");
+        assert!(
+            (estimate - expected).abs() <= 0.01 * expected.abs() + 1e-9,
+            "q={q}: {estimate} vs {expected}"
+        );
+    }
+    assert_eq!(stats.quantile(-0.1), None);
+    assert_eq!(stats.quantile(1.1), None);
+    assert_eq!(stats.quantile(0.), stats.min());
+    assert_eq!(stats.quantile(1.), stats.max());
+}
+
+#[test]
+fn roll_up() {
+    let points = points(5_000);
+    let fine = HexBin::from_points(Resolution::Eight, points.iter().copied());
+
+    for resolution in [Resolution::Eight, Resolution::Six, Resolution::Zero] {
+        let rolled = fine.roll_up(resolution).expect("roll-up");
+        // Aggregate each value directly in the ancestor of its fine cell.
+        let mut direct = HexBin::new(resolution);
+        for &(point, value) in &points {
+            let cell = point
+                .to_cell(Resolution::Eight)
+                .parent(resolution)
+                .expect("parent");
+            direct.insert(LatLng::from(cell), value);
+        }
+
+        assert_eq!(rolled.resolution(), resolution);
+        assert_eq!(rolled.len(), direct.len());
+        for (cell, stats) in direct.iter() {
+            assert_same_stats(rolled.get(cell).expect("rolled"), stats);
+            assert_same_stats(&fine.stats(cell).expect("stats"), stats);
+        }
+    }
+
+    assert_eq!(
+        fine.roll_up(Resolution::Nine).err(),
+        Some(ResolutionMismatch)
+    );
+}
+
+#[test]
+fn drill_down() {
+    let points = points(2_000);
+    let fine = HexBin::from_points(Resolution::Eight, points.iter().copied());
+    let coarse = fine.roll_up(Resolution::Five).expect("roll-up");
+
+    // The counts of the children add up to the count of their parent.
+    for (cell, stats) in coarse.iter() {
+        let count = cell
+            .children(Resolution::Eight)
+            .filter_map(|child| fine.get(child))
+            .map(CellStats::count)
+            .sum::<u64>();
+        assert_eq!(count, stats.count());
+    }
+
+    let cell = points[0].0.to_cell(Resolution::Nine);
+    assert_eq!(fine.stats(cell).err(), Some(ResolutionMismatch));
+}
+
+#[test]
+fn merge() {
+    let points = points(1_000);
+    let (lhs, rhs) = points.split_at(400);
+    let whole = HexBin::from_points(Resolution::Seven, points.iter().copied());
+
+    let mut merged =
+        HexBin::from_points(Resolution::Seven, lhs.iter().copied());
+    merged
+        .merge(&HexBin::from_points(Resolution::Seven, rhs.iter().copied()))
+        .expect("same resolution");
+
+    assert_eq!(merged.len(), whole.len());
+    for (cell, stats) in whole.iter() {
+        assert_same_stats(merged.get(cell).expect("merged"), stats);
+    }
+
+    let other = HexBin::new(Resolution::Eight);
+    assert_eq!(merged.merge(&other), Err(ResolutionMismatch));
+}
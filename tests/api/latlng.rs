@@ -95,6 +95,143 @@ fn distance_wrapped_longitude() {
     );
 }
 
+#[test]
+fn initial_bearing() {
+    let src = LatLng::new(10., 10.).expect("src");
+    for (lat, lng, expected) in [
+        (20., 10., 0.),
+        (10., 11., 90.),
+        (0., 10., 180.),
+        (10., 9., 270.),
+    ] {
+        let dst = LatLng::new(lat, lng).expect("dst");
+        let bearing = src.initial_bearing(dst);
+        assert!((0. ..360.).contains(&bearing), "{bearing}");
+        assert!((bearing - expected).abs() < 0.1, "{bearing} vs {expected}");
+    }
+}
+
+#[test]
+fn destination() {
+    let src = LatLng::new(48.864716, 2.349014).expect("src");
+    let dst = LatLng::new(31.224361, 121.469170).expect("dst");
+
+    // Round trip through bearing and distance.
+    let result = src
+        .destination(src.initial_bearing(dst), src.distance_m(dst))
+        .expect("destination");
+    assert!(result.distance_m(dst) < 1e-6, "{result}");
+
+    // Going through the pole.
+    let src = LatLng::new(80., 0.).expect("src");
+    let quarter = 20_f64.to_radians() * h3o::EARTH_RADIUS_KM * 1000.;
+    let result = src.destination(0., quarter).expect("destination");
+    assert_float_eq!(result.lat(), 80., abs <= 1e-9);
+    assert_float_eq!(result.lng(), 180., abs <= 1e-9);
+
+    assert_eq!(src.destination(42., 0.).expect("same"), src);
+    assert!(src.destination(f64::NAN, 10.).is_err());
+    assert!(src.destination(10., f64::INFINITY).is_err());
+}
+
+#[test]
+fn intermediate() {
+    let src = LatLng::new(48.864716, 2.349014).expect("src");
+    let dst = LatLng::new(31.224361, 121.469170).expect("dst");
+    let distance = src.distance_rads(dst);
+
+    assert_eq!(src.intermediate(dst, 0.).expect("start"), src);
+    assert!(src.intermediate(dst, 1.).expect("end").distance_rads(dst) < 1e-12);
+    for fraction in [0.1, 0.25, 0.5, 0.9] {
+        let point = src.intermediate(dst, fraction).expect("point");
+        assert_float_eq!(
+            src.distance_rads(point),
+            fraction * distance,
+            abs <= 1e-12
+        );
+        assert_float_eq!(
+            point.distance_rads(dst),
+            (1. - fraction) * distance,
+            abs <= 1e-12
+        );
+    }
+
+    let antipode = LatLng::new(-48.864716, 2.349014 - 180.).expect("antipode");
+    assert!(src.intermediate(antipode, 0.5).is_err());
+    assert!(src.intermediate(dst, f64::NAN).is_err());
+    assert_eq!(src.intermediate(src, 0.5).expect("same"), src);
+}
+
+#[test]
+fn cross_track_distance() {
+    let start = LatLng::new(0., 0.).expect("start");
+    let end = LatLng::new(0., 10.).expect("end");
+
+    for lat in [-45., -1., 0., 1., 45.] {
+        let point = LatLng::new(lat, 5.).expect("point");
+        // Right of an eastward path is south.
+        assert_float_eq!(
+            point.cross_track_distance_rads(start, end),
+            -f64::to_radians(lat),
+            abs <= 1e-12
+        );
+        assert_float_eq!(
+            point.cross_track_distance_m(end, start),
+            point.distance_m(LatLng::new(0., 5.).expect("foot")) * lat.signum(),
+            abs <= 1e-6
+        );
+    }
+
+    // Degenerate path.
+    let point = LatLng::new(1., 1.).expect("point");
+    assert_eq!(
+        point.cross_track_distance_km(start, start),
+        point.distance_km(start)
+    );
+}
+
+#[test]
+fn distance_to_segment() {
+    let start = LatLng::new(0., 0.).expect("start");
+    let end = LatLng::new(0., 10.).expect("end");
+
+    // Closest point within the arc.
+    for lat in [-45., -1., 0., 1., 45.] {
+        let point = LatLng::new(lat, 5.).expect("point");
+        assert_float_eq!(
+            point.distance_to_segment_rads(start, end),
+            f64::to_radians(lat).abs(),
+            abs <= 1e-12
+        );
+        assert_float_eq!(
+            point.distance_to_segment_rads(end, start),
+            f64::to_radians(lat).abs(),
+            abs <= 1e-12
+        );
+    }
+
+    // Closest point beyond the endpoints.
+    for (lng, endpoint) in [(-5., start), (15., end), (-170., start)] {
+        let point = LatLng::new(1., lng).expect("point");
+        assert_float_eq!(
+            point.distance_to_segment_m(start, end),
+            point.distance_m(endpoint),
+            abs <= 1e-6
+        );
+        assert!(
+            point.distance_to_segment_m(start, end)
+                > point.cross_track_distance_m(start, end).abs()
+        );
+    }
+
+    // Degenerate path.
+    let point = LatLng::new(1., 1.).expect("point");
+    assert_eq!(
+        point.distance_to_segment_km(start, start),
+        point.distance_km(start)
+    );
+}
+
 #[test]
 fn to_cell_icosahedron_center() {
     let ll = LatLng::new(28.173218757257807, 23.03222744086644).expect("ll");
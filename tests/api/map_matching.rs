@@ -0,0 +1,194 @@
+use h3o::{CellIndex, LatLng, MapMatcherBuilder, Resolution};
+
+fn assert_contiguous(path: &[CellIndex]) {
+    for pair in path.windows(2) {
+        assert!(
+            pair[0].is_neighbor_with(pair[1]).expect("same resolution"),
+            "{} -> {}",
+            pair[0],
+            pair[1]
+        );
+    }
+}
+
+/// Returns a point on the great circle arc between `src` and `dst`.
+fn lerp(src: LatLng, dst: LatLng, t: f64) -> LatLng {
+    LatLng::new(
+        src.lat() + (dst.lat() - src.lat()) * t,
+        src.lng() + (dst.lng() - src.lng()) * t,
+    )
+    .expect("point")
+}
+
+#[test]
+fn empty() {
+    let matcher = MapMatcherBuilder::new(Resolution::Nine)
+        .build()
+        .expect("matcher");
+    assert!(matcher.match_trace([]).is_empty());
+}
+
+#[test]
+fn single_point() {
+    let matcher = MapMatcherBuilder::new(Resolution::Nine)
+        .build()
+        .expect("matcher");
+    let point = LatLng::new(48.8566, 2.3522).expect("point");
+    assert_eq!(
+        matcher.match_trace([point]),
+        vec![point.to_cell(Resolution::Nine)]
+    );
+}
+
+#[test]
+fn exact_trace() {
+    let src = CellIndex::try_from(0x8a1fb46622dffff).expect("src");
+    let dst = CellIndex::try_from(0x8a1fb4644937fff).expect("dst");
+    let expected = src
+        .grid_path_cells(dst)
+        .expect("path")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("cells");
+
+    let matcher = MapMatcherBuilder::new(Resolution::Ten)
+        .accuracy(1.)
+        .build()
+        .expect("matcher");
+    let path = matcher.match_trace(expected.iter().copied().map(LatLng::from));
+
+    assert_eq!(path, expected);
+}
+
+#[test]
+fn stationary_jitter() {
+    let src = CellIndex::try_from(0x8a1fb46622dffff).expect("src");
+    let dst = CellIndex::try_from(0x8a1fb46622d7fff).expect("dst");
+    let (src, dst) = (LatLng::from(src), LatLng::from(dst));
+
+    // A stationary receiver, jittering around the boundary between two cells.
+    let trace = (0..20)
+        .map(|i| lerp(src, dst, if i % 2 == 0 { 0.45 } else { 0.55 }))
+        .collect::<Vec<_>>();
+    let raw = trace
+        .iter()
+        .map(|point| point.to_cell(Resolution::Ten))
+        .collect::<Vec<_>>();
+    assert!(raw.windows(2).all(|pair| pair[0] != pair[1]));
+
+    let matcher = MapMatcherBuilder::new(Resolution::Ten)
+        .build()
+        .expect("matcher");
+    let path = matcher.match_trace(trace);
+
+    assert_eq!(path.len(), 1);
+}
+
+#[test]
+fn noisy_trace() {
+    let src = LatLng::new(48.8566, 2.3522).expect("src");
+    let dst = LatLng::new(48.8738, 2.2950).expect("dst");
+    // Alternating offset of about 20m across the track.
+    let trace = (0..=100).map(|i| {
+        let point = lerp(src, dst, f64::from(i) / 100.);
+        let offset = if i % 2 == 0 { 0.0002 } else { -0.0002 };
+        LatLng::new(point.lat() + offset, point.lng()).expect("noisy point")
+    });
+
+    let matcher = MapMatcherBuilder::new(Resolution::Eleven)
+        .accuracy(20.)
+        .build()
+        .expect("matcher");
+    let path = matcher.match_trace(trace.clone());
+    assert_contiguous(&path);
+
+    // Shorter than the raw snapping, which goes back and forth.
+    let raw = MapMatcherBuilder::new(Resolution::Eleven)
+        .accuracy(0.1)
+        .build()
+        .expect("matcher")
+        .match_trace(trace);
+    assert_contiguous(&raw);
+    assert!(path.len() < raw.len(), "{} vs {}", path.len(), raw.len());
+}
+
+#[test]
+fn per_point_accuracy() {
+    let src = CellIndex::try_from(0x8a1fb46622dffff).expect("src");
+    let dst = CellIndex::try_from(0x8a1fb46622d7fff).expect("dst");
+    let (src, dst) = (LatLng::from(src), LatLng::from(dst));
+    // An outlier with a poor accuracy is ignored.
+    let outlier = lerp(src, dst, 0.9);
+
+    let matcher = MapMatcherBuilder::new(Resolution::Ten)
+        .build()
+        .expect("matcher");
+    let path = matcher.match_trace_with_accuracy([
+        (src, 5.),
+        (outlier, 200.),
+        (src, 5.),
+    ]);
+    assert_eq!(path, vec![src.to_cell(Resolution::Ten)]);
+
+    // But not if it's accurate.
+    let path = matcher.match_trace_with_accuracy([
+        (src, 5.),
+        (outlier, 5.),
+        (src, 5.),
+    ]);
+    assert_eq!(path.len(), 3);
+}
+
+#[test]
+fn around_pentagon() {
+    let pentagon = CellIndex::try_from(0x8009fffffffffff)
+        .expect("pentagon")
+        .center_child(Resolution::Six)
+        .expect("center child");
+    let ring = pentagon.grid_ring::<Vec<_>>(4);
+    let src = LatLng::from(ring[0]);
+    let dst = LatLng::from(ring[ring.len() / 2]);
+
+    let trace = (0..=50).map(|i| lerp(src, dst, f64::from(i) / 50.));
+    let matcher = MapMatcherBuilder::new(Resolution::Six)
+        .build()
+        .expect("matcher");
+    let path = matcher.match_trace(trace);
+
+    assert_eq!(path.first(), Some(&ring[0]));
+    assert_eq!(path.last(), Some(&ring[ring.len() / 2]));
+    assert_contiguous(&path);
+}
+
+#[test]
+fn sparse_around_pentagon() {
+    // Far apart points, whose gaps must be filled across the pentagon.
+    let pentagon = CellIndex::try_from(0x8009fffffffffff)
+        .expect("pentagon")
+        .center_child(Resolution::Six)
+        .expect("center child");
+    let ring = pentagon.grid_ring::<Vec<_>>(6);
+    let cells = [0, ring.len() / 3, 2 * ring.len() / 3, 0].map(|i| ring[i]);
+
+    let matcher = MapMatcherBuilder::new(Resolution::Six)
+        .accuracy(1.)
+        .build()
+        .expect("matcher");
+    let path = matcher.match_trace(cells.iter().copied().map(LatLng::from));
+
+    assert_eq!(path.first(), Some(&cells[0]));
+    assert!(cells.iter().all(|cell| path.contains(cell)));
+    assert_contiguous(&path);
+}
+
+#[test]
+fn invalid_accuracy() {
+    for accuracy in [0., -1., f64::NAN, f64::INFINITY] {
+        assert!(
+            MapMatcherBuilder::new(Resolution::Ten)
+                .accuracy(accuracy)
+                .build()
+                .is_err(),
+            "{accuracy}"
+        );
+    }
+}
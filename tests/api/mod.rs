@@ -1,18 +1,37 @@
+mod autocorrelation;
 mod avg_edge_len;
 mod base_cell;
 mod boundary;
+mod cell_bitmap;
+mod cell_component;
 mod cell_index;
+mod cell_set_decoder;
 mod directed_edge_index;
 mod direction;
+mod dissolver;
+mod earth_model;
 mod edge;
+mod edge_ring;
 mod face;
 mod face_set;
 #[cfg(feature = "geo")]
 mod geom;
+mod hex_raster;
+mod hexbin;
 mod index_mode;
 mod latlng;
 mod localij;
+mod map_matching;
+mod point_index;
+mod raster;
 mod resolution;
+mod rng;
+#[cfg(feature = "serde")]
+mod serde;
+mod shape_metrics;
+mod simplifier;
+mod smoothing;
+mod trajectory;
 mod vertex;
 mod vertex_index;
 
@@ -0,0 +1,162 @@
+use super::rng::SplitMix64;
+use h3o::{CellIndex, LatLng, PointIndex, Resolution};
+
+/// Deterministic pseudo-random points around a center.
+fn points(center: LatLng, spread: f64, count: usize) -> Vec<LatLng> {
+    let mut rng = SplitMix64::new();
+    (0..count)
+        .map(|_| {
+            let lat =
+                (center.lat() + rng.next_signed() * spread).clamp(-89.9, 89.9);
+            let mut lng = center.lng() + rng.next_signed() * spread;
+            if lng > 180. {
+                lng -= 360.;
+            } else if lng < -180. {
+                lng += 360.;
+            }
+            LatLng::new(lat, lng).expect("point")
+        })
+        .collect()
+}
+
+/// Checks the queries against a brute-force search.
+fn check(index: &PointIndex<usize>, points: &[LatLng], queries: &[LatLng]) {
+    let radius = index.resolution().edge_length_m() * 5.;
+    for &query in queries {
+        let mut expected = points
+            .iter()
+            .map(|point| query.distance_m(*point))
+            .collect::<Vec<_>>();
+        expected.sort_unstable_by(f64::total_cmp);
+
+        let result = index
+            .nearest(query, 10)
+            .iter()
+            .map(|neighbor| neighbor.distance_m)
+            .collect::<Vec<_>>();
+        assert_eq!(result, expected[..10.min(expected.len())], "{query}");
+
+        let result = index
+            .within(query, radius)
+            .iter()
+            .map(|neighbor| neighbor.distance_m)
+            .collect::<Vec<_>>();
+        let count = expected.partition_point(|&distance| distance <= radius);
+        assert_eq!(result, expected[..count], "{query}");
+    }
+}
+
+fn index(resolution: Resolution, points: &[LatLng]) -> PointIndex<usize> {
+    PointIndex::from_points(
+        resolution,
+        points.iter().enumerate().map(|(id, point)| (*point, id)),
+    )
+}
+
+#[test]
+fn nearest_and_within() {
+    let center = LatLng::new(48.8566, 2.3522).expect("center");
+    let points = points(center, 0.5, 2000);
+    let queries = self::points(center, 0.7, 50);
+
+    for resolution in [Resolution::Five, Resolution::Seven, Resolution::Eight] {
+        check(&index(resolution, &points), &points, &queries);
+    }
+}
+
+#[test]
+fn antimeridian() {
+    let center = LatLng::new(-16.5, 180.).expect("center");
+    let points = points(center, 1., 1000);
+    let queries = self::points(center, 1., 50);
+    assert!(points.iter().any(|point| point.lng() < 0.));
+    assert!(points.iter().any(|point| point.lng() > 0.));
+
+    check(&index(Resolution::Six, &points), &points, &queries);
+}
+
+#[test]
+fn pentagon() {
+    let pentagon = CellIndex::try_from(0x8009fffffffffff).expect("pentagon");
+    let center = LatLng::from(pentagon);
+    let points = points(center, 2., 1000);
+    let queries = self::points(center, 2., 50);
+
+    for resolution in [Resolution::Three, Resolution::Five] {
+        check(&index(resolution, &points), &points, &queries);
+    }
+}
+
+#[test]
+fn sparse() {
+    let a = LatLng::new(48.8566, 2.3522).expect("a");
+    let b = LatLng::new(-33.8688, 151.2093).expect("b");
+    let index = PointIndex::from_points(Resolution::Two, [(a, 'a'), (b, 'b')]);
+
+    // Every point is returned, even on the other side of the globe.
+    let result = index.nearest(a, 5);
+    assert_eq!(result.len(), 2);
+    assert_eq!(*result[1].value, 'b');
+    assert!(index.nearest(a, 0).is_empty());
+    assert_eq!(index.within(a, 1.).len(), 1);
+}
+
+#[test]
+fn sparse_fine_resolution() {
+    let a = LatLng::new(48.8566, 2.3522).expect("a");
+    let b = LatLng::new(-33.8688, 151.2093).expect("b");
+    let c = LatLng::new(48.8570, 2.3530).expect("c");
+    let index = PointIndex::from_points(
+        Resolution::Ten,
+        [(a, 'a'), (b, 'b'), (c, 'c')],
+    );
+
+    let result = index.nearest(a, 3);
+    assert_eq!(
+        result
+            .iter()
+            .map(|neighbor| *neighbor.value)
+            .collect::<Vec<_>>(),
+        ['a', 'c', 'b']
+    );
+    assert_eq!(index.within(a, 1_000.).len(), 2);
+    assert_eq!(index.within(a, 20_000_000.).len(), 3);
+
+    // Around a pentagon.
+    let pentagon = CellIndex::base_cells()
+        .find(|cell| cell.is_pentagon())
+        .expect("pentagon")
+        .center_child(Resolution::Ten)
+        .expect("center child");
+    let index = PointIndex::from_points(Resolution::Ten, [(b, 'b')]);
+    let result = index.nearest(LatLng::from(pentagon), 1);
+    assert_eq!(result.len(), 1);
+    assert_eq!(*result[0].value, 'b');
+}
+
+#[test]
+fn insert_remove() {
+    let center = LatLng::new(48.8566, 2.3522).expect("center");
+    let points = points(center, 0.1, 100);
+    let mut index = PointIndex::new(Resolution::Eight);
+    assert!(index.is_empty());
+    for (id, &point) in points.iter().enumerate() {
+        index.insert(point, id);
+    }
+    assert_eq!(index.len(), points.len());
+    assert_eq!(index.iter().count(), points.len());
+
+    let nearest = index.nearest(center, 1)[0];
+    let (point, id) = (nearest.point, *nearest.value);
+    assert!(!index.remove(point, &(id + 1)));
+    assert!(index.remove(point, &id));
+    assert!(!index.remove(point, &id));
+    assert_eq!(index.len(), points.len() - 1);
+    assert_ne!(*index.nearest(center, 1)[0].value, id);
+
+    for (id, &point) in points.iter().enumerate() {
+        index.remove(point, &id);
+    }
+    assert!(index.is_empty());
+    assert!(index.nearest(center, 3).is_empty());
+}
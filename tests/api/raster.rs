@@ -0,0 +1,187 @@
+use h3o::{
+    CellIndex, GeoTransform, LatLng, Raster, Resolution, error::InvalidRaster,
+};
+
+#[test]
+fn invalid_transform() {
+    assert!(GeoTransform::north_up(2.3, 48.9, 0.01, 0.01).is_ok());
+    assert!(GeoTransform::north_up(f64::NAN, 48.9, 0.01, 0.01).is_err());
+    assert!(GeoTransform::north_up(2.3, 48.9, 0., 0.01).is_err());
+    assert!(GeoTransform::new([0., 1., 1., 0., 1., 1.]).is_err());
+}
+
+#[test]
+fn size_mismatch() {
+    let transform = GeoTransform::north_up(2.3, 48.9, 0.01, 0.01).expect("tf");
+    let data = [0_u8; 10];
+
+    assert!(Raster::new(transform, 2, 5, &data).is_ok());
+    assert!(matches!(
+        Raster::new(transform, 3, 3, &data),
+        Err(InvalidRaster { .. })
+    ));
+    assert!(Raster::new(transform, usize::MAX, 2, &data).is_err());
+}
+
+#[test]
+fn pixel_coordinates() {
+    let transform = GeoTransform::north_up(2., 49., 0.5, 0.25).expect("tf");
+    let corner = transform.to_latlng(2., 4.).expect("corner");
+    assert!((corner.lng() - 3.).abs() < 1e-12);
+    assert!((corner.lat() - 48.).abs() < 1e-12);
+
+    let transform = GeoTransform::north_up(-180., 90., 1., 1.).expect("tf");
+    assert!(transform.to_latlng(0., 181.).is_none());
+}
+
+#[test]
+fn mean() {
+    let transform = GeoTransform::north_up(2.3, 48.9, 0.01, 0.01).expect("tf");
+    let data = (0..100).map(|value| value as f32).collect::<Vec<_>>();
+    let raster = Raster::new(transform, 10, 10, &data).expect("raster");
+
+    let cells = raster.to_cells_mean(Resolution::Six);
+    assert!(cells.is_sorted_by_key(|(cell, _)| *cell));
+
+    for (cell, value) in cells {
+        let mut pixels = Vec::new();
+        for row in 0..10 {
+            for col in 0..10 {
+                let center = transform
+                    .to_latlng(col as f64 + 0.5, row as f64 + 0.5)
+                    .expect("center");
+                if center.to_cell(Resolution::Six) == cell {
+                    pixels.push(f64::from(data[row * 10 + col]));
+                }
+            }
+        }
+        let expected = pixels.iter().sum::<f64>() / pixels.len() as f64;
+        assert!((value - expected).abs() < 1e-9, "{cell}");
+    }
+}
+
+#[test]
+fn nodata() {
+    let transform = GeoTransform::north_up(2.3, 48.9, 0.01, 0.01).expect("tf");
+    let data = [5_i16, -9999, 5, -9999];
+    let raster = Raster::new(transform, 2, 2, &data)
+        .expect("raster")
+        .with_nodata(-9999);
+
+    for (_, value) in raster.to_cells_mean(Resolution::Four) {
+        assert_eq!(value, 5.);
+    }
+    for (_, value) in raster.to_cells_area_weighted(Resolution::Four) {
+        assert!((value - 5.).abs() < 1e-12);
+    }
+    for (_, value) in raster.to_cells_mode(Resolution::Four) {
+        assert_eq!(value, 5);
+    }
+
+    let data = [f64::NAN, 2.];
+    let raster = Raster::new(transform, 2, 1, &data).expect("raster");
+    let cells = raster.to_cells_mean(Resolution::Zero);
+    assert_eq!(cells.len(), 1);
+    assert_eq!(cells[0].1, 2.);
+}
+
+#[test]
+fn mode() {
+    let transform =
+        GeoTransform::north_up(2.3, 48.9, 0.001, 0.001).expect("tf");
+    let data = [3_u8, 3, 2, 2, 1, 7];
+    let raster = Raster::new(transform, 3, 2, &data).expect("raster");
+
+    // Tie between 2 and 3: the lowest wins.
+    let cells = raster.to_cells_mode(Resolution::Four);
+    assert_eq!(cells.len(), 1);
+    assert_eq!(cells[0].1, 2);
+}
+
+#[test]
+fn area_weighted() {
+    // Pixels much larger than the cells.
+    let transform = GeoTransform::north_up(2., 49., 0.1, 0.1).expect("tf");
+    let data = [1., 2., 3., 4.];
+    let raster = Raster::new(transform, 2, 2, &data).expect("raster");
+
+    let sampled = raster.to_cells_mean(Resolution::Seven);
+    let weighted = raster.to_cells_area_weighted(Resolution::Seven);
+    assert_eq!(sampled.len(), 4);
+    assert!(weighted.len() > 50, "{}", weighted.len());
+    assert!(weighted.is_sorted_by_key(|(cell, _)| *cell));
+
+    // Cells fully inside a pixel get its value.
+    let cell = LatLng::new(48.95, 2.05)
+        .expect("point")
+        .to_cell(Resolution::Seven);
+    let value = weighted
+        .iter()
+        .find_map(|&(c, value)| (c == cell).then_some(value));
+    assert_eq!(value, Some(1.));
+
+    // Constant raster.
+    let data = [7.; 4];
+    let raster = Raster::new(transform, 2, 2, &data).expect("raster");
+    for (_, value) in raster.to_cells_area_weighted(Resolution::Five) {
+        assert!((value - 7.).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn area_weighted_tiny_cells() {
+    // Cells so small that the samples miss some of them.
+    let transform =
+        GeoTransform::north_up(2., 49., 0.0005, 0.0005).expect("tf");
+    let data = [1., 2., 3., 4.];
+    let raster = Raster::new(transform, 2, 2, &data).expect("raster");
+
+    let weighted = raster.to_cells_area_weighted(Resolution::Fourteen);
+    // More cells than the 16×16 samples per pixel.
+    assert!(weighted.len() > 4 * 256, "{}", weighted.len());
+    assert!(weighted.is_sorted_by_key(|(cell, _)| *cell));
+    for (_, value) in weighted {
+        assert!((1. ..=4.).contains(&value), "{value}");
+    }
+}
+
+#[test]
+fn rasterize() {
+    let transform = GeoTransform::north_up(2.3, 48.9, 0.01, 0.01).expect("tf");
+    let data = (0..64).collect::<Vec<u32>>();
+    let raster = Raster::new(transform, 8, 8, &data).expect("raster");
+
+    // Cells much smaller than the pixels hold a single pixel center.
+    let cells = raster.to_cells_mode(Resolution::Eleven);
+    assert_eq!(cells.len(), 64);
+
+    let pixels = transform.rasterize(8, 8, cells).expect("pixels");
+    let expected = data.iter().copied().map(Some).collect::<Vec<_>>();
+    assert_eq!(pixels, expected);
+}
+
+#[test]
+fn rasterize_mixed_resolutions() {
+    let transform = GeoTransform::north_up(2.3, 48.9, 0.01, 0.01).expect("tf");
+    let center = transform.to_latlng(2.5, 2.5).expect("center");
+    let coarse = center.to_cell(Resolution::Three);
+    let fine = center.to_cell(Resolution::Nine);
+
+    let pixels = transform
+        .rasterize(5, 5, [(coarse, 'c'), (fine, 'f')])
+        .expect("pixels");
+    assert_eq!(pixels[12], Some('f'));
+    assert!(pixels.iter().filter(|&&pixel| pixel == Some('c')).count() > 0);
+
+    let far = CellIndex::try_from(0x8009fffffffffff).expect("far");
+    let pixels = transform.rasterize(5, 5, [(far, 1)]).expect("pixels");
+    assert!(pixels.iter().all(Option::is_none));
+    assert!(
+        transform
+            .rasterize::<u8>(5, 5, [])
+            .expect("pixels")
+            .iter()
+            .all(Option::is_none)
+    );
+    assert!(transform.rasterize::<u8>(usize::MAX, 2, []).is_err());
+}
@@ -0,0 +1,183 @@
+use h3o::{CellIndex, DirectedEdgeIndex, VertexIndex};
+use serde::{Deserialize, Serialize};
+use serde_test::{Configure, Token, assert_de_tokens_error, assert_tokens};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record<T: h3o::serde::Index> {
+    #[serde(with = "h3o::serde::hex")]
+    hex: T,
+    #[serde(with = "h3o::serde::integer")]
+    integer: T,
+    #[serde(with = "h3o::serde::components")]
+    components: T,
+}
+
+fn cell() -> CellIndex {
+    CellIndex::try_from(0x8a1fb46622dffff).expect("cell")
+}
+
+fn edge() -> DirectedEdgeIndex {
+    DirectedEdgeIndex::try_from(0x13a194e699ab7fff).expect("edge")
+}
+
+fn vertex() -> VertexIndex {
+    VertexIndex::try_from(0x2222597fffffffff).expect("vertex")
+}
+
+#[test]
+fn human_readable() {
+    assert_tokens(&cell().readable(), &[Token::Str("8a1fb46622dffff")]);
+    assert_tokens(&edge().readable(), &[Token::Str("13a194e699ab7fff")]);
+    assert_tokens(&vertex().readable(), &[Token::Str("2222597fffffffff")]);
+}
+
+#[test]
+fn compact() {
+    assert_tokens(&cell().compact(), &[Token::U64(0x8a1fb46622dffff)]);
+    assert_tokens(&edge().compact(), &[Token::U64(0x13a194e699ab7fff)]);
+    assert_tokens(&vertex().compact(), &[Token::U64(0x2222597fffffffff)]);
+}
+
+#[test]
+fn json() {
+    let json = serde_json::to_string(&cell()).expect("serialize");
+    assert_eq!(json, r#""8a1fb46622dffff""#);
+
+    // Every representation is accepted.
+    for input in [
+        r#""8a1fb46622dffff""#,
+        "622054503267303423",
+        r#"{"res":10,"base_cell":15,"digits":[6,6,4,3,1,4,2,1,3,3]}"#,
+    ] {
+        let result = serde_json::from_str::<CellIndex>(input);
+        assert_eq!(result.ok(), Some(cell()), "{input}");
+    }
+}
+
+#[test]
+fn helpers() {
+    let record = Record {
+        hex: cell(),
+        integer: cell(),
+        components: cell(),
+    };
+    let json = serde_json::to_string(&record).expect("serialize");
+    assert_eq!(
+        json,
+        r#"{"hex":"8a1fb46622dffff","integer":622054503267303423,"components":{"res":10,"base_cell":15,"digits":[6,6,4,3,1,4,2,1,3,3]}}"#
+    );
+    let result = serde_json::from_str::<Record<CellIndex>>(&json);
+    assert_eq!(result.ok(), Some(record));
+
+    let record = Record {
+        hex: edge(),
+        integer: edge(),
+        components: edge(),
+    };
+    let json = serde_json::to_string(&record).expect("serialize");
+    assert!(json.contains(r#""edge":3"#), "{json}");
+    let result = serde_json::from_str::<Record<DirectedEdgeIndex>>(&json);
+    assert_eq!(result.ok(), Some(record));
+
+    let record = Record {
+        hex: vertex(),
+        integer: vertex(),
+        components: vertex(),
+    };
+    let json = serde_json::to_string(&record).expect("serialize");
+    assert!(json.contains(r#""vertex":2"#), "{json}");
+    let result = serde_json::from_str::<Record<VertexIndex>>(&json);
+    assert_eq!(result.ok(), Some(record));
+}
+
+#[test]
+fn helpers_are_strict() {
+    let mut deserializer =
+        serde_json::Deserializer::from_str("622054503267303423");
+    let result =
+        h3o::serde::hex::deserialize::<CellIndex, _>(&mut deserializer);
+    assert!(result.is_err());
+
+    let mut deserializer =
+        serde_json::Deserializer::from_str(r#""8a1fb46622dffff""#);
+    let result =
+        h3o::serde::integer::deserialize::<CellIndex, _>(&mut deserializer);
+    assert!(result.is_err());
+
+    let mut deserializer =
+        serde_json::Deserializer::from_str(r#""0x8a1fb46622dffff""#);
+    let result =
+        h3o::serde::hex::deserialize::<CellIndex, _>(&mut deserializer);
+    assert_eq!(result.ok(), Some(cell()));
+
+    // Only hexadecimal strings are accepted.
+    for input in [
+        r#""622054503267303423""#,
+        r#""a3yt6c8pr""#,
+        r#""15/6643142133""#,
+        r#""+8a1fb46622dffff""#,
+        r#""0x""#,
+        r#""""#,
+    ] {
+        let mut deserializer = serde_json::Deserializer::from_str(input);
+        let result =
+            h3o::serde::hex::deserialize::<CellIndex, _>(&mut deserializer);
+        assert!(result.is_err(), "{input}");
+    }
+}
+
+#[test]
+fn invalid() {
+    assert_de_tokens_error::<serde_test::Readable<CellIndex>>(
+        &[Token::Str("8a1fb46622dfff7")],
+        "invalid cell index (got Some(622054503267303415)): invalid unused direction pattern",
+    );
+    assert_de_tokens_error::<serde_test::Compact<CellIndex>>(
+        &[Token::U64(0x8a1fb46622dfff7)],
+        "invalid cell index (got Some(622054503267303415)): invalid unused direction pattern",
+    );
+    // A cell isn't a vertex.
+    assert!(serde_json::from_str::<VertexIndex>("622054503267303423").is_err());
+    assert!(serde_json::from_str::<CellIndex>("-1").is_err());
+
+    for input in [
+        // Invalid resolution.
+        r#"{"res":16,"base_cell":15,"digits":[]}"#,
+        // Invalid base cell.
+        r#"{"res":0,"base_cell":122,"digits":[]}"#,
+        // Missing digit.
+        r#"{"res":2,"base_cell":15,"digits":[6]}"#,
+        // Invalid digit.
+        r#"{"res":1,"base_cell":15,"digits":[7]}"#,
+        // Deleted subsequence of a pentagon.
+        r#"{"res":1,"base_cell":4,"digits":[1]}"#,
+        // Unexpected field.
+        r#"{"res":0,"base_cell":15,"digits":[],"edge":1}"#,
+        r#"{"res":0,"base_cell":15,"digits":[],"foo":1}"#,
+    ] {
+        let result = serde_json::from_str::<CellIndex>(input);
+        assert!(result.is_err(), "{input}");
+    }
+
+    for input in [
+        // Missing edge.
+        r#"{"res":0,"base_cell":15,"digits":[]}"#,
+        // Invalid edge.
+        r#"{"res":0,"base_cell":15,"digits":[],"edge":0}"#,
+        // Pentagons have no edge 1.
+        r#"{"res":0,"base_cell":4,"digits":[],"edge":1}"#,
+    ] {
+        let result = serde_json::from_str::<DirectedEdgeIndex>(input);
+        assert!(result.is_err(), "{input}");
+    }
+
+    for input in [
+        // Missing vertex.
+        r#"{"res":0,"base_cell":15,"digits":[]}"#,
+        // Non-canonical owner.
+        r#"{"res":0,"base_cell":15,"digits":[],"vertex":0}"#,
+    ] {
+        let result = serde_json::from_str::<VertexIndex>(input);
+        assert!(result.is_err(), "{input}");
+    }
+}
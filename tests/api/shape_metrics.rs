@@ -0,0 +1,71 @@
+use h3o::{CellIndex, DirectedEdgeIndex, LatLng, Resolution};
+
+#[test]
+fn consistent_with_cell_measures() {
+    let cells = [
+        CellIndex::try_from(0x8a1fb46622dffff).expect("hexagon"),
+        CellIndex::try_from(0x8009fffffffffff).expect("pentagon"),
+        // Class III, with distortion vertexes.
+        CellIndex::try_from(0x81083ffffffffff).expect("distorted"),
+    ];
+
+    for cell in cells {
+        let metrics = cell.metrics();
+        let edges = cell
+            .edges()
+            .map(DirectedEdgeIndex::length_m)
+            .collect::<Vec<_>>();
+        let min = edges.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = edges.iter().copied().fold(0., f64::max);
+
+        assert_eq!(metrics.area_m2(), cell.area_m2(), "{cell}");
+        assert!((metrics.perimeter_m() - cell.perimeter_m()).abs() < 1e-6);
+        assert!(
+            (metrics.perimeter_m() - edges.iter().sum::<f64>()).abs() < 1e-6,
+            "{cell}"
+        );
+        assert!((metrics.min_edge_length_m() - min).abs() < 1e-9, "{cell}");
+        assert!((metrics.max_edge_length_m() - max).abs() < 1e-9, "{cell}");
+        assert!(metrics.edge_ratio() >= 1., "{cell}");
+        assert!(metrics.compactness() > 0.8, "{cell}");
+        assert!(metrics.compactness() <= 1., "{cell}");
+    }
+}
+
+#[test]
+fn pentagons_are_regular() {
+    let pentagon = CellIndex::try_from(0x8009fffffffffff).expect("pentagon");
+    for resolution in Resolution::range(Resolution::Zero, Resolution::Fifteen) {
+        let cell = pentagon.center_child(resolution).expect("center child");
+        let metrics = cell.metrics();
+
+        assert!((metrics.edge_ratio() - 1.) < 1e-8, "{cell}");
+        assert!(metrics.centroid().distance_m(cell.into()) < 1e-3, "{cell}");
+    }
+}
+
+#[test]
+fn centroid_differs_from_center() {
+    // Large hexagon, far from the center of its icosahedron face.
+    let cell = CellIndex::try_from(0x8001fffffffffff).expect("cell");
+    let centroid = cell.metrics().centroid();
+    let offset = centroid.distance_km(cell.into());
+    assert!(offset > 50. && offset < 100., "{offset}");
+
+    // The centroid of a small hexagon almost matches its center.
+    let cell = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    assert!(cell.metrics().centroid().distance_m(cell.into()) < 1.);
+}
+
+#[test]
+fn centroid_of_regular_cells() {
+    // Small cells are almost regular: their centroid is close to their center.
+    let point = LatLng::new(48.854, 2.3735).expect("point");
+    for resolution in Resolution::range(Resolution::Two, Resolution::Twelve) {
+        let cell = point.to_cell(resolution);
+        let centroid = cell.metrics().centroid();
+        let edge = cell.metrics().min_edge_length_m();
+
+        assert!(centroid.distance_m(cell.into()) < edge / 10., "{cell}");
+    }
+}
@@ -0,0 +1,260 @@
+use h3o::{CellIndex, LatLng, Resolution, Simplifier, error::DissolutionError};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// ~10m, in radians.
+const TOLERANCE: f64 = 10. / 6_371_007.180_918_475;
+
+type Point = (u64, u64);
+type Segment = (Point, Point);
+
+fn center() -> CellIndex {
+    CellIndex::try_from(0x8c283470d921bff).expect("center")
+}
+
+fn point(ll: LatLng) -> Point {
+    (ll.lat_radians().to_bits(), ll.lng_radians().to_bits())
+}
+
+fn segments(ring: &[LatLng]) -> Vec<Segment> {
+    (0..ring.len())
+        .map(|i| (point(ring[i]), point(ring[(i + 1) % ring.len()])))
+        .collect()
+}
+
+/// Planar crossing test, good enough for small regions.
+fn crosses(lhs: (LatLng, LatLng), rhs: (LatLng, LatLng)) -> bool {
+    let orient = |a: LatLng, b: LatLng, c: LatLng| {
+        let value = (b.lng_radians() - a.lng_radians())
+            * (c.lat_radians() - a.lat_radians())
+            - (b.lat_radians() - a.lat_radians())
+                * (c.lng_radians() - a.lng_radians());
+        value.signum()
+    };
+    let shared = [lhs.0, lhs.1].into_iter().any(|p| p == rhs.0 || p == rhs.1);
+
+    !shared
+        && orient(lhs.0, lhs.1, rhs.0) * orient(lhs.0, lhs.1, rhs.1) < 0.
+        && orient(rhs.0, rhs.1, lhs.0) * orient(rhs.0, rhs.1, lhs.1) < 0.
+}
+
+/// Planar signed area, good enough for small regions.
+fn signed_area(ring: &[LatLng]) -> f64 {
+    (0..ring.len())
+        .map(|i| {
+            let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+            a.lng_radians() * b.lat_radians()
+                - b.lng_radians() * a.lat_radians()
+        })
+        .sum::<f64>()
+}
+
+/// Planar point-in-polygon test, good enough for small regions.
+fn contains(ring: &[LatLng], ll: LatLng) -> bool {
+    let (x, y) = (ll.lng_radians(), ll.lat_radians());
+    let mut is_inside = false;
+    for i in 0..ring.len() {
+        let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+        let (ax, ay) = (a.lng_radians(), a.lat_radians());
+        let (bx, by) = (b.lng_radians(), b.lat_radians());
+        if (ay > y) != (by > y) && x < ax + (y - ay) * (bx - ax) / (by - ay) {
+            is_inside = !is_inside;
+        }
+    }
+    is_inside
+}
+
+fn assert_valid(rings: &[&Vec<LatLng>]) {
+    let lines = rings
+        .iter()
+        .flat_map(|ring| {
+            assert!(ring.len() >= 3, "degenerate ring");
+            (0..ring.len()).map(|i| (ring[i], ring[(i + 1) % ring.len()]))
+        })
+        .collect::<Vec<_>>();
+    for (i, &lhs) in lines.iter().enumerate() {
+        for &rhs in &lines[i + 1..] {
+            assert!(!crosses(lhs, rhs), "crossing segments");
+        }
+    }
+}
+
+#[test]
+fn simplify() {
+    let cells = center().grid_disk::<Vec<_>>(10);
+    let outline = CellIndex::outline(cells.iter().copied()).expect("outline");
+    let rings = Simplifier::new(TOLERANCE)
+        .expect("simplifier")
+        .simplify(cells)
+        .expect("rings");
+
+    assert_eq!(rings.len(), 1);
+    assert!(rings[0].len() < outline[0].boundary().len() / 4);
+    assert_valid(&rings.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn zero_tolerance() {
+    let cells = center().grid_ring::<Vec<_>>(3);
+    let outline = CellIndex::outline(cells.iter().copied()).expect("outline");
+    let rings = Simplifier::new(0.)
+        .expect("simplifier")
+        .simplify(cells)
+        .expect("rings");
+
+    assert_eq!(rings.len(), 2);
+    let result = rings.iter().map(Vec::len).collect::<BTreeSet<_>>();
+    let expected = outline
+        .iter()
+        .map(|ring| ring.boundary().len())
+        .collect::<BTreeSet<_>>();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn thin_ring() {
+    // A huge tolerance would collapse the rings onto each other without
+    // topology preservation.
+    let cells = center().grid_ring::<Vec<_>>(8);
+    let rings = Simplifier::new(TOLERANCE * 100.)
+        .expect("simplifier")
+        .simplify(cells)
+        .expect("rings");
+
+    assert_eq!(rings.len(), 2);
+    assert_valid(&rings.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn hole_near_border() {
+    // A large tolerance flattens the long stair-step sides of the disk, and
+    // would leave the holes next to them outside of the shell.
+    let tolerance = TOLERANCE * 20.;
+    for hole in center().grid_ring::<Vec<_>>(9) {
+        let cells = center()
+            .grid_disk::<Vec<_>>(10)
+            .into_iter()
+            .filter(|&cell| cell != hole);
+        let rings = Simplifier::new(tolerance)
+            .expect("simplifier")
+            .simplify(cells)
+            .expect("rings");
+
+        assert_eq!(rings.len(), 2);
+        // Holes run clockwise.
+        let (shell, hole) = if signed_area(&rings[0]) > 0. {
+            (&rings[0], &rings[1])
+        } else {
+            (&rings[1], &rings[0])
+        };
+        assert!(
+            hole.iter().all(|&ll| contains(shell, ll)),
+            "hole outside of its shell"
+        );
+        assert_valid(&rings.iter().collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn enclosed_region() {
+    let inner = center().grid_disk::<Vec<_>>(4);
+    let outer = (5..8).flat_map(|k| center().grid_ring::<Vec<_>>(k));
+    let cells = inner
+        .into_iter()
+        .map(|cell| (cell, 0))
+        .chain(outer.map(|cell| (cell, 1)));
+    let regions = Simplifier::new(TOLERANCE)
+        .expect("simplifier")
+        .simplify_regions(cells)
+        .expect("regions");
+
+    assert_eq!(regions[&0].len(), 1);
+    assert_eq!(regions[&1].len(), 2);
+    // The hole of the outer region is exactly the inner region.
+    let inner = segments(&regions[&0][0])
+        .into_iter()
+        .map(|(from, to)| (to, from))
+        .collect::<BTreeSet<_>>();
+    let hole = regions[&1]
+        .iter()
+        .map(|ring| segments(ring).into_iter().collect::<BTreeSet<_>>())
+        .find(|ring| ring.is_subset(&inner))
+        .expect("hole");
+    assert_eq!(hole, inner);
+    assert_valid(&regions.values().flatten().collect::<Vec<_>>());
+}
+
+#[test]
+fn adjacent_regions() {
+    let cells = center().grid_disk::<Vec<_>>(12);
+    let origin = LatLng::from(center());
+    let regions = Simplifier::new(TOLERANCE)
+        .expect("simplifier")
+        .simplify_regions(cells.into_iter().map(|cell| {
+            let ll = LatLng::from(cell);
+            let quadrant = (
+                ll.lat_radians() > origin.lat_radians(),
+                ll.lng_radians() > origin.lng_radians(),
+            );
+            (cell, quadrant)
+        }))
+        .expect("regions");
+    assert_eq!(regions.len(), 4);
+
+    let rings = regions
+        .iter()
+        .map(|(&key, rings)| {
+            assert_eq!(rings.len(), 1);
+            (key, &rings[0])
+        })
+        .collect::<BTreeMap<_, _>>();
+    for (lhs_key, lhs) in &rings {
+        for (rhs_key, rhs) in &rings {
+            if lhs_key == rhs_key {
+                continue;
+            }
+            let rhs_points =
+                rhs.iter().copied().map(point).collect::<BTreeSet<_>>();
+            let common = lhs
+                .iter()
+                .filter(|&&ll| rhs_points.contains(&point(ll)))
+                .count();
+            let reversed = segments(rhs)
+                .into_iter()
+                .map(|(from, to)| (to, from))
+                .collect::<BTreeSet<_>>();
+            let shared = segments(lhs)
+                .into_iter()
+                .filter(|segment| reversed.contains(segment))
+                .count();
+            // Adjacent quadrants share a single border, diagonal ones a
+            // single point.
+            if common > 1 {
+                assert_eq!(shared, common - 1, "{lhs_key:?}/{rhs_key:?}");
+            } else {
+                assert_eq!(shared, 0);
+            }
+        }
+    }
+    assert_valid(&rings.values().copied().collect::<Vec<_>>());
+}
+
+#[test]
+fn errors() {
+    let simplifier = Simplifier::new(TOLERANCE).expect("simplifier");
+    let result = simplifier.simplify([center(), center()]);
+    assert_eq!(result, Err(DissolutionError::DuplicateInput));
+
+    let parent = center().parent(Resolution::Eleven).expect("parent");
+    let result = simplifier.simplify([center(), parent]);
+    assert_eq!(result, Err(DissolutionError::UnsupportedResolution));
+
+    let result = simplifier.simplify(std::iter::empty());
+    assert_eq!(result, Ok(Vec::new()));
+}
+
+#[test]
+fn invalid_tolerance() {
+    assert!(Simplifier::new(-1.).is_err());
+    assert!(Simplifier::new(f64::NAN).is_err());
+    assert!(Simplifier::new(f64::INFINITY).is_err());
+}
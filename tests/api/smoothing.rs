@@ -0,0 +1,222 @@
+use h3o::{
+    CellIndex, KernelDistance, KernelSmootherBuilder, Resolution,
+    SmoothingKernel, error::ResolutionMismatch,
+};
+
+fn total(values: &[(CellIndex, f64)]) -> f64 {
+    values.iter().map(|(_, value)| value).sum()
+}
+
+#[test]
+fn density_preserves_mass() {
+    let cell = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    let kernels = [
+        SmoothingKernel::Uniform { bandwidth: 2. },
+        SmoothingKernel::Triangular { bandwidth: 3. },
+        SmoothingKernel::Epanechnikov { bandwidth: 3. },
+        SmoothingKernel::Gaussian { sigma: 1. },
+    ];
+
+    for kernel in kernels {
+        let smoother = KernelSmootherBuilder::new(kernel, 2)
+            .build()
+            .expect("smoother");
+        let density = smoother
+            .density([(cell, 10.), (cell, 5.)])
+            .expect("density");
+
+        assert_eq!(density.len(), 19, "{kernel:?}");
+        assert!((total(&density) - 15.).abs() < 1e-9, "{kernel:?}");
+        assert!(density.is_sorted_by_key(|&(cell, _)| cell));
+    }
+}
+
+#[test]
+fn density_is_isotropic() {
+    let cell = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    let smoother =
+        KernelSmootherBuilder::new(SmoothingKernel::Gaussian { sigma: 1. }, 2)
+            .build()
+            .expect("smoother");
+    let density = smoother.density([(cell, 1.)]).expect("density");
+    let value = |cell| {
+        density
+            .iter()
+            .find_map(|&(c, value)| (c == cell).then_some(value))
+            .expect("value")
+    };
+
+    let center = value(cell);
+    let ring = cell.grid_ring::<Vec<_>>(1);
+    let first = value(ring[0]);
+    assert!(center > first);
+    for neighbor in ring {
+        assert!((value(neighbor) - first).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn density_around_pentagon() {
+    let pentagon = CellIndex::try_from(0x8009fffffffffff)
+        .expect("pentagon")
+        .center_child(Resolution::Six)
+        .expect("center child");
+    let smoother =
+        KernelSmootherBuilder::new(SmoothingKernel::Gaussian { sigma: 1. }, 2)
+            .build()
+            .expect("smoother");
+
+    let density = smoother.density([(pentagon, 100.)]).expect("density");
+
+    // 1 + 5 + 10 cells.
+    assert_eq!(density.len(), 16);
+    assert!((total(&density) - 100.).abs() < 1e-9);
+}
+
+#[test]
+fn metric_distance() {
+    let cell = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    let sigma = Resolution::Ten.edge_length_m() * 2.;
+    let smoother =
+        KernelSmootherBuilder::new(SmoothingKernel::Gaussian { sigma }, 3)
+            .distance(KernelDistance::Metric)
+            .build()
+            .expect("smoother");
+
+    let density = smoother.density([(cell, 1.)]).expect("density");
+
+    assert_eq!(density.len(), 37);
+    assert!((total(&density) - 1.).abs() < 1e-9);
+    let max = density
+        .iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .expect("max");
+    assert_eq!(max.0, cell);
+}
+
+#[test]
+fn smooth_constant_field() {
+    let pentagon = CellIndex::try_from(0x8009fffffffffff)
+        .expect("pentagon")
+        .center_child(Resolution::Four)
+        .expect("center child");
+    let cells = pentagon.grid_disk::<Vec<_>>(4);
+    let smoother = KernelSmootherBuilder::new(
+        SmoothingKernel::Epanechnikov { bandwidth: 3. },
+        2,
+    )
+    .build()
+    .expect("smoother");
+
+    let smoothed = smoother
+        .smooth(cells.iter().map(|&cell| (cell, 42.)))
+        .expect("smoothed");
+
+    assert_eq!(smoothed.len(), cells.len());
+    for (cell, value) in smoothed {
+        assert!((value - 42.).abs() < 1e-9, "{cell}: {value}");
+    }
+}
+
+#[test]
+fn smooth_spike() {
+    let cell = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    let cells = cell.grid_disk::<Vec<_>>(3);
+    let smoother = KernelSmootherBuilder::new(
+        SmoothingKernel::Uniform { bandwidth: 1. },
+        1,
+    )
+    .build()
+    .expect("smoother");
+
+    let smoothed = smoother
+        .smooth(cells.iter().map(|&c| (c, if c == cell { 70. } else { 0. })))
+        .expect("smoothed");
+
+    for (c, value) in smoothed {
+        let expected = match c.grid_distance(cell).expect("distance") {
+            0 | 1 => 10.,
+            _ => 0.,
+        };
+        assert!((value - expected).abs() < 1e-9, "{c}: {value}");
+    }
+}
+
+#[test]
+fn output_resolution() {
+    let cell = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    let cells = cell.grid_disk::<Vec<_>>(5);
+    let smoother =
+        KernelSmootherBuilder::new(SmoothingKernel::Gaussian { sigma: 1. }, 2)
+            .output_resolution(Resolution::Eight)
+            .build()
+            .expect("smoother");
+
+    let density = smoother
+        .density(cells.iter().map(|&cell| (cell, 1.)))
+        .expect("density");
+    assert!((total(&density) - cells.len() as f64).abs() < 1e-9);
+    assert!(
+        density
+            .iter()
+            .all(|(cell, _)| cell.resolution() == Resolution::Eight)
+    );
+
+    let smoothed = smoother
+        .smooth(cells.iter().map(|&cell| (cell, 3.)))
+        .expect("smoothed");
+    assert!(smoothed.iter().all(|&(_, value)| (value - 3.).abs() < 1e-9));
+    let mut parents = cells
+        .iter()
+        .map(|cell| cell.parent(Resolution::Eight).expect("parent"))
+        .collect::<Vec<_>>();
+    parents.sort_unstable();
+    parents.dedup();
+    assert_eq!(
+        smoothed.iter().map(|&(cell, _)| cell).collect::<Vec<_>>(),
+        parents
+    );
+}
+
+#[test]
+fn resolution_mismatch() {
+    let cell = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    let parent = cell.parent(Resolution::Nine).expect("parent");
+    let smoother =
+        KernelSmootherBuilder::new(SmoothingKernel::Gaussian { sigma: 1. }, 1)
+            .build()
+            .expect("smoother");
+    assert_eq!(
+        smoother.density([(cell, 1.), (parent, 1.)]),
+        Err(ResolutionMismatch)
+    );
+    assert_eq!(
+        smoother.smooth([(cell, 1.), (parent, 1.)]),
+        Err(ResolutionMismatch)
+    );
+
+    let smoother =
+        KernelSmootherBuilder::new(SmoothingKernel::Gaussian { sigma: 1. }, 1)
+            .output_resolution(Resolution::Eleven)
+            .build()
+            .expect("smoother");
+    assert_eq!(smoother.density([(cell, 1.)]), Err(ResolutionMismatch));
+    assert_eq!(smoother.smooth([(cell, 1.)]), Err(ResolutionMismatch));
+}
+
+#[test]
+fn invalid_bandwidth() {
+    for bandwidth in [0., -1., f64::NAN, f64::INFINITY] {
+        for kernel in [
+            SmoothingKernel::Uniform { bandwidth },
+            SmoothingKernel::Triangular { bandwidth },
+            SmoothingKernel::Epanechnikov { bandwidth },
+            SmoothingKernel::Gaussian { sigma: bandwidth },
+        ] {
+            assert!(
+                KernelSmootherBuilder::new(kernel, 1).build().is_err(),
+                "{kernel:?}"
+            );
+        }
+    }
+}
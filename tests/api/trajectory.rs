@@ -0,0 +1,215 @@
+use h3o::{
+    CellIndex, LatLng, Resolution, TrajectoryBuilder, error::UnorderedTimestamp,
+};
+use std::num::NonZeroU64;
+
+/// Checks that consecutive visits are neighbors and share their boundaries.
+fn assert_contiguous(trajectory: &h3o::Trajectory) {
+    for pair in trajectory.visits().windows(2) {
+        assert!(
+            pair[0]
+                .cell
+                .is_neighbor_with(pair[1].cell)
+                .expect("same res"),
+            "{} -> {}",
+            pair[0].cell,
+            pair[1].cell
+        );
+        assert_eq!(pair[0].exit, pair[1].entry);
+        assert!(pair[0].entry <= pair[0].exit);
+    }
+}
+
+#[test]
+fn run_length_encoding() {
+    let mut trajectory = TrajectoryBuilder::new(Resolution::Nine).build();
+    let a = LatLng::new(48.8566, 2.3522).expect("a");
+    let b = LatLng::new(48.85661, 2.35221).expect("b");
+    let cell = a.to_cell(Resolution::Nine);
+    assert_eq!(b.to_cell(Resolution::Nine), cell);
+
+    trajectory
+        .push_batch([(a, 10), (b, 20), (a, 20), (b, 35)])
+        .expect("ordered");
+
+    let visits = trajectory.visits();
+    assert_eq!(visits.len(), 1);
+    assert_eq!(visits[0].cell, cell);
+    assert_eq!((visits[0].entry, visits[0].exit), (10, 35));
+    assert_eq!(visits[0].point_count, 4);
+    assert!(!visits[0].is_interpolated());
+    assert_eq!(visits[0].dwell(), 25);
+}
+
+#[test]
+fn neighbors_split_halfway() {
+    let src = CellIndex::try_from(0x8a1fb46622dffff).expect("src");
+    let dst = CellIndex::try_from(0x8a1fb46622d7fff).expect("dst");
+    let mut trajectory = TrajectoryBuilder::new(Resolution::Ten).build();
+    trajectory
+        .push_batch([(src.into(), 0), (dst.into(), 100)])
+        .expect("ordered");
+
+    let visits = trajectory.visits();
+    assert_eq!(visits.len(), 2);
+    assert_eq!((visits[0].entry, visits[0].exit), (0, 50));
+    assert_eq!((visits[1].entry, visits[1].exit), (50, 100));
+}
+
+#[test]
+fn gap_filling() {
+    let src = LatLng::new(48.8566, 2.3522).expect("src");
+    let dst = LatLng::new(48.8738, 2.2950).expect("dst");
+    let mut trajectory = TrajectoryBuilder::new(Resolution::Nine).build();
+    trajectory
+        .push_batch([(src, 1_000), (dst, 2_000)])
+        .expect("ordered");
+
+    let visits = trajectory.visits();
+    assert!(visits.len() > 2);
+    assert_eq!(visits[0].cell, src.to_cell(Resolution::Nine));
+    assert_eq!(
+        visits.last().expect("dst").cell,
+        dst.to_cell(Resolution::Nine)
+    );
+    assert_eq!(visits[0].entry, 1_000);
+    assert_eq!(visits.last().expect("dst").exit, 2_000);
+    assert!(
+        visits[1..visits.len() - 1]
+            .iter()
+            .all(|v| v.is_interpolated())
+    );
+    assert_contiguous(&trajectory);
+}
+
+#[test]
+fn gap_filling_disabled() {
+    let src = LatLng::new(48.8566, 2.3522).expect("src");
+    let dst = LatLng::new(48.8738, 2.2950).expect("dst");
+    let mut trajectory = TrajectoryBuilder::new(Resolution::Nine)
+        .disable_gap_filling()
+        .build();
+    trajectory
+        .push_batch([(src, 0), (dst, 10)])
+        .expect("ordered");
+
+    let visits = trajectory.visits();
+    assert_eq!(visits.len(), 2);
+    assert_eq!((visits[0].entry, visits[0].exit), (0, 5));
+    assert_eq!((visits[1].entry, visits[1].exit), (5, 10));
+}
+
+#[test]
+fn gap_filling_max_gap() {
+    let src = LatLng::new(48.8566, 2.3522).expect("src");
+    let dst = LatLng::new(48.8738, 2.2950).expect("dst");
+    let gap = src
+        .to_cell(Resolution::Nine)
+        .grid_distance(dst.to_cell(Resolution::Nine))
+        .expect("distance")
+        - 1;
+    let gap = u32::try_from(gap).expect("positive gap");
+
+    let mut trajectory = TrajectoryBuilder::new(Resolution::Nine)
+        .max_gap(gap)
+        .build();
+    trajectory
+        .push_batch([(src, 0), (dst, 10)])
+        .expect("ordered");
+    assert_eq!(trajectory.visits().len(), gap as usize + 2);
+    assert_contiguous(&trajectory);
+
+    let mut trajectory = TrajectoryBuilder::new(Resolution::Nine)
+        .max_gap(gap - 1)
+        .build();
+    trajectory
+        .push_batch([(src, 0), (dst, 10)])
+        .expect("ordered");
+    let visits = trajectory.visits();
+    assert_eq!(visits.len(), 2);
+    assert_eq!((visits[0].entry, visits[0].exit), (0, 5));
+    assert_eq!((visits[1].entry, visits[1].exit), (5, 10));
+}
+
+#[test]
+fn gap_filling_around_pentagon() {
+    let pentagon = CellIndex::try_from(0x8009fffffffffff)
+        .expect("pentagon")
+        .center_child(Resolution::Five)
+        .expect("center child");
+    assert!(pentagon.is_pentagon());
+
+    // Cross the pentagon between opposite sides.
+    let ring = pentagon.grid_ring::<Vec<_>>(3);
+    let mut trajectory = TrajectoryBuilder::new(Resolution::Five).build();
+    for (time, &cell) in (0..).step_by(1_000).zip(&ring) {
+        let opposite =
+            ring[(ring.len() / 2 + time as usize / 1_000) % ring.len()];
+        trajectory.push(cell.into(), 2 * time).expect("ordered");
+        trajectory
+            .push(opposite.into(), 2 * time + 1_000)
+            .expect("ordered");
+    }
+
+    assert_contiguous(&trajectory);
+}
+
+#[test]
+fn dwell_times() {
+    let a = LatLng::new(48.8566, 2.3522).expect("a");
+    let b = LatLng::new(48.8738, 2.2950).expect("b");
+    let mut trajectory = TrajectoryBuilder::new(Resolution::Nine).build();
+    trajectory
+        .push_batch([(a, 0), (a, 100), (b, 200), (b, 300), (a, 400)])
+        .expect("ordered");
+
+    let dwell_times = trajectory.dwell_times();
+    assert!(dwell_times.is_sorted_by_key(|&(cell, _)| cell));
+    assert_eq!(dwell_times.iter().map(|(_, time)| time).sum::<u64>(), 400);
+
+    let cell = a.to_cell(Resolution::Nine);
+    let visited = trajectory
+        .visits()
+        .iter()
+        .filter(|visit| visit.cell == cell)
+        .map(|visit| visit.dwell())
+        .sum::<u64>();
+    let dwell = dwell_times
+        .iter()
+        .find_map(|&(c, time)| (c == cell).then_some(time));
+    assert_eq!(dwell, Some(visited));
+    assert!(visited > 100);
+}
+
+#[test]
+fn time_buckets() {
+    let src = CellIndex::try_from(0x8a1fb46622dffff).expect("src");
+    let dst = CellIndex::try_from(0x8a1fb46622d7fff).expect("dst");
+    let mut trajectory = TrajectoryBuilder::new(Resolution::Ten).build();
+    trajectory
+        .push_batch([(src.into(), 0), (dst.into(), 100), (dst.into(), 120)])
+        .expect("ordered");
+
+    let window = NonZeroU64::new(30).expect("window");
+    assert_eq!(
+        trajectory.time_buckets(window),
+        vec![(src, 0), (dst, 1), (src, 1), (dst, 2), (dst, 3)]
+    );
+
+    // Instantaneous visits still cover their bucket.
+    let mut trajectory = TrajectoryBuilder::new(Resolution::Ten).build();
+    trajectory.push(src.into(), 42).expect("ordered");
+    assert_eq!(trajectory.time_buckets(window), vec![(src, 1)]);
+}
+
+#[test]
+fn unordered_timestamps() {
+    let a = LatLng::new(48.8566, 2.3522).expect("a");
+    let b = LatLng::new(48.8738, 2.2950).expect("b");
+    let mut trajectory = TrajectoryBuilder::new(Resolution::Nine).build();
+
+    let result = trajectory.push_batch([(a, 10), (b, 20), (a, 15)]);
+    assert_eq!(result, Err(UnorderedTimestamp));
+    assert_eq!(trajectory.visits().last().expect("b").exit, 20);
+    assert!(trajectory.push(a, 20).is_ok());
+}
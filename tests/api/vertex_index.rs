@@ -1,4 +1,4 @@
-use h3o::VertexIndex;
+use h3o::{CellIndex, Vertex, VertexIndex, VertexIndexBuilder};
 
 #[test]
 fn try_from_str() {
@@ -37,3 +37,29 @@ fn display() {
             .to_owned();
     assert_eq!(result, expected, "binary");
 }
+
+#[test]
+fn builder() {
+    let cell = CellIndex::try_from(0x8a1fb46622dffff).expect("cell");
+    for (i, expected) in cell.vertexes().enumerate() {
+        let vertex =
+            Vertex::try_from(u8::try_from(i).expect("vertex")).expect("vertex");
+        let result = VertexIndexBuilder::new(cell, vertex).build();
+        assert_eq!(result, Ok(expected));
+    }
+
+    // Vertexes are expressed relative to their canonical owner.
+    let vertex = Vertex::try_from(3).expect("vertex");
+    let index = VertexIndexBuilder::new(cell, vertex)
+        .build()
+        .expect("index");
+    assert_ne!(index.owner(), cell);
+    assert_eq!(VertexIndex::try_from(u64::from(index)), Ok(index));
+
+    // Pentagons only have 5 vertexes.
+    let pentagon = CellIndex::try_from(0x8009fffffffffff).expect("pentagon");
+    let builder = VertexIndexBuilder::new(cell, vertex).cell(pentagon);
+    assert!(builder.build().is_ok());
+    let vertex = Vertex::try_from(5).expect("vertex");
+    assert!(builder.vertex(vertex).build().is_err());
+}
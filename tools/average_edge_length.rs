@@ -1,13 +1,32 @@
 //! Compute average H3 cell edge length.
 //!
 //! Inspired from https://gist.github.com/mciethan/3e10802c1f41972831c325994d97ef27
-use h3o::{CellIndex, DirectedEdgeIndex, Resolution};
+use h3o::{CellIndex, DirectedEdgeIndex, EarthModel, Resolution};
 use polyfit_rs::polyfit_rs::polyfit;
 
 fn main() {
+    // Spherical model by default, `wgs84` for the ellipsoidal one.
+    if std::env::args().nth(1).as_deref() == Some("wgs84") {
+        for avg_m in averages(|edge| edge.length_m_with(EarthModel::Wgs84)) {
+            let avg_km = avg_m / 1000.;
+            println!("{avg_km},{avg_m}");
+        }
+        return;
+    }
+
+    for avg in averages(DirectedEdgeIndex::length_rads) {
+        let avg_km = avg * h3o::EARTH_RADIUS_KM;
+        let avg_m = avg_km * 1000.;
+        println!("{avg},{avg_km},{avg_m}");
+    }
+}
+
+/// Computes the average edge length at every resolution, using the given
+/// length function.
+fn averages(length: impl Fn(DirectedEdgeIndex) -> f64 + Copy) -> Vec<f64> {
     // Compute the exact average for the resolution 0 to 6.
     let mut averages = Resolution::range(Resolution::Zero, Resolution::Six)
-        .map(avg_edge_len_at_res)
+        .map(|resolution| avg_edge_len_at_res(resolution, length))
         .collect::<Vec<_>>();
     // Extrapolate values for finer resolutions.
     let x = [0., 1., 2., 3., 4., 5., 6.];
@@ -16,11 +35,7 @@ fn main() {
     for resolution in 7..=15_u8 {
         averages.push((coeffs[1] * f64::from(resolution) + coeffs[0]).exp())
     }
-    for avg in averages {
-        let avg_km = avg * h3o::EARTH_RADIUS_KM;
-        let avg_m = avg_km * 1000.;
-        println!("{avg},{avg_km},{avg_m}");
-    }
+    averages
 }
 
 /// Returns every edge at the given resolution.
@@ -31,11 +46,14 @@ fn edges_at_res(
         .flat_map(|cell| cell.edges())
 }
 
-/// Compute the average edge length (in radians) at the given resolution.
-fn avg_edge_len_at_res(resolution: Resolution) -> f64 {
+/// Compute the average edge length at the given resolution.
+fn avg_edge_len_at_res(
+    resolution: Resolution,
+    length: impl Fn(DirectedEdgeIndex) -> f64,
+) -> f64 {
     let (count, sum) = edges_at_res(resolution)
         .fold((0, 0.), |(count, sum), edge| {
-            (count + 1, sum + edge.length_rads())
+            (count + 1, sum + length(edge))
         });
     sum / count as f64
 }